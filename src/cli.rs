@@ -14,24 +14,111 @@ pub(crate) struct Cli {
 #[derive(Args, Debug, Clone, Default)]
 pub struct IgnoreOptions {
     /// Don't respect .gitignore files
-    #[arg(long)]
+    #[arg(long, overrides_with = "ignore_git")]
     pub no_ignore_git: bool,
 
+    /// Respect .gitignore files, even if the configuration says not to
+    #[arg(long, overrides_with = "no_ignore_git")]
+    pub ignore_git: bool,
+
     /// Don't respect .ignore files (ripgrep/ag format)
-    #[arg(long)]
+    #[arg(long, overrides_with = "ignore")]
     pub no_ignore: bool,
 
+    /// Respect .ignore files, even if the configuration says not to
+    #[arg(long, overrides_with = "no_ignore")]
+    pub ignore: bool,
+
     /// Don't respect global gitignore files
-    #[arg(long)]
+    #[arg(long, overrides_with = "ignore_global")]
     pub no_ignore_global: bool,
 
+    /// Respect global gitignore files, even if the configuration says not to
+    #[arg(long, overrides_with = "no_ignore_global")]
+    pub ignore_global: bool,
+
     /// Don't automatically ignore hidden files and directories
-    #[arg(long)]
+    #[arg(long, overrides_with = "no_hidden")]
     pub hidden: bool,
 
+    /// Ignore hidden files and directories, even if the configuration says not to
+    #[arg(long, overrides_with = "hidden")]
+    pub no_hidden: bool,
+
     /// Disable all ignore filtering (show everything)
-    #[arg(long)]
+    #[arg(long, overrides_with = "ignore_all")]
     pub no_ignore_all: bool,
+
+    /// Apply ignore filtering, even if the configuration disables it
+    #[arg(long, overrides_with = "no_ignore_all")]
+    pub ignore_all: bool,
+
+    /// Skip packages whose directory (relative to the workspace root) matches a glob - can be used multiple times
+    #[arg(long)]
+    pub exclude: Vec<String>,
+}
+
+impl IgnoreOptions {
+    /// Fill in defaults from the project configuration; flags given on the command line win
+    ///
+    /// Each ignore flag or its negation overrides the configured value, and
+    /// `--exclude` patterns replace the configured ones.
+    pub fn with_config(mut self, config: &crate::io::config::Config) -> Self {
+        let flag = |set: bool, unset: bool, configured: bool| set || (!unset && configured);
+        self.no_ignore_git = flag(self.no_ignore_git, self.ignore_git, config.no_ignore_git);
+        self.no_ignore = flag(self.no_ignore, self.ignore, config.no_ignore);
+        self.no_ignore_global = flag(
+            self.no_ignore_global,
+            self.ignore_global,
+            config.no_ignore_global,
+        );
+        self.hidden = flag(self.hidden, self.no_hidden, config.hidden);
+        self.no_ignore_all = flag(self.no_ignore_all, self.ignore_all, config.no_ignore_all);
+        if self.exclude.is_empty() {
+            self.exclude = config.exclude.clone();
+        }
+        self
+    }
+}
+
+/// Controls whether an operation writes its manifest edits to disk
+#[derive(Args, Debug, Clone, Default)]
+pub(crate) struct WriteOptions {
+    /// Show the changes and a diff of each manifest without writing anything
+    #[arg(long)]
+    pub(crate) dry_run: bool,
+
+    /// Exit with a non-zero status if any manifest would change (implies --dry-run)
+    #[arg(long, conflicts_with = "dry_run")]
+    pub(crate) check: bool,
+
+    /// Create an annotated git tag for each changed package on the new commit (requires --commit)
+    #[arg(long, requires = "commit")]
+    pub(crate) tag: bool,
+
+    /// Tag name template for --tag and `roll auto`; {name} and {version} are replaced [default: {name}-v{version}]
+    #[arg(long)]
+    pub(crate) tag_template: Option<String>,
+
+    /// Add a section for each new version to the package's CHANGELOG.md
+    #[arg(long)]
+    pub(crate) changelog: bool,
+
+    /// Commit the manifests that were changed after saving
+    #[arg(long)]
+    pub(crate) commit: bool,
+
+    /// Commit message template; {packages}, {changes} and {operation} are replaced
+    #[arg(long, requires = "commit")]
+    pub(crate) commit_message: Option<String>,
+
+    /// Allow --commit with unrelated uncommitted changes in the working directory
+    #[arg(long, requires = "commit")]
+    pub(crate) allow_dirty: bool,
+
+    /// Also bump packages that depend on changed ones (major if a dependency makes a breaking change) [default level: patch]
+    #[arg(long, value_name = "LEVEL", num_args = 0..=1, default_missing_value = "patch")]
+    pub(crate) cascade: Option<BumpLevel>,
 }
 
 #[derive(Clone, Debug, ValueEnum, Default)]
@@ -43,6 +130,47 @@ pub(crate) enum OutputFormat {
     Json,
 }
 
+#[derive(Clone, Debug, ValueEnum, Default)]
+pub(crate) enum GraphFormat {
+    /// Indented tree of each member's dependencies
+    #[default]
+    Text,
+    /// Graphviz DOT
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+    /// JSON format for scripting
+    Json,
+}
+
+#[derive(Clone, Debug, ValueEnum, Default)]
+pub(crate) enum ShowFormat {
+    /// One `name: version` line per member (default)
+    #[default]
+    Text,
+    /// JSON array of members for scripting
+    Json,
+    /// Aligned columns with a header row
+    Table,
+    /// Members nested by directory
+    Tree,
+    /// Tab-separated values with a header row
+    Tsv,
+}
+
+#[derive(Clone, Debug, ValueEnum, Default)]
+pub(crate) enum LintFormat {
+    /// One line per problem (default)
+    #[default]
+    Text,
+    /// JSON array of findings for scripting
+    Json,
+    /// SARIF log for code scanning
+    Sarif,
+    /// GitHub Actions workflow annotations
+    Github,
+}
+
 #[derive(Subcommand)]
 pub(crate) enum Commands {
     /// Increment version numbers
@@ -59,9 +187,12 @@ pub(crate) enum Commands {
         #[command(flatten)]
         package_selection: PackageSelection,
 
-        /// Output format
-        #[arg(long, default_value = "simple")]
-        format: OutputFormat,
+        /// Output format [default: simple]
+        #[arg(long)]
+        format: Option<OutputFormat>,
+
+        #[command(flatten)]
+        write_options: WriteOptions,
 
         #[command(flatten)]
         ignore_options: IgnoreOptions,
@@ -72,9 +203,12 @@ pub(crate) enum Commands {
         /// Version to sync all crates to (e.g., "1.2.3")
         version: String,
 
-        /// Output format
-        #[arg(long, default_value = "simple")]
-        format: OutputFormat,
+        /// Output format [default: simple]
+        #[arg(long)]
+        format: Option<OutputFormat>,
+
+        #[command(flatten)]
+        write_options: WriteOptions,
 
         #[command(flatten)]
         ignore_options: IgnoreOptions,
@@ -82,6 +216,10 @@ pub(crate) enum Commands {
 
     /// Display current versions for workspace members
     Show {
+        /// Output format
+        #[arg(long, default_value = "text")]
+        format: ShowFormat,
+
         #[command(flatten)]
         package_selection: PackageSelection,
 
@@ -89,14 +227,102 @@ pub(crate) enum Commands {
         ignore_options: IgnoreOptions,
     },
 
-    /// Check for missing/malformed version fields
+    /// Check versions and dependency requirements against the lint rules
     Lint {
+        /// Also require every selected package to share one version
+        #[arg(long)]
+        lockstep: bool,
+
+        /// Repair the problems that have an automatic fix, then report the rest
+        #[arg(long)]
+        fix: bool,
+
+        /// Output format
+        #[arg(long, default_value = "text")]
+        format: LintFormat,
+
+        #[command(flatten)]
+        package_selection: PackageSelection,
+
+        #[command(flatten)]
+        ignore_options: IgnoreOptions,
+    },
+
+    /// Display how workspace members depend on each other, with a release order
+    Graph {
+        /// Output format
+        #[arg(long, default_value = "text")]
+        format: GraphFormat,
+
+        #[command(flatten)]
+        ignore_options: IgnoreOptions,
+    },
+
+    /// Record, apply and check intended releases in .changeset files
+    Changeset {
+        #[command(subcommand)]
+        command: ChangesetCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum ChangesetCommand {
+    /// Write a changeset releasing the selected packages at a bump level
+    Add {
+        /// How far the selected packages should move in the next release
+        level: BumpLevel,
+
+        /// Summary of the change, used for the changelog
+        #[arg(short = 'm', long)]
+        message: String,
+
         #[command(flatten)]
         package_selection: PackageSelection,
 
         #[command(flatten)]
         ignore_options: IgnoreOptions,
     },
+
+    /// Apply all pending changesets: bump versions, write changelogs and delete the files
+    Version {
+        /// Output format [default: simple]
+        #[arg(long)]
+        format: Option<OutputFormat>,
+
+        #[command(flatten)]
+        write_options: WriteOptions,
+
+        #[command(flatten)]
+        ignore_options: IgnoreOptions,
+    },
+
+    /// List pending releases; fail if packages changed since a git ref lack a changeset
+    Status {
+        /// Git ref to compare HEAD against
+        #[arg(long, value_name = "REF", default_value = "main")]
+        since: String,
+
+        #[command(flatten)]
+        ignore_options: IgnoreOptions,
+    },
+}
+
+/// A release level, as recorded in a changeset or cascaded to dependents
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub(crate) enum BumpLevel {
+    Major,
+    Minor,
+    Patch,
+}
+
+impl From<BumpLevel> for crate::domain::conventional::Level {
+    fn from(level: BumpLevel) -> Self {
+        match level {
+            BumpLevel::Major => Self::Major,
+            BumpLevel::Minor => Self::Minor,
+            BumpLevel::Patch => Self::Patch,
+        }
+    }
 }
 
 #[derive(Args, Debug)]
@@ -112,6 +338,14 @@ pub(crate) struct PackageSelection {
     /// Alias for --workspace (cargo compatibility)
     #[arg(long = "all", conflicts_with = "packages")]
     pub(crate) all: bool,
+
+    /// Select members with files changed on HEAD since it branched off a git ref
+    #[arg(long, value_name = "REF", conflicts_with_all = ["packages", "workspace", "all"])]
+    pub(crate) changed_since: Option<String>,
+
+    /// With --changed-since, also select members that depend on changed ones
+    #[arg(long, requires = "changed_since")]
+    pub(crate) include_dependents: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -128,9 +362,12 @@ pub(crate) enum BumpType {
         #[command(flatten)]
         ignore_options: IgnoreOptions,
 
-        /// Output format
-        #[arg(long, default_value = "simple")]
-        format: OutputFormat,
+        /// Output format [default: simple]
+        #[arg(long)]
+        format: Option<OutputFormat>,
+
+        #[command(flatten)]
+        write_options: WriteOptions,
     },
 
     /// Increment minor version (x.y.0)
@@ -145,9 +382,12 @@ pub(crate) enum BumpType {
         #[command(flatten)]
         ignore_options: IgnoreOptions,
 
-        /// Output format
-        #[arg(long, default_value = "simple")]
-        format: OutputFormat,
+        /// Output format [default: simple]
+        #[arg(long)]
+        format: Option<OutputFormat>,
+
+        #[command(flatten)]
+        write_options: WriteOptions,
     },
 
     /// Increment patch version (x.y.z)
@@ -162,9 +402,139 @@ pub(crate) enum BumpType {
         #[command(flatten)]
         ignore_options: IgnoreOptions,
 
-        /// Output format
-        #[arg(long, default_value = "simple")]
-        format: OutputFormat,
+        /// Output format [default: simple]
+        #[arg(long)]
+        format: Option<OutputFormat>,
+
+        #[command(flatten)]
+        write_options: WriteOptions,
+    },
+
+    /// Start a prerelease of the next major version (x.0.0-alpha.0)
+    Premajor {
+        /// Prerelease identifier (e.g. alpha, beta, rc)
+        #[arg(default_value = "alpha")]
+        identifier: String,
+
+        #[command(flatten)]
+        package_selection: PackageSelection,
+
+        #[command(flatten)]
+        ignore_options: IgnoreOptions,
+
+        /// Output format [default: simple]
+        #[arg(long)]
+        format: Option<OutputFormat>,
+
+        #[command(flatten)]
+        write_options: WriteOptions,
+    },
+
+    /// Start a prerelease of the next minor version (x.y.0-alpha.0)
+    Preminor {
+        /// Prerelease identifier (e.g. alpha, beta, rc)
+        #[arg(default_value = "alpha")]
+        identifier: String,
+
+        #[command(flatten)]
+        package_selection: PackageSelection,
+
+        #[command(flatten)]
+        ignore_options: IgnoreOptions,
+
+        /// Output format [default: simple]
+        #[arg(long)]
+        format: Option<OutputFormat>,
+
+        #[command(flatten)]
+        write_options: WriteOptions,
+    },
+
+    /// Start a prerelease of the next patch version (x.y.z-alpha.0)
+    Prepatch {
+        /// Prerelease identifier (e.g. alpha, beta, rc)
+        #[arg(default_value = "alpha")]
+        identifier: String,
+
+        #[command(flatten)]
+        package_selection: PackageSelection,
+
+        #[command(flatten)]
+        ignore_options: IgnoreOptions,
+
+        /// Output format [default: simple]
+        #[arg(long)]
+        format: Option<OutputFormat>,
+
+        #[command(flatten)]
+        write_options: WriteOptions,
+    },
+
+    /// Increment the prerelease counter, or move to another prerelease channel
+    Pre {
+        /// Prerelease channel to move to (e.g. beta); defaults to the current channel
+        identifier: Option<String>,
+
+        #[command(flatten)]
+        package_selection: PackageSelection,
+
+        #[command(flatten)]
+        ignore_options: IgnoreOptions,
+
+        /// Output format [default: simple]
+        #[arg(long)]
+        format: Option<OutputFormat>,
+
+        #[command(flatten)]
+        write_options: WriteOptions,
+    },
+
+    /// Move to the release candidate channel (shorthand for `pre rc`)
+    Rc {
+        #[command(flatten)]
+        package_selection: PackageSelection,
+
+        #[command(flatten)]
+        ignore_options: IgnoreOptions,
+
+        /// Output format [default: simple]
+        #[arg(long)]
+        format: Option<OutputFormat>,
+
+        #[command(flatten)]
+        write_options: WriteOptions,
+    },
+
+    /// Infer each package's bump from its Conventional Commits since its last release tag
+    Auto {
+        #[command(flatten)]
+        package_selection: PackageSelection,
+
+        #[command(flatten)]
+        ignore_options: IgnoreOptions,
+
+        /// Output format [default: simple]
+        #[arg(long)]
+        format: Option<OutputFormat>,
+
+        #[command(flatten)]
+        write_options: WriteOptions,
+    },
+
+    /// Drop the prerelease identifier (x.y.z-rc.1 → x.y.z)
+    Release {
+        #[command(flatten)]
+        package_selection: PackageSelection,
+
+        #[command(flatten)]
+        ignore_options: IgnoreOptions,
+
+        /// Output format [default: simple]
+        #[arg(long)]
+        format: Option<OutputFormat>,
+
+        #[command(flatten)]
+        write_options: WriteOptions,
     },
 }
 
@@ -174,7 +544,8 @@ impl From<BumpType>
         crate::domain::VersionBump,
         crate::domain::PackageSelection,
         IgnoreOptions,
-        OutputFormat,
+        Option<OutputFormat>,
+        WriteOptions,
     )
 {
     fn from(bump_type: BumpType) -> Self {
@@ -184,41 +555,130 @@ impl From<BumpType>
                 package_selection,
                 ignore_options,
                 format,
+                write_options,
             } => (
                 crate::domain::VersionBump::Major(amount),
                 package_selection.into(),
                 ignore_options,
                 format,
+                write_options,
             ),
             BumpType::Minor {
                 amount,
                 package_selection,
                 ignore_options,
                 format,
+                write_options,
             } => (
                 crate::domain::VersionBump::Minor(amount),
                 package_selection.into(),
                 ignore_options,
                 format,
+                write_options,
             ),
             BumpType::Patch {
                 amount,
                 package_selection,
                 ignore_options,
                 format,
+                write_options,
             } => (
                 crate::domain::VersionBump::Patch(amount),
                 package_selection.into(),
                 ignore_options,
                 format,
+                write_options,
+            ),
+            BumpType::Premajor {
+                identifier,
+                package_selection,
+                ignore_options,
+                format,
+                write_options,
+            } => (
+                crate::domain::VersionBump::Premajor(identifier),
+                package_selection.into(),
+                ignore_options,
+                format,
+                write_options,
+            ),
+            BumpType::Preminor {
+                identifier,
+                package_selection,
+                ignore_options,
+                format,
+                write_options,
+            } => (
+                crate::domain::VersionBump::Preminor(identifier),
+                package_selection.into(),
+                ignore_options,
+                format,
+                write_options,
+            ),
+            BumpType::Prepatch {
+                identifier,
+                package_selection,
+                ignore_options,
+                format,
+                write_options,
+            } => (
+                crate::domain::VersionBump::Prepatch(identifier),
+                package_selection.into(),
+                ignore_options,
+                format,
+                write_options,
+            ),
+            BumpType::Pre {
+                identifier,
+                package_selection,
+                ignore_options,
+                format,
+                write_options,
+            } => (
+                crate::domain::VersionBump::Prerelease(identifier),
+                package_selection.into(),
+                ignore_options,
+                format,
+                write_options,
+            ),
+            BumpType::Rc {
+                package_selection,
+                ignore_options,
+                format,
+                write_options,
+            } => (
+                crate::domain::VersionBump::Prerelease(Some("rc".to_string())),
+                package_selection.into(),
+                ignore_options,
+                format,
+                write_options,
+            ),
+            BumpType::Release {
+                package_selection,
+                ignore_options,
+                format,
+                write_options,
+            } => (
+                crate::domain::VersionBump::Release,
+                package_selection.into(),
+                ignore_options,
+                format,
+                write_options,
             ),
+            // The bump is inferred per package, so `roll auto` is dispatched before conversion
+            BumpType::Auto { .. } => unreachable!("roll auto has no single version bump"),
         }
     }
 }
 
 impl From<PackageSelection> for crate::domain::PackageSelection {
     fn from(selection: PackageSelection) -> Self {
-        if !selection.packages.is_empty() {
+        if let Some(reference) = selection.changed_since {
+            crate::domain::PackageSelection::ChangedSince {
+                reference,
+                include_dependents: selection.include_dependents,
+            }
+        } else if !selection.packages.is_empty() {
             crate::domain::PackageSelection::Specific(selection.packages)
         } else if selection.workspace || selection.all {
             crate::domain::PackageSelection::Workspace
@@ -227,3 +687,52 @@ impl From<PackageSelection> for crate::domain::PackageSelection {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::config::Config;
+
+    #[derive(Parser)]
+    struct Flags {
+        #[command(flatten)]
+        ignore_options: IgnoreOptions,
+    }
+
+    fn ignore_options(args: &[&str], config: &Config) -> IgnoreOptions {
+        let args = ["odo"].iter().chain(args);
+        Flags::parse_from(args).ignore_options.with_config(config)
+    }
+
+    #[test]
+    fn test_ignore_options_with_config() {
+        let config = Config {
+            no_ignore_git: true,
+            hidden: true,
+            exclude: vec!["vendor/*".to_string()],
+            ..Default::default()
+        };
+
+        let options = ignore_options(&[], &config);
+        assert!(options.no_ignore_git && options.hidden && !options.no_ignore);
+        assert_eq!(options.exclude, vec!["vendor/*"]);
+
+        // Negations turn configured flags off, and --exclude replaces the list
+        let options = ignore_options(
+            &[
+                "--ignore-git",
+                "--no-hidden",
+                "--no-ignore",
+                "--exclude",
+                "examples/*",
+            ],
+            &config,
+        );
+        assert!(!options.no_ignore_git && !options.hidden && options.no_ignore);
+        assert_eq!(options.exclude, vec!["examples/*"]);
+
+        // The last of a flag and its negation wins
+        let options = ignore_options(&["--ignore-git", "--no-ignore-git"], &Config::default());
+        assert!(options.no_ignore_git);
+    }
+}
@@ -0,0 +1,169 @@
+//! Intent-to-release files, in the format popularized by Changesets
+//!
+//! Each file names the packages it releases and their bump levels in a front
+//! matter block, followed by a summary for the changelog:
+//!
+//! ```text
+//! ---
+//! "core": minor
+//! "cli": patch
+//! ---
+//!
+//! Add array support to the parser
+//! ```
+
+use super::conventional::Level;
+use anyhow::Context;
+
+/// A pending release recorded in a changeset file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Changeset {
+    /// File stem, e.g. "add-array-support"
+    pub id: String,
+    /// Packages to release and their bump levels, in file order
+    pub releases: Vec<(String, Level)>,
+    pub summary: String,
+}
+
+impl Changeset {
+    /// Parse the contents of a changeset file
+    pub fn parse(id: &str, content: &str) -> anyhow::Result<Self> {
+        let content = content.replace("\r\n", "\n");
+        let rest = content
+            .strip_prefix("---\n")
+            .with_context(|| format!("Changeset '{}' does not start with '---'", id))?;
+        let (front_matter, summary) = match rest.split_once("\n---") {
+            Some((front_matter, summary)) => (front_matter, summary),
+            None if rest.starts_with("---") => ("", &rest[3..]),
+            None => anyhow::bail!("Changeset '{}' has no closing '---'", id),
+        };
+
+        let mut releases: Vec<(String, Level)> = Vec::new();
+        for line in front_matter.lines().map(str::trim) {
+            if line.is_empty() {
+                continue;
+            }
+            let (package, level) = line
+                .rsplit_once(':')
+                .with_context(|| format!("Invalid line in changeset '{}': {}", id, line))?;
+            let package = package.trim().trim_matches(|c| c == '"' || c == '\'');
+            let level: Level = level
+                .trim()
+                .parse()
+                .with_context(|| format!("Invalid line in changeset '{}': {}", id, line))?;
+
+            match releases.iter_mut().find(|(name, _)| name == package) {
+                Some((_, existing)) => *existing = (*existing).max(level),
+                None => releases.push((package.to_string(), level)),
+            }
+        }
+
+        Ok(Self {
+            id: id.to_string(),
+            releases,
+            summary: summary.trim().to_string(),
+        })
+    }
+
+    /// Render the changeset as file contents
+    pub fn render(&self) -> String {
+        let mut content = String::from("---\n");
+        for (package, level) in &self.releases {
+            content.push_str(&format!("\"{}\": {}\n", package, level));
+        }
+        content.push_str("---\n\n");
+        content.push_str(self.summary.trim());
+        content.push('\n');
+        content
+    }
+}
+
+/// The highest level each package is released at across `changesets`
+///
+/// Packages are listed in the order they first appear.
+pub fn highest_levels(changesets: &[Changeset]) -> Vec<(String, Level)> {
+    let mut levels: Vec<(String, Level)> = Vec::new();
+    for (package, level) in changesets.iter().flat_map(|c| &c.releases) {
+        match levels.iter_mut().find(|(name, _)| name == package) {
+            Some((_, existing)) => *existing = (*existing).max(*level),
+            None => levels.push((package.clone(), *level)),
+        }
+    }
+    levels
+}
+
+/// Turn a summary into a file stem, e.g. "Add array support" → "add-array-support"
+pub fn slug(summary: &str) -> String {
+    let words: Vec<String> = summary
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .take(6)
+        .map(str::to_ascii_lowercase)
+        .collect();
+
+    if words.is_empty() {
+        "changeset".to_string()
+    } else {
+        words.join("-")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_render() {
+        let content = "---\n\"core\": minor\n'cli': PATCH\n@scope/web: major\ncore: patch\n---\n\nAdd arrays\n\nMore detail.\n";
+        let changeset = Changeset::parse("add-arrays", content).unwrap();
+        assert_eq!(
+            changeset.releases,
+            vec![
+                ("core".to_string(), Level::Minor),
+                ("cli".to_string(), Level::Patch),
+                ("@scope/web".to_string(), Level::Major),
+            ]
+        );
+        assert_eq!(changeset.summary, "Add arrays\n\nMore detail.");
+        assert_eq!(
+            changeset.render(),
+            "---\n\"core\": minor\n\"cli\": patch\n\"@scope/web\": major\n---\n\nAdd arrays\n\nMore detail.\n"
+        );
+
+        let empty = Changeset::parse("empty", "---\n---\n\nDocs only\n").unwrap();
+        assert!(empty.releases.is_empty());
+        assert_eq!(empty.summary, "Docs only");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Changeset::parse("a", "core: minor\n").is_err());
+        assert!(Changeset::parse("a", "---\ncore: minor\n").is_err());
+        let err = Changeset::parse("a", "---\ncore: huge\n---\nx\n").unwrap_err();
+        assert!(format!("{:#}", err).contains("Unknown bump level 'huge'"));
+    }
+
+    #[test]
+    fn test_highest_levels() {
+        let changesets = vec![
+            Changeset::parse("a", "---\ncore: patch\ncli: minor\n---\na\n").unwrap(),
+            Changeset::parse("b", "---\ncore: major\n---\nb\n").unwrap(),
+        ];
+        assert_eq!(
+            highest_levels(&changesets),
+            vec![
+                ("core".to_string(), Level::Major),
+                ("cli".to_string(), Level::Minor),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_slug() {
+        assert_eq!(slug("Add array support!\nDetails"), "add-array-support");
+        assert_eq!(slug("  ...  "), "changeset");
+    }
+}
@@ -0,0 +1,293 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use super::VersionBump;
+
+/// The release level a commit calls for under Conventional Commits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    /// `fix:`
+    Patch,
+    /// `feat:`
+    Minor,
+    /// `!` after the type/scope, or a `BREAKING CHANGE:` footer
+    Major,
+}
+
+/// How breaking changes and features are treated while a package is still at 0.x
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ZeroMajor {
+    /// Shift levels down one place, as Cargo does: breaking changes bump the
+    /// minor version and features the patch version
+    #[default]
+    Shift,
+    /// Bump exactly as for 1.0 and later, so a breaking change releases 1.0.0
+    Normal,
+}
+
+/// A commit from a package's history
+#[derive(Debug, Clone, PartialEq)]
+pub struct Commit {
+    pub hash: String,
+    pub message: String,
+}
+
+/// A commit that contributed to a bump decision
+#[derive(Debug, Clone, Serialize)]
+pub struct DrivingCommit {
+    pub hash: String,
+    pub summary: String,
+    pub level: Level,
+}
+
+/// Why `roll auto` did (or did not) bump a package
+#[derive(Debug, Clone, Serialize)]
+pub struct BumpDecision {
+    pub package: String,
+    /// The release tag history was read from, or `None` for the full history
+    pub since: Option<String>,
+    /// Highest level among the package's commits, before 0.x semantics are applied
+    pub level: Option<Level>,
+    /// The bump that was applied (e.g. "minor"), if any
+    pub bump: Option<String>,
+    pub commits: Vec<DrivingCommit>,
+    /// The workspace root whose version the package inherits, and whose
+    /// decision its commits count toward
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inherits: Option<String>,
+}
+
+impl BumpDecision {
+    /// Build the decision for `package` from the commits since its last release
+    pub fn from_commits(package: &str, since: Option<String>, commits: &[Commit]) -> Self {
+        let commits: Vec<DrivingCommit> = commits
+            .iter()
+            .filter_map(|commit| {
+                classify(&commit.message).map(|level| DrivingCommit {
+                    hash: commit.hash.clone(),
+                    summary: commit
+                        .message
+                        .lines()
+                        .next()
+                        .unwrap_or_default()
+                        .to_string(),
+                    level,
+                })
+            })
+            .collect();
+
+        Self {
+            package: package.to_string(),
+            since,
+            level: commits.iter().map(|commit| commit.level).max(),
+            bump: None,
+            commits,
+            inherits: None,
+        }
+    }
+
+    /// The decision for `package`, which inherits its version from `root`
+    pub fn inherited(package: &str, root: &str) -> Self {
+        Self {
+            package: package.to_string(),
+            since: None,
+            level: None,
+            bump: None,
+            commits: Vec::new(),
+            inherits: Some(root.to_string()),
+        }
+    }
+}
+
+impl Level {
+    /// The bump for this level applied to `version`, honoring `zero_major`
+    pub fn to_bump(self, version: &str, zero_major: ZeroMajor) -> VersionBump {
+        let initial_development = zero_major == ZeroMajor::Shift
+            && semver::Version::parse(version).is_ok_and(|v| v.major == 0);
+
+        match (self, initial_development) {
+            (Level::Major, false) => VersionBump::Major(1),
+            (Level::Major, true) | (Level::Minor, false) => VersionBump::Minor(1),
+            (Level::Minor, true) | (Level::Patch, _) => VersionBump::Patch(1),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Patch => "patch",
+            Level::Minor => "minor",
+            Level::Major => "major",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Level {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "patch" => Ok(Level::Patch),
+            "minor" => Ok(Level::Minor),
+            "major" => Ok(Level::Major),
+            _ => anyhow::bail!(
+                "Unknown bump level '{}' (expected major, minor or patch)",
+                s
+            ),
+        }
+    }
+}
+
+/// The parts of a Conventional Commits subject line (`type(scope)!: description`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConventionalCommit<'a> {
+    pub commit_type: &'a str,
+    pub scope: Option<&'a str>,
+    /// `!` after the type/scope, or a `BREAKING CHANGE:` footer
+    pub breaking: bool,
+    pub description: &'a str,
+}
+
+/// Parse a commit message per the Conventional Commits specification
+///
+/// Returns `None` for messages that don't follow the convention.
+pub fn parse(message: &str) -> Option<ConventionalCommit<'_>> {
+    let subject = message.lines().next()?;
+    let (prefix, description) = subject.split_once(':')?;
+
+    let (prefix, bang) = match prefix.strip_suffix('!') {
+        Some(prefix) => (prefix, true),
+        None => (prefix, false),
+    };
+    let (commit_type, scope) = match prefix.split_once('(') {
+        Some((commit_type, scope)) => (commit_type, Some(scope.strip_suffix(')')?)),
+        None => (prefix, None),
+    };
+    if commit_type.is_empty()
+        || !commit_type
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+    {
+        return None;
+    }
+
+    let breaking_footer = message
+        .lines()
+        .skip(1)
+        .any(|line| line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:"));
+
+    Some(ConventionalCommit {
+        commit_type,
+        scope,
+        breaking: bang || breaking_footer,
+        description: description.trim(),
+    })
+}
+
+/// Classify a commit message per the Conventional Commits specification
+///
+/// Returns `None` for commit types that don't call for a release (`chore`,
+/// `docs`, ...) and for messages that don't follow the convention.
+pub fn classify(message: &str) -> Option<Level> {
+    let commit = parse(message)?;
+
+    if commit.breaking {
+        return Some(Level::Major);
+    }
+    match commit.commit_type.to_ascii_lowercase().as_str() {
+        "feat" => Some(Level::Minor),
+        "fix" => Some(Level::Patch),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(hash: &str, message: &str) -> Commit {
+        Commit {
+            hash: hash.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify("fix: handle empty input"), Some(Level::Patch));
+        assert_eq!(classify("feat(parser): add arrays"), Some(Level::Minor));
+        assert_eq!(classify("Feat: shouting"), Some(Level::Minor));
+        assert_eq!(classify("refactor!: drop old API"), Some(Level::Major));
+        assert_eq!(classify("feat(api)!: new signature"), Some(Level::Major));
+        assert_eq!(
+            classify("fix: tweak\n\nBREAKING CHANGE: config moved"),
+            Some(Level::Major)
+        );
+        assert_eq!(classify("chore: bump deps"), None);
+        assert_eq!(classify("docs(readme): typo"), None);
+        assert_eq!(classify("Merge branch 'main'"), None);
+        assert_eq!(classify("fix typo: in readme"), None);
+        assert_eq!(classify("feat(oops: unclosed"), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("feat(api)!: new signature\n\nbody"),
+            Some(ConventionalCommit {
+                commit_type: "feat",
+                scope: Some("api"),
+                breaking: true,
+                description: "new signature",
+            })
+        );
+        assert_eq!(parse("fix: crash").unwrap().scope, None);
+        assert_eq!(parse("Merge branch 'main'"), None);
+    }
+
+    #[test]
+    fn test_level_to_bump() {
+        let shift = ZeroMajor::Shift;
+        assert_eq!(Level::Major.to_bump("1.2.3", shift), VersionBump::Major(1));
+        assert_eq!(Level::Minor.to_bump("1.2.3", shift), VersionBump::Minor(1));
+        assert_eq!(Level::Major.to_bump("0.2.3", shift), VersionBump::Minor(1));
+        assert_eq!(Level::Minor.to_bump("0.2.3", shift), VersionBump::Patch(1));
+        assert_eq!(Level::Patch.to_bump("0.2.3", shift), VersionBump::Patch(1));
+
+        let normal = ZeroMajor::Normal;
+        assert_eq!(Level::Major.to_bump("0.2.3", normal), VersionBump::Major(1));
+        assert_eq!(Level::Minor.to_bump("0.2.3", normal), VersionBump::Minor(1));
+    }
+
+    #[test]
+    fn test_bump_decision_from_commits() {
+        let commits = vec![
+            commit("c3", "chore: release"),
+            commit("c2", "feat: add flag\n\nDetails."),
+            commit("c1", "fix: crash"),
+        ];
+
+        let decision = BumpDecision::from_commits("core", Some("core-v1.0.0".into()), &commits);
+        assert_eq!(decision.level, Some(Level::Minor));
+        assert_eq!(decision.commits.len(), 2);
+        assert_eq!(decision.commits[0].hash, "c2");
+        assert_eq!(decision.commits[0].summary, "feat: add flag");
+
+        let decision = BumpDecision::from_commits("core", None, &commits[..1]);
+        assert_eq!(decision.level, None);
+        assert!(decision.commits.is_empty());
+        assert!(serde_json::to_value(&decision)
+            .unwrap()
+            .get("inherits")
+            .is_none());
+
+        let decision = BumpDecision::inherited("core", "ws");
+        assert_eq!(decision.level, None);
+        assert_eq!(serde_json::to_value(&decision).unwrap()["inherits"], "ws");
+    }
+}
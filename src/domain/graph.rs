@@ -0,0 +1,505 @@
+//! The dependency graph between workspace members
+//!
+//! Edges join a member to the members it depends on, matched by name within
+//! the same ecosystem. Development dependencies are kept in the graph but
+//! don't constrain the release order and don't count towards cycles, since
+//! publishing never needs them first.
+
+use super::{Ecosystem, VersionField, Workspace};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+/// A workspace member in the dependency graph
+#[derive(Debug, Clone, Serialize)]
+pub struct Node {
+    pub name: String,
+    pub ecosystem: Ecosystem,
+    /// The member's own version, if it declares one
+    pub version: Option<String>,
+    pub path: PathBuf,
+}
+
+/// A dependency of one member on another, by node index
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    /// The dependency table it is declared in (e.g. "dependencies", "devDependencies")
+    pub table: String,
+    pub requirement: Option<String>,
+    pub dev: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct DependencyGraph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+impl DependencyGraph {
+    /// Build the graph of dependencies between the members of `workspace`
+    ///
+    /// A member declaring the same dependency in several tables gets one edge
+    /// per table. Nodes are in workspace order.
+    pub fn from_workspace(workspace: &Workspace) -> Self {
+        let nodes = workspace
+            .members
+            .iter()
+            .map(|member| Node {
+                name: member.name().to_string(),
+                ecosystem: member.ecosystem(),
+                version: match member.version() {
+                    VersionField::Concrete(version) => Some(version.clone()),
+                    _ => None,
+                },
+                path: member.path().clone(),
+            })
+            .collect();
+
+        let mut edges = Vec::new();
+        for (from, member) in workspace.members.iter().enumerate() {
+            for dependency in member.dependencies() {
+                let to = workspace.members.iter().position(|m| {
+                    m.name() == dependency.name && m.ecosystem() == member.ecosystem()
+                });
+                if let Some(to) = to {
+                    edges.push(Edge {
+                        from,
+                        to,
+                        table: dependency.table.clone(),
+                        requirement: dependency.requirement.clone(),
+                        dev: dependency.is_dev(),
+                    });
+                }
+            }
+        }
+
+        Self { nodes, edges }
+    }
+
+    /// Indices of the nodes `node` depends on outside development, without duplicates
+    fn dependencies_of(&self, node: usize) -> Vec<usize> {
+        let targets: BTreeSet<usize> = self
+            .edges
+            .iter()
+            .filter(|edge| edge.from == node && !edge.dev)
+            .map(|edge| edge.to)
+            .collect();
+        targets.into_iter().collect()
+    }
+
+    /// An order to release members in, dependencies before their dependents
+    ///
+    /// Ties keep workspace order. Members on a cycle can't be ordered; they
+    /// come last, in workspace order.
+    pub fn release_order(&self) -> Vec<usize> {
+        let mut remaining: Vec<usize> = (0..self.nodes.len())
+            .map(|node| self.dependencies_of(node).len())
+            .collect();
+        let mut ready: BTreeSet<usize> = (0..self.nodes.len())
+            .filter(|&node| remaining[node] == 0)
+            .collect();
+
+        let mut order = Vec::new();
+        while let Some(node) = ready.pop_first() {
+            order.push(node);
+            for (dependent, count) in remaining.iter_mut().enumerate() {
+                if self.dependencies_of(dependent).contains(&node) {
+                    *count -= 1;
+                    if *count == 0 {
+                        ready.insert(dependent);
+                    }
+                }
+            }
+        }
+
+        let ordered: BTreeSet<usize> = order.iter().copied().collect();
+        order.extend((0..self.nodes.len()).filter(|node| !ordered.contains(node)));
+        order
+    }
+
+    /// Groups of members that depend on each other in a circle, outside development
+    ///
+    /// Each cycle lists its members in workspace order; a member depending on
+    /// itself is a cycle of one.
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let mut tarjan = Tarjan {
+            graph: self,
+            index: 0,
+            indices: vec![None; self.nodes.len()],
+            lowlinks: vec![0; self.nodes.len()],
+            stack: Vec::new(),
+            on_stack: vec![false; self.nodes.len()],
+            components: Vec::new(),
+        };
+        for node in 0..self.nodes.len() {
+            if tarjan.indices[node].is_none() {
+                tarjan.connect(node);
+            }
+        }
+
+        let mut cycles: Vec<Vec<usize>> = tarjan
+            .components
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1 || self.dependencies_of(component[0]).contains(&component[0])
+            })
+            .map(|mut component| {
+                component.sort();
+                component
+            })
+            .collect();
+        cycles.sort();
+        cycles
+    }
+
+    /// Render the graph as a Graphviz DOT digraph
+    pub fn to_dot(&self) -> String {
+        let mut output = String::from("digraph workspace {\n");
+        for index in 0..self.nodes.len() {
+            output.push_str(&format!(
+                "  n{} [label=\"{}\"];\n",
+                index,
+                escape(&self.label(index))
+            ));
+        }
+        for edge in &self.edges {
+            let style = if edge.dev {
+                " [style=dashed, label=\"dev\"]"
+            } else {
+                ""
+            };
+            output.push_str(&format!("  n{} -> n{}{};\n", edge.from, edge.to, style));
+        }
+        for line in self.summary() {
+            output.push_str(&format!("  // {}\n", line));
+        }
+        output.push_str("}\n");
+        output
+    }
+
+    /// Render the graph as a Mermaid flowchart
+    pub fn to_mermaid(&self) -> String {
+        let mut output = String::from("graph TD\n");
+        for index in 0..self.nodes.len() {
+            output.push_str(&format!(
+                "  n{}[\"{}\"]\n",
+                index,
+                self.label(index).replace('"', "#quot;")
+            ));
+        }
+        for edge in &self.edges {
+            let arrow = if edge.dev { "-.->|dev|" } else { "-->" };
+            output.push_str(&format!("  n{} {} n{}\n", edge.from, arrow, edge.to));
+        }
+        for line in self.summary() {
+            output.push_str(&format!("  %% {}\n", line));
+        }
+        output
+    }
+
+    /// Render the graph as an indented tree of each top-level member's dependencies
+    ///
+    /// Top-level members are the ones no other member depends on outside
+    /// development; members on a cycle with no other way in are shown as well.
+    /// A member whose dependencies were already listed is marked `(*)`.
+    pub fn to_text(&self) -> String {
+        let depended_on: BTreeSet<usize> = self
+            .edges
+            .iter()
+            .filter(|edge| !edge.dev && edge.from != edge.to)
+            .map(|edge| edge.to)
+            .collect();
+
+        let mut output = String::new();
+        let mut expanded = BTreeSet::new();
+        for node in (0..self.nodes.len()).filter(|node| !depended_on.contains(node)) {
+            self.write_tree(node, 0, None, &mut Vec::new(), &mut expanded, &mut output);
+        }
+        // Whatever is left is only reachable through a cycle
+        for node in 0..self.nodes.len() {
+            if !expanded.contains(&node) {
+                self.write_tree(node, 0, None, &mut Vec::new(), &mut expanded, &mut output);
+            }
+        }
+
+        output.push('\n');
+        for line in self.summary() {
+            output.push_str(&line);
+            output.push('\n');
+        }
+        output
+    }
+
+    fn write_tree(
+        &self,
+        node: usize,
+        depth: usize,
+        edge: Option<&Edge>,
+        path: &mut Vec<usize>,
+        expanded: &mut BTreeSet<usize>,
+        output: &mut String,
+    ) {
+        let mut line = format!("{}{}", "  ".repeat(depth), self.label(node));
+        if edge.is_some_and(|edge| edge.dev) {
+            line.push_str(" [dev]");
+        }
+
+        if path.contains(&node) {
+            output.push_str(&format!("{} (cycle)\n", line));
+            return;
+        }
+        if !expanded.insert(node) {
+            let has_children = self.edges.iter().any(|edge| edge.from == node);
+            if has_children {
+                line.push_str(" (*)");
+            }
+            output.push_str(&format!("{}\n", line));
+            return;
+        }
+        output.push_str(&format!("{}\n", line));
+
+        path.push(node);
+        for edge in self.edges.iter().filter(|edge| edge.from == node) {
+            self.write_tree(edge.to, depth + 1, Some(edge), path, expanded, output);
+        }
+        path.pop();
+    }
+
+    /// "name version", or just the name for members without a version of their own
+    fn label(&self, index: usize) -> String {
+        let node = &self.nodes[index];
+        match &node.version {
+            Some(version) => format!("{} {}", node.name, version),
+            None => node.name.clone(),
+        }
+    }
+
+    /// The release order and cycles, one line each
+    fn summary(&self) -> Vec<String> {
+        let names = |nodes: &[usize]| {
+            nodes
+                .iter()
+                .map(|&node| self.nodes[node].name.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        let mut lines = vec![format!(
+            "Release order: {}",
+            names(&self.release_order()).join(", ")
+        )];
+        let cycles = self.cycles();
+        if cycles.is_empty() {
+            lines.push("Cycles: none".to_string());
+        }
+        for cycle in cycles {
+            let mut members = names(&cycle);
+            members.push(members[0]);
+            lines.push(format!("Cycle: {}", members.join(" → ")));
+        }
+        lines
+    }
+
+    /// The graph as plain data for JSON output, with members referred to by name
+    pub fn report(&self) -> GraphReport<'_> {
+        let name = |node: usize| self.nodes[node].name.as_str();
+        GraphReport {
+            nodes: &self.nodes,
+            edges: self
+                .edges
+                .iter()
+                .map(|edge| EdgeReport {
+                    from: name(edge.from),
+                    to: name(edge.to),
+                    table: &edge.table,
+                    requirement: edge.requirement.as_deref(),
+                    dev: edge.dev,
+                })
+                .collect(),
+            release_order: self.release_order().into_iter().map(name).collect(),
+            cycles: self
+                .cycles()
+                .into_iter()
+                .map(|cycle| cycle.into_iter().map(name).collect())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct GraphReport<'a> {
+    pub nodes: &'a [Node],
+    pub edges: Vec<EdgeReport<'a>>,
+    pub release_order: Vec<&'a str>,
+    pub cycles: Vec<Vec<&'a str>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EdgeReport<'a> {
+    pub from: &'a str,
+    pub to: &'a str,
+    pub table: &'a str,
+    pub requirement: Option<&'a str>,
+    pub dev: bool,
+}
+
+/// Escape a label for a double-quoted DOT string
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Tarjan's strongly connected components over non-development edges
+struct Tarjan<'a> {
+    graph: &'a DependencyGraph,
+    index: usize,
+    indices: Vec<Option<usize>>,
+    lowlinks: Vec<usize>,
+    stack: Vec<usize>,
+    on_stack: Vec<bool>,
+    components: Vec<Vec<usize>>,
+}
+
+impl Tarjan<'_> {
+    fn connect(&mut self, node: usize) {
+        self.indices[node] = Some(self.index);
+        self.lowlinks[node] = self.index;
+        self.index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+
+        for next in self.graph.dependencies_of(node) {
+            match self.indices[next] {
+                None => {
+                    self.connect(next);
+                    self.lowlinks[node] = self.lowlinks[node].min(self.lowlinks[next]);
+                }
+                Some(index) if self.on_stack[next] => {
+                    self.lowlinks[node] = self.lowlinks[node].min(index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if Some(self.lowlinks[node]) == self.indices[node] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Dependency, WorkspaceMember};
+
+    fn member(name: &str, dependencies: &[(&str, &str)]) -> WorkspaceMember {
+        WorkspaceMember::Cargo {
+            name: name.to_string(),
+            path: PathBuf::from(format!("/ws/{}", name)),
+            version: VersionField::Concrete("1.0.0".to_string()),
+            dependencies: dependencies
+                .iter()
+                .map(|(name, table)| Dependency {
+                    name: name.to_string(),
+                    requirement: Some("1.0".to_string()),
+                    table: table.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    fn graph(members: Vec<WorkspaceMember>) -> DependencyGraph {
+        DependencyGraph::from_workspace(&Workspace {
+            members,
+            ..Default::default()
+        })
+    }
+
+    fn names(graph: &DependencyGraph, nodes: &[usize]) -> Vec<String> {
+        nodes
+            .iter()
+            .map(|&node| graph.nodes[node].name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_release_order() {
+        let graph = graph(vec![
+            member("app", &[("cli", "dependencies"), ("serde", "dependencies")]),
+            member("cli", &[("core", "dependencies")]),
+            member("core", &[("app", "dev-dependencies")]),
+            member("macros", &[]),
+        ]);
+
+        // External dependencies have no edges; dev edges are kept but marked
+        assert_eq!(graph.edges.len(), 3);
+        assert!(graph.edges[2].dev);
+
+        assert_eq!(
+            names(&graph, &graph.release_order()),
+            vec!["core", "cli", "app", "macros"]
+        );
+        assert!(graph.cycles().is_empty());
+    }
+
+    #[test]
+    fn test_cycles() {
+        let graph = graph(vec![
+            member("a", &[("b", "dependencies")]),
+            member("b", &[("a", "build-dependencies")]),
+            member("c", &[("a", "dependencies")]),
+            member("d", &[("d", "dependencies")]),
+        ]);
+
+        let cycles = graph.cycles();
+        assert_eq!(cycles.len(), 2);
+        assert_eq!(names(&graph, &cycles[0]), vec!["a", "b"]);
+        assert_eq!(names(&graph, &cycles[1]), vec!["d"]);
+
+        // Members on a cycle, and those depending on them, come last
+        assert_eq!(
+            names(&graph, &graph.release_order()),
+            vec!["a", "b", "c", "d"]
+        );
+        assert!(graph.to_text().contains("Cycle: a → b → a\n"));
+    }
+
+    #[test]
+    fn test_render() {
+        let graph = graph(vec![
+            member("app", &[("core", "dependencies")]),
+            member("core", &[]),
+            member("tool", &[("core", "dev-dependencies")]),
+        ]);
+
+        assert_eq!(
+            graph.to_text(),
+            "app 1.0.0\n  core 1.0.0\ntool 1.0.0\n  core 1.0.0 [dev]\n\nRelease order: core, app, tool\nCycles: none\n"
+        );
+        assert_eq!(
+            graph.to_dot(),
+            "digraph workspace {\n  n0 [label=\"app 1.0.0\"];\n  n1 [label=\"core 1.0.0\"];\n  n2 [label=\"tool 1.0.0\"];\n  n0 -> n1;\n  n2 -> n1 [style=dashed, label=\"dev\"];\n  // Release order: core, app, tool\n  // Cycles: none\n}\n"
+        );
+        assert_eq!(
+            graph.to_mermaid(),
+            "graph TD\n  n0[\"app 1.0.0\"]\n  n1[\"core 1.0.0\"]\n  n2[\"tool 1.0.0\"]\n  n0 --> n1\n  n2 -.->|dev| n1\n  %% Release order: core, app, tool\n  %% Cycles: none\n"
+        );
+
+        let json = serde_json::to_value(graph.report()).unwrap();
+        assert_eq!(json["edges"][1]["from"], "tool");
+        assert_eq!(json["edges"][1]["dev"], true);
+        assert_eq!(
+            json["release_order"],
+            serde_json::json!(["core", "app", "tool"])
+        );
+        assert_eq!(json["cycles"], serde_json::json!([]));
+    }
+}
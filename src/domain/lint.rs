@@ -0,0 +1,889 @@
+use super::scheme::VersionScheme;
+use super::{
+    compare_versions, requirement_accepts, rewrite_requirement, Ecosystem, GroupKind,
+    PackageSelection, VersionField, Workspace, WorkspaceMember,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// How serious a lint finding is; only errors make `odo lint` fail
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// A lint rule, identified by its `id` in output and configuration
+#[derive(Debug)]
+pub struct Rule {
+    pub id: &'static str,
+    pub description: &'static str,
+    /// Severity of its findings unless configured otherwise
+    pub severity: Severity,
+    /// Only checked with `--lockstep`
+    pub lockstep: bool,
+}
+
+/// Every lint rule, in the order they are checked
+pub const RULES: &[Rule] = &[
+    Rule {
+        id: "missing-version",
+        description: "Packages declare a version",
+        severity: Severity::Warning,
+        lockstep: false,
+    },
+    Rule {
+        id: "invalid-version",
+        description: "Versions are valid in the package's versioning scheme",
+        severity: Severity::Error,
+        lockstep: false,
+    },
+    Rule {
+        id: "group-drift",
+        description: "Members of a fixed group share one version",
+        severity: Severity::Error,
+        lockstep: false,
+    },
+    Rule {
+        id: "requirement-mismatch",
+        description: "Requirements on workspace members accept their current version",
+        severity: Severity::Error,
+        lockstep: false,
+    },
+    Rule {
+        id: "lockstep-drift",
+        description: "All selected packages share one version",
+        severity: Severity::Error,
+        lockstep: true,
+    },
+    Rule {
+        id: "inheritable-version",
+        description: "Cargo members inherit the `[workspace.package]` version",
+        severity: Severity::Error,
+        lockstep: true,
+    },
+    Rule {
+        id: "unknown-group-member",
+        description: "Groups only list workspace packages",
+        severity: Severity::Error,
+        lockstep: false,
+    },
+];
+
+/// Look up a rule by id
+pub fn rule(id: &str) -> Option<&'static Rule> {
+    RULES.iter().find(|rule| rule.id == id)
+}
+
+/// Which rules run and how severe their findings are
+#[derive(Debug, Clone, Default)]
+pub struct LintOptions {
+    /// Also check the lockstep rules
+    pub lockstep: bool,
+    /// Severities overriding the rules' defaults, keyed by rule id
+    pub severities: HashMap<&'static str, Severity>,
+}
+
+impl LintOptions {
+    /// Options with severities configured per rule id, rejecting unknown ids
+    pub fn new(lockstep: bool, severities: &BTreeMap<String, Severity>) -> anyhow::Result<Self> {
+        let mut options = Self {
+            lockstep,
+            ..Default::default()
+        };
+        for (id, &severity) in severities {
+            let rule = rule(id).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown lint rule '{}' (expected one of: {})",
+                    id,
+                    RULES.iter().map(|r| r.id).collect::<Vec<_>>().join(", ")
+                )
+            })?;
+            options.severities.insert(rule.id, severity);
+        }
+        Ok(options)
+    }
+
+    fn severity(&self, rule: &'static str) -> Severity {
+        self.severities
+            .get(rule)
+            .copied()
+            .or_else(|| self::rule(rule).map(|r| r.severity))
+            .unwrap_or(Severity::Error)
+    }
+}
+
+/// A problem found by a lint rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// Id of the rule that found it
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub member: String,
+    pub message: String,
+    /// Manifest the problem was found in (empty when it isn't tied to a manifest)
+    pub path: PathBuf,
+    /// Key path of the offending value, such as `dependencies.core`
+    pub location: Option<String>,
+    /// Line of `location` in the manifest, when known (filled in by the caller)
+    pub line: Option<usize>,
+    /// Edit that repairs the problem, when one is known
+    pub fix: Option<Fix>,
+}
+
+impl Diagnostic {
+    fn new(rule: &'static str, member: &WorkspaceMember, message: String) -> Self {
+        Self {
+            rule,
+            severity: Severity::Error,
+            member: member.name().to_string(),
+            message,
+            path: member.manifest_path(),
+            location: None,
+            line: None,
+            fix: None,
+        }
+    }
+
+    /// The manifest path relative to `root`, with `/` separators
+    pub fn relative_path(&self, root: &Path) -> Option<String> {
+        if self.path.as_os_str().is_empty() {
+            return None;
+        }
+        let relative = self.path.strip_prefix(root).unwrap_or(&self.path);
+        Some(
+            relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+        )
+    }
+
+    /// This diagnostic as reported by `--format json`, with its path relative to `root`
+    pub fn finding(&self, root: &Path) -> Finding<'_> {
+        Finding {
+            rule: self.rule,
+            severity: self.severity,
+            member: &self.member,
+            path: self.relative_path(root),
+            location: self.location.as_deref(),
+            line: self.line,
+            message: &self.message,
+            fixable: self.fix.is_some(),
+        }
+    }
+}
+
+/// A diagnostic as reported by `--format json`
+#[derive(Debug, Serialize)]
+pub struct Finding<'a> {
+    pub rule: &'a str,
+    pub severity: Severity,
+    pub member: &'a str,
+    pub path: Option<String>,
+    pub location: Option<&'a str>,
+    pub line: Option<usize>,
+    pub message: &'a str,
+    pub fixable: bool,
+}
+
+/// Render `diagnostics` as a SARIF 2.1.0 log for code scanning, with paths relative to `root`
+pub fn to_sarif(diagnostics: &[Diagnostic], root: &Path) -> serde_json::Value {
+    let level = |severity: Severity| match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note",
+    };
+
+    let rules: Vec<_> = RULES
+        .iter()
+        .map(|rule| {
+            serde_json::json!({
+                "id": rule.id,
+                "shortDescription": { "text": rule.description },
+                "defaultConfiguration": { "level": level(rule.severity) },
+            })
+        })
+        .collect();
+
+    let results: Vec<_> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let mut location = serde_json::Map::new();
+            if let Some(path) = diagnostic.relative_path(root) {
+                location.insert(
+                    "physicalLocation".to_string(),
+                    serde_json::json!({
+                        "artifactLocation": { "uri": path, "uriBaseId": "%SRCROOT%" },
+                        "region": { "startLine": diagnostic.line.unwrap_or(1) },
+                    }),
+                );
+            }
+            if let Some(key) = &diagnostic.location {
+                location.insert(
+                    "logicalLocations".to_string(),
+                    serde_json::json!([{ "fullyQualifiedName": key, "kind": "member" }]),
+                );
+            }
+            serde_json::json!({
+                "ruleId": diagnostic.rule,
+                "ruleIndex": RULES.iter().position(|r| r.id == diagnostic.rule),
+                "level": level(diagnostic.severity),
+                "message": { "text": format!("{}: {}", diagnostic.member, diagnostic.message) },
+                "locations": if location.is_empty() { vec![] } else { vec![location] },
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "odometer",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_HOMEPAGE"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+/// Render `diagnostics` as GitHub Actions workflow commands, one annotation per line
+///
+/// Paths are relative to `root`, which should be the repository root.
+pub fn to_github(diagnostics: &[Diagnostic], root: &Path) -> String {
+    let mut output = String::new();
+    for diagnostic in diagnostics {
+        let command = match diagnostic.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "notice",
+        };
+        let mut properties = Vec::new();
+        if let Some(path) = diagnostic.relative_path(root) {
+            properties.push(format!("file={}", escape_property(&path)));
+            if let Some(line) = diagnostic.line {
+                properties.push(format!("line={}", line));
+            }
+        }
+        properties.push(format!("title={}", escape_property(diagnostic.rule)));
+
+        let mut message = format!("{}: {}", diagnostic.member, diagnostic.message);
+        if let Some(location) = &diagnostic.location {
+            message.push_str(&format!(" ({})", location));
+        }
+        output.push_str(&format!(
+            "::{} {}::{}\n",
+            command,
+            properties.join(","),
+            escape_data(&message)
+        ));
+    }
+    output
+}
+
+/// Escape the message of a workflow command
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a property value of a workflow command
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// An automatic repair for a diagnostic, applied to its member by `Workspace::apply_fix`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fix {
+    /// Set the version field
+    Version(VersionField),
+    /// Replace the requirement declared on a dependency
+    Requirement {
+        table: String,
+        dependency: String,
+        requirement: String,
+    },
+}
+
+impl Workspace {
+    /// Check the selected members against the lint rules
+    ///
+    /// Members are checked in name order; group entries naming no workspace
+    /// package are reported last.
+    pub fn lint(
+        &self,
+        selection: &PackageSelection,
+        options: &LintOptions,
+    ) -> anyhow::Result<Vec<Diagnostic>> {
+        let indices = self.select_member_indices(selection)?;
+        let mut members: Vec<&WorkspaceMember> =
+            indices.iter().map(|&i| &self.members[i]).collect();
+        members.sort_by(|a, b| a.name().cmp(b.name()));
+
+        let mut diagnostics = Vec::new();
+        for &member in &members {
+            diagnostics.extend(self.lint_version(member));
+            diagnostics.extend(self.lint_requirements(member));
+        }
+
+        if options.lockstep {
+            diagnostics.extend(self.lint_lockstep(&members));
+            diagnostics.extend(self.lint_inheritance(&members));
+        }
+
+        for group in &self.groups {
+            for name in &group.members {
+                if !self.members.iter().any(|m| m.name() == name) {
+                    diagnostics.push(Diagnostic {
+                        rule: "unknown-group-member",
+                        severity: Severity::Error,
+                        member: name.clone(),
+                        message: format!(
+                            "Listed in group '{}' but not found in workspace",
+                            group.name
+                        ),
+                        path: PathBuf::new(),
+                        location: Some(format!("groups.{}.members", group.name)),
+                        line: None,
+                        fix: None,
+                    });
+                }
+            }
+        }
+
+        for diagnostic in &mut diagnostics {
+            diagnostic.severity = options.severity(diagnostic.rule);
+        }
+        Ok(diagnostics)
+    }
+
+    /// Apply the fix of `diagnostic` to its member; returns whether anything changed
+    pub fn apply_fix(&mut self, diagnostic: &Diagnostic) -> bool {
+        let member = match self
+            .members
+            .iter_mut()
+            .find(|m| m.name() == diagnostic.member && m.manifest_path() == diagnostic.path)
+        {
+            Some(member) => member,
+            None => return false,
+        };
+
+        match &diagnostic.fix {
+            Some(Fix::Version(version)) if member.version() != version => {
+                member.set_version(version.clone());
+                true
+            }
+            Some(Fix::Requirement {
+                table,
+                dependency,
+                requirement,
+            }) => {
+                let mut changed = false;
+                for entry in member.dependencies_mut() {
+                    if entry.table == *table
+                        && entry.name == *dependency
+                        && entry.requirement.as_ref() != Some(requirement)
+                    {
+                        entry.requirement = Some(requirement.clone());
+                        changed = true;
+                    }
+                }
+                changed
+            }
+            _ => false,
+        }
+    }
+
+    /// Missing, invalid or drifted version of `member`
+    fn lint_version(&self, member: &WorkspaceMember) -> Option<Diagnostic> {
+        let location = Some(self.version_location(member));
+        let version = match member.version() {
+            VersionField::Concrete(version) => version,
+            VersionField::Absent if self.root.as_ref() != Some(&member.manifest_path()) => {
+                let fix = if self.can_inherit_version(member) {
+                    Some(VersionField::Inherited)
+                } else if *self.scheme(member) == VersionScheme::Semver {
+                    Some(VersionField::Concrete("0.1.0".to_string()))
+                } else {
+                    None
+                };
+                return Some(Diagnostic {
+                    location,
+                    fix: fix.map(Fix::Version),
+                    ..Diagnostic::new("missing-version", member, "No version declared".to_string())
+                });
+            }
+            _ => return None,
+        };
+
+        if let Err(e) = self.scheme(member).validate(version) {
+            return Some(Diagnostic {
+                location,
+                ..Diagnostic::new(
+                    "invalid-version",
+                    member,
+                    format!("Invalid version '{}': {}", version, e),
+                )
+            });
+        }
+
+        let group = self
+            .group_of(member.name())
+            .filter(|group| group.kind == GroupKind::Fixed)?;
+        match self.highest_in_group(group, &[]).map(|m| m.version()) {
+            Some(VersionField::Concrete(expected)) if expected != version => Some(Diagnostic {
+                location,
+                ..Diagnostic::new(
+                    "group-drift",
+                    member,
+                    format!(
+                        "Version '{}' differs from fixed group '{}' ({})",
+                        version, group.name, expected
+                    ),
+                )
+            }),
+            _ => None,
+        }
+    }
+
+    /// Dependencies of `member` on other members whose requirement rejects their current version
+    fn lint_requirements(&self, member: &WorkspaceMember) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for dependency in member.dependencies() {
+            let requirement = match &dependency.requirement {
+                Some(requirement) => requirement,
+                None => continue,
+            };
+            let target = self
+                .members
+                .iter()
+                .find(|m| m.name() == dependency.name && m.ecosystem() == member.ecosystem());
+            let version = match target.and_then(|target| self.resolved_version(target)) {
+                Some(version) => version,
+                None => continue,
+            };
+
+            if requirement_accepts(member.ecosystem(), requirement, version) == Some(false) {
+                let fix = rewrite_requirement(requirement, version)
+                    .filter(|new| {
+                        requirement_accepts(member.ecosystem(), new, version) == Some(true)
+                    })
+                    .map(|new| Fix::Requirement {
+                        table: dependency.table.clone(),
+                        dependency: dependency.name.clone(),
+                        requirement: new,
+                    });
+                diagnostics.push(Diagnostic {
+                    location: Some(format!("{}.{}", dependency.table, dependency.name)),
+                    fix,
+                    ..Diagnostic::new(
+                        "requirement-mismatch",
+                        member,
+                        format!(
+                            "Requirement '{}' on {} does not accept its version {}",
+                            requirement, dependency.name, version
+                        ),
+                    )
+                });
+            }
+        }
+        diagnostics
+    }
+
+    /// Members whose concrete version differs from the one most of `members` share
+    ///
+    /// Ties go to the highest version.
+    fn lint_lockstep(&self, members: &[&WorkspaceMember]) -> Vec<Diagnostic> {
+        let versions: Vec<(&WorkspaceMember, &str)> = members
+            .iter()
+            .filter_map(|&member| match member.version() {
+                VersionField::Concrete(version) => Some((member, version.as_str())),
+                _ => None,
+            })
+            .collect();
+
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for (_, version) in &versions {
+            *counts.entry(version).or_default() += 1;
+        }
+        let (majority, count) = match counts
+            .into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then_with(|| compare_versions(a.0, b.0)))
+        {
+            Some(majority) => majority,
+            None => return Vec::new(),
+        };
+
+        versions
+            .iter()
+            .filter(|(_, version)| *version != majority)
+            .map(|(member, version)| Diagnostic {
+                location: Some(self.version_location(member)),
+                ..Diagnostic::new(
+                    "lockstep-drift",
+                    member,
+                    format!(
+                        "Version '{}' differs from lockstep version {} (shared by {} of {} members)",
+                        version,
+                        majority,
+                        count,
+                        versions.len()
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    /// Cargo members that declare their own version although the workspace root offers one
+    fn lint_inheritance(&self, members: &[&WorkspaceMember]) -> Vec<Diagnostic> {
+        members
+            .iter()
+            .filter(|member| self.can_inherit_version(member))
+            .filter_map(|member| match member.version() {
+                VersionField::Concrete(version) => {
+                    let inherited = self.workspace_version();
+                    // Inheriting a different version would silently change it
+                    let (message, fix) = match inherited {
+                        Some(inherited) if inherited != version => (
+                            format!(
+                                "Declares version '{}' instead of inheriting the workspace \
+                                 version {} (version.workspace = true)",
+                                version, inherited
+                            ),
+                            None,
+                        ),
+                        _ => (
+                            format!(
+                                "Declares version '{}' instead of inheriting the workspace \
+                                 version (version.workspace = true)",
+                                version
+                            ),
+                            Some(Fix::Version(VersionField::Inherited)),
+                        ),
+                    };
+                    Some(Diagnostic {
+                        location: Some(self.version_location(member)),
+                        fix,
+                        ..Diagnostic::new("inheritable-version", member, message)
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether `member` is a Cargo member below a root offering a `[workspace.package]` version
+    fn can_inherit_version(&self, member: &WorkspaceMember) -> bool {
+        match self.root.as_deref().and_then(|root| root.parent()) {
+            Some(root_dir) => {
+                self.inheritable_version
+                    && member.ecosystem() == Ecosystem::Cargo
+                    && member.path() != root_dir
+                    && member.path().starts_with(root_dir)
+            }
+            None => false,
+        }
+    }
+
+    /// Key path of the version field in the manifest of `member`
+    fn version_location(&self, member: &WorkspaceMember) -> String {
+        match member.ecosystem() {
+            Ecosystem::Cargo
+                if self.inheritable_version
+                    && self.root.as_ref() == Some(&member.manifest_path()) =>
+            {
+                "workspace.package.version"
+            }
+            Ecosystem::Cargo => "package.version",
+            Ecosystem::Node => "version",
+            Ecosystem::Python => "project.version",
+        }
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Dependency;
+
+    fn cargo(name: &str, version: VersionField, dependencies: Vec<Dependency>) -> WorkspaceMember {
+        WorkspaceMember::Cargo {
+            name: name.to_string(),
+            path: PathBuf::from(format!("ws/{}", name)),
+            version,
+            dependencies,
+        }
+    }
+
+    fn concrete(version: &str) -> VersionField {
+        VersionField::Concrete(version.to_string())
+    }
+
+    fn dependency(name: &str, requirement: &str) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            requirement: Some(requirement.to_string()),
+            table: "dependencies".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_report_formats() {
+        let workspace = Workspace {
+            members: vec![
+                cargo("app", concrete("1.0.0"), vec![dependency("core", "0.4")]),
+                cargo("core", concrete("0.5.0"), vec![]),
+            ],
+            groups: vec![crate::domain::VersionGroup {
+                name: "core".to_string(),
+                kind: GroupKind::Linked,
+                members: vec!["core".to_string(), "gone, 50%".to_string()],
+            }],
+            ..Default::default()
+        };
+        let mut diagnostics = workspace
+            .lint(&PackageSelection::Workspace, &LintOptions::default())
+            .unwrap();
+        diagnostics[0].line = Some(6);
+        let root = Path::new("ws");
+
+        let findings: Vec<_> = diagnostics.iter().map(|d| d.finding(root)).collect();
+        assert_eq!(
+            serde_json::to_value(&findings).unwrap(),
+            serde_json::json!([
+                {
+                    "rule": "requirement-mismatch",
+                    "severity": "error",
+                    "member": "app",
+                    "path": "app/Cargo.toml",
+                    "location": "dependencies.core",
+                    "line": 6,
+                    "message": "Requirement '0.4' on core does not accept its version 0.5.0",
+                    "fixable": true,
+                },
+                {
+                    "rule": "unknown-group-member",
+                    "severity": "error",
+                    "member": "gone, 50%",
+                    "path": null,
+                    "location": "groups.core.members",
+                    "line": null,
+                    "message": "Listed in group 'core' but not found in workspace",
+                    "fixable": false,
+                },
+            ])
+        );
+
+        let sarif = to_sarif(&diagnostics, root);
+        assert_eq!(sarif["version"], "2.1.0");
+        let results = &sarif["runs"][0]["results"];
+        assert_eq!(results[0]["ruleId"], "requirement-mismatch");
+        assert_eq!(
+            sarif["runs"][0]["tool"]["driver"]["rules"]
+                [results[0]["ruleIndex"].as_u64().unwrap() as usize]["id"],
+            "requirement-mismatch"
+        );
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "app/Cargo.toml"
+        );
+        assert_eq!(
+            results[0]["locations"][0]["logicalLocations"][0]["fullyQualifiedName"],
+            "dependencies.core"
+        );
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["region"]["startLine"],
+            6
+        );
+        assert!(results[1]["locations"][0]["physicalLocation"].is_null());
+
+        assert_eq!(
+            to_github(&diagnostics, root),
+            "::error file=app/Cargo.toml,line=6,title=requirement-mismatch::app: Requirement '0.4' on \
+             core does not accept its version 0.5.0 (dependencies.core)\n\
+             ::error title=unknown-group-member::gone, 50%25: Listed in group 'core' but not \
+             found in workspace (groups.core.members)\n"
+        );
+    }
+
+    #[test]
+    fn test_lint_options() {
+        let severities = BTreeMap::from([("missing-version".to_string(), Severity::Error)]);
+        let options = LintOptions::new(true, &severities).unwrap();
+        assert!(options.lockstep);
+        assert_eq!(options.severity("missing-version"), Severity::Error);
+        assert_eq!(options.severity("requirement-mismatch"), Severity::Error);
+
+        let severities = BTreeMap::from([("no-such-rule".to_string(), Severity::Info)]);
+        let err = LintOptions::new(false, &severities).unwrap_err();
+        assert!(err.to_string().contains("Unknown lint rule 'no-such-rule'"));
+    }
+
+    #[test]
+    fn test_lint_missing_version() {
+        let mut workspace = Workspace {
+            members: vec![
+                cargo("", VersionField::Absent, vec![]),
+                cargo("core", VersionField::Absent, vec![]),
+            ],
+            root: Some(PathBuf::from("ws/Cargo.toml")),
+            ..Default::default()
+        };
+
+        // The workspace root may go without a version
+        let diagnostics = workspace
+            .lint(&PackageSelection::Workspace, &LintOptions::default())
+            .unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, "missing-version");
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[0].member, "core");
+        assert_eq!(diagnostics[0].path, PathBuf::from("ws/core/Cargo.toml"));
+        assert_eq!(diagnostics[0].location.as_deref(), Some("package.version"));
+        assert_eq!(diagnostics[0].fix, Some(Fix::Version(concrete("0.1.0"))));
+
+        workspace.inheritable_version = true;
+        let diagnostics = workspace
+            .lint(&PackageSelection::Workspace, &LintOptions::default())
+            .unwrap();
+        assert_eq!(
+            diagnostics[0].fix,
+            Some(Fix::Version(VersionField::Inherited))
+        );
+
+        assert!(workspace.apply_fix(&diagnostics[0]));
+        assert!(!workspace.apply_fix(&diagnostics[0]));
+        assert_eq!(*workspace.members[1].version(), VersionField::Inherited);
+    }
+
+    #[test]
+    fn test_lint_fixes_requirements() {
+        let mut workspace = Workspace {
+            members: vec![
+                cargo(
+                    "app",
+                    concrete("1.0.0"),
+                    vec![dependency("core", "0.4"), dependency("util", ">=2, <3")],
+                ),
+                cargo("core", concrete("0.5.0"), vec![]),
+                cargo("util", concrete("3.0.0"), vec![]),
+            ],
+            ..Default::default()
+        };
+        let options = LintOptions::new(
+            false,
+            &BTreeMap::from([("requirement-mismatch".to_string(), Severity::Warning)]),
+        )
+        .unwrap();
+
+        let diagnostics = workspace
+            .lint(&PackageSelection::Workspace, &options)
+            .unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Warning));
+        assert_eq!(
+            diagnostics[0].fix,
+            Some(Fix::Requirement {
+                table: "dependencies".to_string(),
+                dependency: "core".to_string(),
+                requirement: "0.5".to_string(),
+            })
+        );
+        // Ranges can't be rewritten
+        assert_eq!(diagnostics[1].fix, None);
+
+        for diagnostic in &diagnostics {
+            workspace.apply_fix(diagnostic);
+        }
+        let diagnostics = workspace
+            .lint(&PackageSelection::Workspace, &options)
+            .unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].location.as_deref(),
+            Some("dependencies.util")
+        );
+    }
+
+    #[test]
+    fn test_lint_inheritance_fix() {
+        let mut workspace = Workspace {
+            members: vec![
+                cargo("", concrete("2.0.0"), vec![]),
+                cargo("core", concrete("2.0.0"), vec![]),
+            ],
+            root: Some(PathBuf::from("ws/Cargo.toml")),
+            inheritable_version: true,
+            ..Default::default()
+        };
+        let options = LintOptions {
+            lockstep: true,
+            ..Default::default()
+        };
+
+        let diagnostics = workspace
+            .lint(&PackageSelection::Workspace, &options)
+            .unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, "inheritable-version");
+        assert_eq!(diagnostics[0].member, "core");
+
+        assert!(workspace.apply_fix(&diagnostics[0]));
+        assert!(workspace
+            .lint(&PackageSelection::Workspace, &options)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_lint_inheritance_without_fix_for_other_version() {
+        let workspace = Workspace {
+            members: vec![
+                cargo("", concrete("1.0.0"), vec![]),
+                cargo("core", concrete("2.0.0"), vec![]),
+            ],
+            root: Some(PathBuf::from("ws/Cargo.toml")),
+            inheritable_version: true,
+            ..Default::default()
+        };
+        let options = LintOptions {
+            lockstep: true,
+            ..Default::default()
+        };
+
+        let diagnostics = workspace
+            .lint(&PackageSelection::Workspace, &options)
+            .unwrap();
+        let inheritance: Vec<_> = diagnostics
+            .iter()
+            .filter(|d| d.rule == "inheritable-version")
+            .collect();
+        assert_eq!(inheritance.len(), 1);
+        assert_eq!(inheritance[0].member, "core");
+        assert!(inheritance[0].message.contains("workspace version 1.0.0"));
+        assert_eq!(inheritance[0].fix, None);
+    }
+}
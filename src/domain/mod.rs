@@ -0,0 +1,3160 @@
+pub mod changeset;
+pub mod conventional;
+pub mod graph;
+pub mod lint;
+pub mod scheme;
+pub mod show;
+
+use anyhow::Context;
+use conventional::{BumpDecision, Level, ZeroMajor};
+use scheme::{Clock, SystemClock, VersionScheme};
+use semver;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::PathBuf;
+
+/// Domain types for version management operations
+#[derive(Debug, Clone, PartialEq)]
+pub enum VersionBump {
+    Major(i32),
+    Minor(i32),
+    Patch(i32),
+    /// Start a prerelease of the next major version (`1.2.3` → `2.0.0-beta.0`)
+    Premajor(String),
+    /// Start a prerelease of the next minor version (`1.2.3` → `1.3.0-beta.0`)
+    Preminor(String),
+    /// Start a prerelease of the next patch version (`1.2.3` → `1.2.4-beta.0`)
+    Prepatch(String),
+    /// Increment the prerelease counter (`-beta.0` → `-beta.1`), or move to another
+    /// channel when an identifier is given (`-beta.1` → `-rc.0`)
+    Prerelease(Option<String>),
+    /// Drop the prerelease (`1.3.0-rc.2` → `1.3.0`)
+    Release,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VersionField {
+    Absent,
+    Concrete(String),
+    Inherited,
+    /// Computed at build time (e.g. pyproject.toml `dynamic = ["version"]`)
+    Dynamic,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PackageSelection {
+    /// Select specific packages by name
+    Specific(Vec<String>),
+    /// Select all workspace members
+    Workspace,
+    /// Default selection (the workspace root)
+    Default,
+    /// Members with files changed since a git ref; resolved into `Changed`
+    /// by the caller before members are selected
+    ChangedSince {
+        reference: String,
+        include_dependents: bool,
+    },
+    /// Members whose directory contains any of `files`, plus (transitively)
+    /// the members that depend on them when `include_dependents` is set
+    Changed {
+        files: Vec<PathBuf>,
+        include_dependents: bool,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionChange {
+    pub package: String,
+    pub old_version: String,
+    pub new_version: String,
+    pub path: PathBuf,
+}
+
+/// A rewritten intra-workspace dependency requirement
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyChange {
+    /// The member whose manifest declares the dependency
+    pub package: String,
+    /// The workspace member depended upon
+    pub dependency: String,
+    /// The dependency table (e.g. "dev-dependencies", "peerDependencies")
+    pub table: String,
+    pub old_requirement: String,
+    pub new_requirement: String,
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OperationResult {
+    pub changes: Vec<VersionChange>,
+    pub dependency_changes: Vec<DependencyChange>,
+    pub operation: String,
+    /// How `roll auto` chose each package's bump
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub decisions: Vec<BumpDecision>,
+    /// Bumps cascaded to dependents of the changed packages
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub propagated: Vec<PropagatedChange>,
+}
+
+impl OperationResult {
+    pub fn new(operation: String) -> Self {
+        Self {
+            changes: Vec::new(),
+            dependency_changes: Vec::new(),
+            operation,
+            decisions: Vec::new(),
+            propagated: Vec::new(),
+        }
+    }
+
+    pub fn add_change(&mut self, change: VersionChange) {
+        self.changes.push(change);
+    }
+
+    pub fn add_dependency_change(&mut self, change: DependencyChange) {
+        self.dependency_changes.push(change);
+    }
+
+    pub fn has_changes(&self) -> bool {
+        !self.changes.is_empty() || !self.propagated.is_empty()
+    }
+
+    /// Every version change, followed by the ones cascaded to dependents
+    pub fn all_changes(&self) -> impl Iterator<Item = &VersionChange> {
+        self.changes
+            .iter()
+            .chain(self.propagated.iter().map(|p| &p.change))
+    }
+}
+
+/// A version change made because a dependency of the package changed
+#[derive(Debug, Clone, Serialize)]
+pub struct PropagatedChange {
+    #[serde(flatten)]
+    pub change: VersionChange,
+    /// The changed dependency that caused this bump
+    pub dependency: String,
+}
+
+/// The package ecosystem a workspace member belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Ecosystem {
+    Cargo,
+    Node,
+    Python,
+}
+
+impl Ecosystem {
+    /// File name of the manifest that describes a package in this ecosystem
+    pub fn manifest_name(&self) -> &'static str {
+        match self {
+            Ecosystem::Cargo => "Cargo.toml",
+            Ecosystem::Node => "package.json",
+            Ecosystem::Python => "pyproject.toml",
+        }
+    }
+}
+
+impl fmt::Display for Ecosystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Ecosystem::Cargo => "cargo",
+            Ecosystem::Node => "node",
+            Ecosystem::Python => "python",
+        })
+    }
+}
+
+/// A dependency declared in a member's manifest
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dependency {
+    /// Name of the package depended upon (after resolving Cargo `package = "..."` renames)
+    pub name: String,
+    /// Version requirement as written, if any (path-only and `workspace = true` entries have none)
+    pub requirement: Option<String>,
+    /// The dependency table (e.g. "dependencies", "target.cfg(unix).dev-dependencies")
+    pub table: String,
+}
+
+impl Dependency {
+    /// Whether the dependency is only used for development (tests, examples, tooling)
+    pub fn is_dev(&self) -> bool {
+        self.table.ends_with("dev-dependencies") || self.table == "devDependencies"
+    }
+}
+
+/// A workspace member, which can be a Rust, Node.js or Python package
+#[derive(Debug, Clone)]
+pub enum WorkspaceMember {
+    Cargo {
+        name: String,
+        path: PathBuf,
+        version: VersionField,
+        dependencies: Vec<Dependency>,
+    },
+    Node {
+        name: String,
+        path: PathBuf,
+        version: VersionField,
+        dependencies: Vec<Dependency>,
+    },
+    Python {
+        name: String,
+        path: PathBuf,
+        version: VersionField,
+        dependencies: Vec<Dependency>,
+    },
+}
+
+impl WorkspaceMember {
+    /// Get the name of the package
+    pub fn name(&self) -> &str {
+        match self {
+            WorkspaceMember::Cargo { name, .. } => name,
+            WorkspaceMember::Node { name, .. } => name,
+            WorkspaceMember::Python { name, .. } => name,
+        }
+    }
+
+    /// Get the path to the package
+    pub fn path(&self) -> &PathBuf {
+        match self {
+            WorkspaceMember::Cargo { path, .. } => path,
+            WorkspaceMember::Node { path, .. } => path,
+            WorkspaceMember::Python { path, .. } => path,
+        }
+    }
+
+    /// Get the version of the package
+    pub fn version(&self) -> &VersionField {
+        match self {
+            WorkspaceMember::Cargo { version, .. } => version,
+            WorkspaceMember::Node { version, .. } => version,
+            WorkspaceMember::Python { version, .. } => version,
+        }
+    }
+
+    /// Set the version of the package
+    pub fn set_version(&mut self, new_version: VersionField) {
+        match self {
+            WorkspaceMember::Cargo { version, .. } => *version = new_version,
+            WorkspaceMember::Node { version, .. } => *version = new_version,
+            WorkspaceMember::Python { version, .. } => *version = new_version,
+        }
+    }
+
+    /// Get the ecosystem of the package
+    pub fn ecosystem(&self) -> Ecosystem {
+        match self {
+            WorkspaceMember::Cargo { .. } => Ecosystem::Cargo,
+            WorkspaceMember::Node { .. } => Ecosystem::Node,
+            WorkspaceMember::Python { .. } => Ecosystem::Python,
+        }
+    }
+
+    /// Get the path to the package manifest
+    pub fn manifest_path(&self) -> PathBuf {
+        self.path().join(self.ecosystem().manifest_name())
+    }
+
+    /// Get the dependencies declared by the package
+    pub fn dependencies(&self) -> &[Dependency] {
+        match self {
+            WorkspaceMember::Cargo { dependencies, .. } => dependencies,
+            WorkspaceMember::Node { dependencies, .. } => dependencies,
+            WorkspaceMember::Python { dependencies, .. } => dependencies,
+        }
+    }
+
+    /// Get mutable access to the dependencies declared by the package
+    pub fn dependencies_mut(&mut self) -> &mut Vec<Dependency> {
+        match self {
+            WorkspaceMember::Cargo { dependencies, .. } => dependencies,
+            WorkspaceMember::Node { dependencies, .. } => dependencies,
+            WorkspaceMember::Python { dependencies, .. } => dependencies,
+        }
+    }
+}
+
+/// A workspace, which is a collection of packages
+#[derive(Debug, Clone, Default)]
+pub struct Workspace {
+    /// The members of the workspace
+    pub members: Vec<WorkspaceMember>,
+    /// Manifest path of the workspace root, if one could be determined
+    pub root: Option<PathBuf>,
+    /// Versioning schemes of members that don't use semver, keyed by member path
+    pub schemes: HashMap<PathBuf, VersionScheme>,
+    /// Named groups of members whose versions move together
+    pub groups: Vec<VersionGroup>,
+    /// Bump dependents of changed members too, if set
+    pub cascade: Option<Cascade>,
+    /// Whether the Cargo workspace root declares a `[workspace.package]` version to inherit
+    pub inheritable_version: bool,
+}
+
+/// How a change is cascaded to the members that depend on the changed one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cascade {
+    /// Bump given to dependents (major instead when a dependency makes a breaking change)
+    pub level: Level,
+    /// How levels apply to dependents still at 0.x
+    pub zero_major: ZeroMajor,
+}
+
+/// How the versions of a group's members are tied together
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupKind {
+    /// Members always share one version and are bumped together
+    Fixed,
+    /// Members that are bumped move to the highest version in the group
+    Linked,
+}
+
+/// A named set of packages whose versions are managed together
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionGroup {
+    pub name: String,
+    pub kind: GroupKind,
+    /// Names of the packages in the group
+    pub members: Vec<String>,
+}
+
+impl Workspace {
+    pub fn roll_version(
+        &mut self,
+        bump: VersionBump,
+        selection: &PackageSelection,
+    ) -> anyhow::Result<OperationResult> {
+        self.roll_version_with_clock(bump, selection, &SystemClock)
+    }
+
+    /// Same as `roll_version`, with `clock` supplying today's date for calver members
+    pub fn roll_version_with_clock(
+        &mut self,
+        bump: VersionBump,
+        selection: &PackageSelection,
+        clock: &dyn Clock,
+    ) -> anyhow::Result<OperationResult> {
+        let mut result = OperationResult::new(format!(
+            "roll {}",
+            match &bump {
+                VersionBump::Major(amount) => format!("major {}", amount),
+                VersionBump::Minor(amount) => format!("minor {}", amount),
+                VersionBump::Patch(amount) => format!("patch {}", amount),
+                VersionBump::Premajor(identifier) => format!("premajor {}", identifier),
+                VersionBump::Preminor(identifier) => format!("preminor {}", identifier),
+                VersionBump::Prepatch(identifier) => format!("prepatch {}", identifier),
+                VersionBump::Prerelease(Some(identifier)) => format!("pre {}", identifier),
+                VersionBump::Prerelease(None) => "pre".to_string(),
+                VersionBump::Release => "release".to_string(),
+            }
+        ));
+
+        let plan = self
+            .with_fixed_groups(self.select_member_indices(selection)?)
+            .into_iter()
+            .map(|index| (index, bump.clone()))
+            .collect();
+        self.roll_planned(plan, clock, &mut result)?;
+        self.propagate(clock, &mut result)?;
+
+        self.update_dependency_requirements(&mut result);
+        Ok(result)
+    }
+
+    /// Roll each package by the level its commit history calls for
+    ///
+    /// Members of a fixed group all take the highest level found in the group.
+    /// The applied bump is recorded on each decision, which end up in the result.
+    pub fn roll_auto(
+        &mut self,
+        decisions: Vec<BumpDecision>,
+        zero_major: ZeroMajor,
+    ) -> anyhow::Result<OperationResult> {
+        self.roll_auto_with_clock(decisions, zero_major, &SystemClock)
+    }
+
+    /// Same as `roll_auto`, with `clock` supplying today's date for calver members
+    pub fn roll_auto_with_clock(
+        &mut self,
+        mut decisions: Vec<BumpDecision>,
+        zero_major: ZeroMajor,
+        clock: &dyn Clock,
+    ) -> anyhow::Result<OperationResult> {
+        let levels: Vec<(String, Level)> = decisions
+            .iter()
+            .filter_map(|d| d.level.map(|level| (d.package.clone(), level)))
+            .collect();
+        let mut result = self.roll_levels_with_clock("roll auto", &levels, zero_major, clock)?;
+
+        for decision in &mut decisions {
+            decision.bump = result
+                .changes
+                .iter()
+                .find(|change| change.package == decision.package)
+                .map(|change| {
+                    bump_name(&change.old_version, &change.new_version)
+                        .map(str::to_string)
+                        .unwrap_or_else(|| decision.level.unwrap_or(Level::Patch).to_string())
+                });
+        }
+        result.decisions = decisions;
+        Ok(result)
+    }
+
+    /// Roll each named package by its own level (e.g. from changeset files)
+    ///
+    /// Members of a fixed group all take the highest level found in the group.
+    pub fn roll_levels(
+        &mut self,
+        operation: &str,
+        levels: &[(String, Level)],
+        zero_major: ZeroMajor,
+    ) -> anyhow::Result<OperationResult> {
+        self.roll_levels_with_clock(operation, levels, zero_major, &SystemClock)
+    }
+
+    /// Same as `roll_levels`, with `clock` supplying today's date for calver members
+    pub fn roll_levels_with_clock(
+        &mut self,
+        operation: &str,
+        levels: &[(String, Level)],
+        zero_major: ZeroMajor,
+        clock: &dyn Clock,
+    ) -> anyhow::Result<OperationResult> {
+        let mut result = OperationResult::new(operation.to_string());
+
+        // Members inheriting the `[workspace.package]` version are released with the root
+        let root = self.inheritance_root().and_then(|root| {
+            self.members
+                .iter()
+                .position(|m| m.manifest_path() == root.manifest_path())
+        });
+
+        let mut indexed: Vec<(usize, Level)> = Vec::new();
+        for (package, level) in levels {
+            let index = self
+                .members
+                .iter()
+                .position(|m| m.name() == package)
+                .ok_or_else(|| anyhow::anyhow!("Package '{}' not found in workspace", package))?;
+            let index = match (self.members[index].version(), root) {
+                (VersionField::Inherited, Some(root)) => root,
+                _ => index,
+            };
+            indexed.push((index, *level));
+        }
+
+        for group in &self.groups {
+            if group.kind != GroupKind::Fixed {
+                continue;
+            }
+            let in_group = |index: usize| {
+                group
+                    .members
+                    .iter()
+                    .any(|m| m == self.members[index].name())
+            };
+            let highest = match indexed
+                .iter()
+                .filter(|(i, _)| in_group(*i))
+                .map(|(_, l)| *l)
+                .max()
+            {
+                Some(level) => level,
+                None => continue,
+            };
+
+            indexed.retain(|(i, _)| !in_group(*i));
+            indexed.extend(
+                (0..self.members.len())
+                    .filter(|&i| in_group(i))
+                    .map(|i| (i, highest)),
+            );
+        }
+
+        // Keep the highest level for each member
+        indexed.sort_by(|(a, a_level), (b, b_level)| a.cmp(b).then(b_level.cmp(a_level)));
+        indexed.dedup_by_key(|(index, _)| *index);
+
+        let mut plan = Vec::new();
+        for (index, level) in indexed {
+            let (base_version, _) = self.roll_base(index).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Package '{}' has no version to release",
+                    self.members[index].name()
+                )
+            })?;
+            plan.push((index, level.to_bump(&base_version, zero_major)));
+        }
+        self.roll_planned(plan, clock, &mut result)?;
+        self.propagate(clock, &mut result)?;
+
+        self.update_dependency_requirements(&mut result);
+        Ok(result)
+    }
+
+    /// Apply each planned bump to the member at its index
+    fn roll_planned(
+        &mut self,
+        plan: Vec<(usize, VersionBump)>,
+        clock: &dyn Clock,
+        result: &mut OperationResult,
+    ) -> anyhow::Result<()> {
+        // Work out every base before any member changes, so grouped members
+        // all start from the group's version as it stood before this roll
+        let bases: Vec<_> = plan
+            .iter()
+            .map(|(index, _)| self.roll_base(*index))
+            .collect();
+
+        for ((index, bump), base) in plan.into_iter().zip(bases) {
+            let member = &mut self.members[index];
+
+            let old_version = match member.version() {
+                VersionField::Concrete(version) => version.clone(),
+                _ => continue,
+            };
+            let (base_version, scheme) = match base {
+                Some(base) => base,
+                None => continue,
+            };
+
+            let new_version = scheme.bump(&base_version, &bump, clock)?;
+
+            if old_version != new_version {
+                result.add_change(VersionChange {
+                    package: member.name().to_string(),
+                    old_version: old_version.clone(),
+                    new_version: new_version.clone(),
+                    path: member.path().clone(),
+                });
+
+                member.set_version(VersionField::Concrete(new_version));
+            }
+        }
+        Ok(())
+    }
+
+    /// Bump the dependents of every changed member, per `self.cascade`
+    ///
+    /// Dependents are walked transitively within each ecosystem, ignoring
+    /// development dependencies. Each gets the cascade level, or a major bump
+    /// when one of its dependencies makes a breaking change (see `is_breaking`); fixed groups
+    /// take the highest level among their members. Members changed by the
+    /// operation itself are left alone.
+    fn propagate(&mut self, clock: &dyn Clock, result: &mut OperationResult) -> anyhow::Result<()> {
+        let cascade = match self.cascade {
+            Some(cascade) => cascade,
+            None => return Ok(()),
+        };
+
+        // Members inheriting the root's version move with it, both when it has
+        // already changed and when it is raised here
+        let changes = self.changes_with_inherited(result);
+        let root = self.inheritance_root().and_then(|root| {
+            self.members
+                .iter()
+                .position(|m| m.manifest_path() == root.manifest_path())
+        });
+        let source = |index: usize| match (self.members[index].version(), root) {
+            (VersionField::Inherited, Some(root))
+                if self.members[index].ecosystem() == Ecosystem::Cargo =>
+            {
+                root
+            }
+            _ => index,
+        };
+
+        let changed: Vec<usize> = changes
+            .iter()
+            .filter_map(|change| {
+                self.members
+                    .iter()
+                    .position(|m| m.name() == change.package && *m.path() == change.path)
+            })
+            .collect();
+
+        // Raise levels until they settle; they only ever go up, so this ends
+        let mut levels: BTreeMap<usize, (Level, String)> = BTreeMap::new();
+        loop {
+            let mut raises = Vec::new();
+
+            for (index, member) in self.members.iter().enumerate() {
+                for dependency in member.dependencies().iter().filter(|d| !d.is_dev()) {
+                    let dep_index = match self.members.iter().position(|m| {
+                        m.name() == dependency.name && m.ecosystem() == member.ecosystem()
+                    }) {
+                        Some(dep_index) => dep_index,
+                        None => continue,
+                    };
+
+                    let breaking = if changed.contains(&dep_index) {
+                        changes
+                            .iter()
+                            .filter(|c| c.package == dependency.name)
+                            .any(|c| {
+                                is_breaking(&c.old_version, &c.new_version, cascade.zero_major)
+                            })
+                    } else if let Some((level, _)) = levels.get(&source(dep_index)) {
+                        self.cascaded_version(source(dep_index), *level, cascade.zero_major, clock)
+                            .is_some_and(|(old, new)| is_breaking(&old, &new, cascade.zero_major))
+                    } else {
+                        continue;
+                    };
+
+                    let level = if breaking {
+                        Level::Major
+                    } else {
+                        cascade.level
+                    };
+                    raises.push((source(index), level, dependency.name.clone()));
+                }
+            }
+
+            for group in self.groups.iter().filter(|g| g.kind == GroupKind::Fixed) {
+                let in_group = |index: usize| {
+                    group
+                        .members
+                        .iter()
+                        .any(|m| m == self.members[index].name())
+                };
+                let highest = levels
+                    .iter()
+                    .filter(|(index, _)| in_group(**index))
+                    .max_by_key(|(_, (level, _))| *level);
+                if let Some((_, (level, dependency))) = highest {
+                    for index in (0..self.members.len()).filter(|&i| in_group(i)) {
+                        raises.push((index, *level, dependency.clone()));
+                    }
+                }
+            }
+
+            let mut raised = false;
+            for (index, level, dependency) in raises {
+                if changed.contains(&index) {
+                    continue;
+                }
+                if levels
+                    .get(&index)
+                    .is_none_or(|(current, _)| *current < level)
+                {
+                    levels.insert(index, (level, dependency));
+                    raised = true;
+                }
+            }
+            if !raised {
+                break;
+            }
+        }
+
+        let mut plan = Vec::new();
+        for (index, (level, _)) in &levels {
+            if let Some((base_version, _)) = self.roll_base(*index) {
+                plan.push((*index, level.to_bump(&base_version, cascade.zero_major)));
+            }
+        }
+
+        let mut cascaded = OperationResult::new(result.operation.clone());
+        self.roll_planned(plan, clock, &mut cascaded)?;
+        for change in cascaded.changes {
+            let dependency = self
+                .members
+                .iter()
+                .position(|m| m.name() == change.package && *m.path() == change.path)
+                .and_then(|index| levels.get(&index))
+                .map(|(_, dependency)| dependency.clone())
+                .unwrap_or_default();
+            result
+                .propagated
+                .push(PropagatedChange { change, dependency });
+        }
+        Ok(())
+    }
+
+    /// The current and cascaded versions of the member at `index` for `level`
+    fn cascaded_version(
+        &self,
+        index: usize,
+        level: Level,
+        zero_major: ZeroMajor,
+        clock: &dyn Clock,
+    ) -> Option<(String, String)> {
+        let old_version = match self.members[index].version() {
+            VersionField::Concrete(version) => version.clone(),
+            _ => return None,
+        };
+        let (base_version, scheme) = self.roll_base(index)?;
+        let new_version = scheme
+            .bump(
+                &base_version,
+                &level.to_bump(&base_version, zero_major),
+                clock,
+            )
+            .ok()?;
+        Some((old_version, new_version))
+    }
+
+    /// The version (and scheme) a roll of the member at `index` starts from
+    ///
+    /// Grouped members start from the highest version in their group.
+    fn roll_base(&self, index: usize) -> Option<(String, VersionScheme)> {
+        let member = &self.members[index];
+        let base = match self.group_of(member.name()) {
+            Some(group) => self.highest_in_group(group, &[]).unwrap_or(member),
+            None => member,
+        };
+        match base.version() {
+            VersionField::Concrete(version) => Some((version.clone(), self.scheme(base).clone())),
+            _ => None,
+        }
+    }
+
+    pub fn set_version(
+        &mut self,
+        version: &str,
+        selection: &PackageSelection,
+    ) -> anyhow::Result<OperationResult> {
+        let mut result = OperationResult::new(format!("set {}", version));
+
+        let indices = self.with_fixed_groups(self.select_member_indices(selection)?);
+
+        for &index in &indices {
+            let name = self.members[index].name();
+            let group = match self.group_of(name) {
+                Some(group) if group.kind == GroupKind::Linked => group,
+                _ => continue,
+            };
+
+            if let Some(VersionField::Concrete(highest)) =
+                self.highest_in_group(group, &indices).map(|m| m.version())
+            {
+                if compare_versions(version, highest) == Ordering::Less {
+                    anyhow::bail!(
+                        "Cannot set '{}' to {}: linked group '{}' is already at {}",
+                        name,
+                        version,
+                        group.name,
+                        highest
+                    );
+                }
+            }
+        }
+
+        for &index in &indices {
+            let member = &mut self.members[index];
+
+            let old_version = match member.version() {
+                VersionField::Concrete(version) => version.clone(),
+                _ => continue,
+            };
+
+            if old_version != version {
+                result.add_change(VersionChange {
+                    package: member.name().to_string(),
+                    old_version: old_version.clone(),
+                    new_version: version.to_string(),
+                    path: member.path().clone(),
+                });
+
+                member.set_version(VersionField::Concrete(version.to_string()));
+            }
+        }
+        self.propagate(&SystemClock, &mut result)?;
+
+        self.update_dependency_requirements(&mut result);
+        Ok(result)
+    }
+
+    pub fn sync_version(&mut self, version: &str) -> anyhow::Result<OperationResult> {
+        let mut result = OperationResult::new(format!("sync {}", version));
+
+        for member in &mut self.members {
+            let old_version = match member.version() {
+                VersionField::Concrete(version) => version.clone(),
+                _ => continue,
+            };
+
+            if old_version != version {
+                result.add_change(VersionChange {
+                    package: member.name().to_string(),
+                    old_version: old_version.clone(),
+                    new_version: version.to_string(),
+                    path: member.path().clone(),
+                });
+
+                member.set_version(VersionField::Concrete(version.to_string()));
+            }
+        }
+        self.propagate(&SystemClock, &mut result)?;
+
+        self.update_dependency_requirements(&mut result);
+        Ok(result)
+    }
+
+    /// The version of `member`, taking inherited Cargo versions from the workspace root manifest
+    pub fn resolved_version<'a>(&'a self, member: &'a WorkspaceMember) -> Option<&'a str> {
+        match member.version() {
+            VersionField::Concrete(version) => Some(version),
+            VersionField::Inherited => self.workspace_version(),
+            _ => None,
+        }
+    }
+
+    /// The `[workspace.package]` version that Cargo members inherit
+    pub fn workspace_version(&self) -> Option<&str> {
+        match self.inheritance_root()?.version() {
+            VersionField::Concrete(version) => Some(version),
+            _ => None,
+        }
+    }
+
+    /// The Cargo workspace root member, whose version `version.workspace = true` inherits
+    pub fn inheritance_root(&self) -> Option<&WorkspaceMember> {
+        let root = self.root.as_ref()?;
+        self.members
+            .iter()
+            .find(|m| m.ecosystem() == Ecosystem::Cargo && m.manifest_path() == *root)
+    }
+
+    /// The versioning scheme of `member` (semver unless configured otherwise)
+    pub fn scheme(&self, member: &WorkspaceMember) -> &VersionScheme {
+        static SEMVER: VersionScheme = VersionScheme::Semver;
+        self.schemes.get(member.path()).unwrap_or(&SEMVER)
+    }
+
+    /// Index of the member that owns `file`: the one with the deepest directory containing it
+    fn owner_of(&self, file: &std::path::Path) -> Option<usize> {
+        self.members
+            .iter()
+            .enumerate()
+            .filter(|(_, member)| file.starts_with(member.path()))
+            .max_by_key(|(_, member)| member.path().components().count())
+            .map(|(index, _)| index)
+    }
+
+    /// The members picked by `selection`, in workspace order
+    pub fn select(&self, selection: &PackageSelection) -> anyhow::Result<Vec<&WorkspaceMember>> {
+        let indices = self.select_member_indices(selection)?;
+        Ok(indices.iter().map(|&i| &self.members[i]).collect())
+    }
+
+    /// The group `package` belongs to, if any
+    pub fn group_of(&self, package: &str) -> Option<&VersionGroup> {
+        self.groups
+            .iter()
+            .find(|group| group.members.iter().any(|m| m == package))
+    }
+
+    /// The member of `group` with the highest concrete version, ignoring members at `skip`
+    fn highest_in_group(&self, group: &VersionGroup, skip: &[usize]) -> Option<&WorkspaceMember> {
+        self.members
+            .iter()
+            .enumerate()
+            .filter(|(index, member)| {
+                !skip.contains(index) && group.members.iter().any(|m| m == member.name())
+            })
+            .filter_map(|(_, member)| match member.version() {
+                VersionField::Concrete(version) => Some((member, version)),
+                _ => None,
+            })
+            .max_by(|(_, a), (_, b)| compare_versions(a, b))
+            .map(|(member, _)| member)
+    }
+
+    /// Add the other members of any fixed group that has a selected member
+    fn with_fixed_groups(&self, mut indices: Vec<usize>) -> Vec<usize> {
+        for group in &self.groups {
+            if group.kind != GroupKind::Fixed
+                || !indices
+                    .iter()
+                    .any(|&i| group.members.iter().any(|m| m == self.members[i].name()))
+            {
+                continue;
+            }
+
+            for (index, member) in self.members.iter().enumerate() {
+                if group.members.iter().any(|m| m == member.name()) && !indices.contains(&index) {
+                    indices.push(index);
+                }
+            }
+        }
+        indices
+    }
+
+    // Keep this for tests but handle errors properly in production code
+    #[cfg(test)]
+    pub fn selected_members(&self, selection: &PackageSelection) -> Vec<&WorkspaceMember> {
+        match self.select_member_indices(selection) {
+            Ok(indices) => {
+                let mut members: Vec<&WorkspaceMember> =
+                    indices.iter().map(|&i| &self.members[i]).collect();
+                members.sort_by(|a, b| a.name().cmp(b.name()));
+                members
+            }
+            Err(_) => vec![], // Return empty on error for tests
+        }
+    }
+
+    /// Point intra-workspace dependency requirements at the new versions in `result`
+    ///
+    /// Only dependencies between members of the same ecosystem are considered.
+    /// Requirements that cannot be rewritten without changing their meaning
+    /// (ranges, wildcards, `workspace:*`) are left alone.
+    fn update_dependency_requirements(&mut self, result: &mut OperationResult) {
+        for change in &self.changes_with_inherited(result) {
+            let ecosystem = match self
+                .members
+                .iter()
+                .find(|m| m.name() == change.package && *m.path() == change.path)
+            {
+                Some(member) => member.ecosystem(),
+                None => continue,
+            };
+
+            for member in &mut self.members {
+                if member.ecosystem() != ecosystem {
+                    continue;
+                }
+
+                let package = member.name().to_string();
+                let path = member.path().clone();
+
+                for dependency in member.dependencies_mut() {
+                    if dependency.name != change.package {
+                        continue;
+                    }
+
+                    let old_requirement = match &dependency.requirement {
+                        Some(requirement) => requirement.clone(),
+                        None => continue,
+                    };
+
+                    let new_requirement =
+                        match rewrite_requirement(&old_requirement, &change.new_version) {
+                            Some(requirement) if requirement != old_requirement => requirement,
+                            _ => continue,
+                        };
+
+                    result.add_dependency_change(DependencyChange {
+                        package: package.clone(),
+                        dependency: dependency.name.clone(),
+                        table: dependency.table.clone(),
+                        old_requirement,
+                        new_requirement: new_requirement.clone(),
+                        path: path.clone(),
+                    });
+
+                    dependency.requirement = Some(new_requirement);
+                }
+            }
+        }
+    }
+
+    /// Every change in `result`, plus one for each Cargo member inheriting the
+    /// `[workspace.package]` version when the workspace root's version changed
+    fn changes_with_inherited(&self, result: &OperationResult) -> Vec<VersionChange> {
+        let mut changes: Vec<VersionChange> = result.all_changes().cloned().collect();
+        let root_change = self.inheritance_root().and_then(|root| {
+            changes
+                .iter()
+                .find(|c| c.package == root.name() && c.path == *root.path())
+                .cloned()
+        });
+        if let Some(root_change) = root_change {
+            changes.extend(
+                self.members
+                    .iter()
+                    .filter(|m| {
+                        m.ecosystem() == Ecosystem::Cargo && *m.version() == VersionField::Inherited
+                    })
+                    .map(|m| VersionChange {
+                        package: m.name().to_string(),
+                        path: m.path().clone(),
+                        ..root_change.clone()
+                    }),
+            );
+        }
+        changes
+    }
+
+    fn select_member_indices(&self, selection: &PackageSelection) -> anyhow::Result<Vec<usize>> {
+        match selection {
+            PackageSelection::Specific(packages) => {
+                let mut indices = Vec::new();
+                for package_name in packages {
+                    match self.members.iter().position(|m| m.name() == *package_name) {
+                        Some(index) => indices.push(index),
+                        None => anyhow::bail!("Package '{}' not found in workspace", package_name),
+                    }
+                }
+                Ok(indices)
+            }
+            PackageSelection::Workspace => Ok((0..self.members.len()).collect()),
+            PackageSelection::ChangedSince { reference, .. } => anyhow::bail!(
+                "Changes since '{}' have not been resolved against git history",
+                reference
+            ),
+            PackageSelection::Changed {
+                files,
+                include_dependents,
+            } => {
+                let mut indices: Vec<usize> = (0..self.members.len())
+                    .filter(|&i| files.iter().any(|file| self.owner_of(file) == Some(i)))
+                    .collect();
+
+                if *include_dependents {
+                    // Walk outwards until no further member depends on a selected one
+                    let mut next = 0;
+                    while next < indices.len() {
+                        let changed = &self.members[indices[next]];
+                        for (index, member) in self.members.iter().enumerate() {
+                            if member.ecosystem() == changed.ecosystem()
+                                && !indices.contains(&index)
+                                && member
+                                    .dependencies()
+                                    .iter()
+                                    .any(|d| d.name == changed.name())
+                            {
+                                indices.push(index);
+                            }
+                        }
+                        next += 1;
+                    }
+                    indices.sort();
+                }
+
+                Ok(indices)
+            }
+            PackageSelection::Default => {
+                if self.members.is_empty() {
+                    anyhow::bail!("No packages found in workspace")
+                }
+
+                let root = self.root.as_ref().ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Could not determine the workspace root; use --package or --workspace to select members"
+                    )
+                })?;
+
+                match self.members.iter().position(|m| m.manifest_path() == *root) {
+                    Some(index) => Ok(vec![index]),
+                    None => anyhow::bail!(
+                        "Workspace root {} is not a workspace member",
+                        root.display()
+                    ),
+                }
+            }
+        }
+    }
+}
+
+impl VersionBump {
+    pub fn apply_to_version(&self, current: &str) -> anyhow::Result<String> {
+        let mut version = semver::Version::parse(current)
+            .with_context(|| format!("Invalid semver version: '{}'", current))?;
+        let original = version.clone();
+
+        // Moving forward a regular level drops any prerelease; one that is
+        // heading for that level graduates to its release (1.3.0-rc.1 → 1.3.0
+        // for minor), which counts as the first step
+        let graduates = match self {
+            VersionBump::Major(amount)
+            | VersionBump::Minor(amount)
+            | VersionBump::Patch(amount)
+                if *amount > 0 && !version.pre.is_empty() =>
+            {
+                version.pre = semver::Prerelease::EMPTY;
+                match self {
+                    VersionBump::Major(_) => version.minor == 0 && version.patch == 0,
+                    VersionBump::Minor(_) => version.patch == 0,
+                    _ => true,
+                }
+            }
+            _ => false,
+        };
+        let step = |amount: i32| amount as u64 - u64::from(graduates);
+
+        match self {
+            VersionBump::Major(amount) => {
+                if *amount < 0 {
+                    let abs_amount = amount.unsigned_abs() as u64;
+                    if version.major < abs_amount {
+                        anyhow::bail!(
+                            "Cannot decrement major version by {} from {}: would result in negative version",
+                            abs_amount, current
+                        );
+                    }
+                    version.major -= abs_amount;
+                } else {
+                    version.major += step(*amount);
+                }
+                version.minor = 0;
+                version.patch = 0;
+            }
+            VersionBump::Minor(amount) => {
+                if *amount < 0 {
+                    let abs_amount = amount.unsigned_abs() as u64;
+                    if version.minor < abs_amount {
+                        anyhow::bail!(
+                            "Cannot decrement minor version by {} from {}: would result in negative version",
+                            abs_amount, current
+                        );
+                    }
+                    version.minor -= abs_amount;
+                } else {
+                    version.minor += step(*amount);
+                }
+                version.patch = 0;
+            }
+            VersionBump::Patch(amount) => {
+                if *amount < 0 {
+                    let abs_amount = amount.unsigned_abs() as u64;
+                    if version.patch < abs_amount {
+                        anyhow::bail!(
+                            "Cannot decrement patch version by {} from {}: would result in negative version",
+                            abs_amount, current
+                        );
+                    }
+                    version.patch -= abs_amount;
+                } else {
+                    version.patch += step(*amount);
+                }
+            }
+            VersionBump::Premajor(identifier) => {
+                version.major += 1;
+                version.minor = 0;
+                version.patch = 0;
+                version.pre = start_prerelease(identifier)?;
+            }
+            VersionBump::Preminor(identifier) => {
+                version.minor += 1;
+                version.patch = 0;
+                version.pre = start_prerelease(identifier)?;
+            }
+            VersionBump::Prepatch(identifier) => {
+                version.patch += 1;
+                version.pre = start_prerelease(identifier)?;
+            }
+            VersionBump::Prerelease(identifier) => {
+                if version.pre.is_empty() {
+                    let identifier = identifier.as_deref().ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "{} is not a prerelease; pass an identifier or use premajor, preminor or prepatch",
+                            current
+                        )
+                    })?;
+                    version.patch += 1;
+                    version.pre = start_prerelease(identifier)?;
+                } else {
+                    let (channel, counter) = split_prerelease(&version.pre);
+                    version.pre = match identifier.as_deref() {
+                        Some(identifier) if identifier != channel => start_prerelease(identifier)?,
+                        _ => semver::Prerelease::new(&match counter {
+                            Some(counter) if channel.is_empty() => format!("{}", counter + 1),
+                            Some(counter) => format!("{}.{}", channel, counter + 1),
+                            None => format!("{}.0", channel),
+                        })?,
+                    };
+                }
+            }
+            VersionBump::Release => {
+                version.pre = semver::Prerelease::EMPTY;
+            }
+        }
+
+        if matches!(self, VersionBump::Prerelease(_)) && version <= original {
+            anyhow::bail!(
+                "Cannot move {} to {}: prerelease channels must move forward (e.g. alpha → beta → rc)",
+                current,
+                version
+            );
+        }
+
+        Ok(version.to_string())
+    }
+}
+
+/// Whether going from `old` to `new` is a breaking change
+///
+/// That is a new major version, or with `ZeroMajor::Shift` a change in the
+/// leftmost non-zero component, as Cargo and caret requirements see it
+/// (0.3.0 → 0.4.0, 0.0.3 → 0.0.4).
+fn is_breaking(old: &str, new: &str, zero_major: ZeroMajor) -> bool {
+    let compatible = |v: &semver::Version| match (zero_major, v.major, v.minor) {
+        (ZeroMajor::Shift, 0, 0) => (0, 0, v.patch),
+        (ZeroMajor::Shift, 0, minor) => (0, minor, 0),
+        (_, major, _) => (major, 0, 0),
+    };
+    match (semver::Version::parse(old), semver::Version::parse(new)) {
+        (Ok(old), Ok(new)) => compatible(&old) != compatible(&new),
+        _ => false,
+    }
+}
+
+/// Name the semver component that changed between two versions ("major", "minor" or "patch")
+fn bump_name(old: &str, new: &str) -> Option<&'static str> {
+    let old = semver::Version::parse(old).ok()?;
+    let new = semver::Version::parse(new).ok()?;
+    Some(if old.major != new.major {
+        "major"
+    } else if old.minor != new.minor {
+        "minor"
+    } else {
+        "patch"
+    })
+}
+
+/// Order two versions by semver precedence, falling back to comparing their
+/// numeric components for versions that are not semver (e.g. calver `2024.03.1`)
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    match (semver::Version::parse(a), semver::Version::parse(b)) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => {
+            let numbers = |v: &str| {
+                v.split(|c: char| !c.is_ascii_digit())
+                    .filter_map(|n| n.parse::<u64>().ok())
+                    .collect::<Vec<_>>()
+            };
+            numbers(a).cmp(&numbers(b))
+        }
+    }
+}
+
+/// Build the first prerelease of a channel, e.g. `beta` → `beta.0`
+fn start_prerelease(identifier: &str) -> anyhow::Result<semver::Prerelease> {
+    if identifier.is_empty() {
+        anyhow::bail!("Prerelease identifier must not be empty");
+    }
+    semver::Prerelease::new(&format!("{}.0", identifier))
+        .with_context(|| format!("Invalid prerelease identifier: '{}'", identifier))
+}
+
+/// Split a prerelease into its channel and trailing numeric counter
+///
+/// `beta.2` → (`beta`, Some(2)), `beta` → (`beta`, None), `3` → (``, Some(3))
+fn split_prerelease(pre: &semver::Prerelease) -> (&str, Option<u64>) {
+    let pre = pre.as_str();
+    let (channel, last) = match pre.rsplit_once('.') {
+        Some((channel, last)) => (channel, last),
+        None => ("", pre),
+    };
+    match last.parse::<u64>() {
+        Ok(counter) => (channel, Some(counter)),
+        Err(_) => (pre, None),
+    }
+}
+
+/// Rewrite a version requirement to point at `new_version`, keeping its style
+///
+/// The operator (`^`, `~`, `=` or none) and the number of version components
+/// are preserved, so `^1.2` becomes `^2.0` and `=1.2.0` becomes `=2.0.0`. A pnpm
+/// `workspace:` prefix is kept as well. Returns `None` for requirements that have
+/// no single-version equivalent, such as ranges, wildcards or `workspace:*`, and
+/// for lower bounds like `>=1.2`, which were chosen on purpose.
+pub fn rewrite_requirement(requirement: &str, new_version: &str) -> Option<String> {
+    if let Some(inner) = requirement.strip_prefix("workspace:") {
+        return rewrite_requirement(inner, new_version).map(|r| format!("workspace:{}", r));
+    }
+
+    let version = semver::Version::parse(new_version).ok()?;
+    let trimmed = requirement.trim();
+
+    let operator = ["^", "~", "="]
+        .into_iter()
+        .find(|op| trimmed.starts_with(op))
+        .unwrap_or("");
+    let current = trimmed[operator.len()..].trim_start();
+
+    // Only plain versions such as "1", "1.2", "1.2.3" or "1.2.3-beta.1" can be rewritten
+    let (numbers, prerelease) = match current.split_once('-') {
+        Some((numbers, prerelease)) => (numbers, Some(prerelease)),
+        None => (current, None),
+    };
+    let components: Vec<&str> = numbers.split('.').collect();
+    if components.is_empty()
+        || components.len() > 3
+        || components
+            .iter()
+            .any(|c| c.is_empty() || !c.chars().all(|ch| ch.is_ascii_digit()))
+        || prerelease.is_some_and(|p| p.is_empty() || p.contains(char::is_whitespace))
+    {
+        return None;
+    }
+
+    // Partial requirements never match prereleases, so those always get the full version
+    let rendered = if components.len() == 3 || !version.pre.is_empty() {
+        let mut full = version.clone();
+        full.build = semver::BuildMetadata::EMPTY;
+        full.to_string()
+    } else if components.len() == 2 {
+        format!("{}.{}", version.major, version.minor)
+    } else {
+        version.major.to_string()
+    };
+
+    Some(format!("{}{}", operator, rendered))
+}
+
+/// Whether `requirement` accepts `version`, or `None` when that can't be told
+///
+/// Cargo requirements use Cargo's syntax, where a bare `0.4` means `^0.4`.
+/// npm requirements are translated from npm's range syntax, where a bare
+/// version is exact and comparators are separated by spaces and `||`.
+/// Protocols, tags and `workspace:*`-style shorthands can't be checked.
+pub fn requirement_accepts(ecosystem: Ecosystem, requirement: &str, version: &str) -> Option<bool> {
+    let version = semver::Version::parse(version).ok()?;
+    match ecosystem {
+        Ecosystem::Cargo => Some(
+            semver::VersionReq::parse(requirement)
+                .ok()?
+                .matches(&version),
+        ),
+        Ecosystem::Node => {
+            let requirement = match requirement.strip_prefix("workspace:") {
+                Some("*" | "^" | "~") => return None,
+                Some(inner) => inner,
+                None => requirement,
+            };
+            let mut accepted = false;
+            for range in requirement.split("||") {
+                accepted |= npm_range(range)?.matches(&version);
+            }
+            Some(accepted)
+        }
+        Ecosystem::Python => None,
+    }
+}
+
+/// Translate one npm range (no `||`) into an equivalent semver requirement
+fn npm_range(range: &str) -> Option<semver::VersionReq> {
+    let range = range.trim();
+    if let Some((low, high)) = range.split_once(" - ") {
+        return semver::VersionReq::parse(&format!(
+            ">={}, <={}",
+            low.trim().trim_start_matches('v'),
+            high.trim().trim_start_matches('v')
+        ))
+        .ok();
+    }
+
+    // Operators may be separated from their version by spaces
+    let mut comparators = Vec::new();
+    let mut operator = String::new();
+    for token in range.split_whitespace() {
+        let split = token
+            .find(|c: char| !matches!(c, '<' | '>' | '=' | '^' | '~'))
+            .unwrap_or(token.len());
+        operator.push_str(&token[..split]);
+        let version = token[split..].trim_start_matches('v');
+        if version.is_empty() {
+            continue;
+        }
+        let version = version
+            .split('.')
+            .map(|c| if c == "x" || c == "X" { "*" } else { c })
+            .collect::<Vec<_>>()
+            .join(".");
+        // A bare npm version is exact; a bare semver one would be a caret requirement
+        if operator.is_empty() && !version.contains('*') {
+            operator.push('=');
+        }
+        comparators.push(format!("{}{}", std::mem::take(&mut operator), version));
+    }
+    if comparators.is_empty() {
+        comparators.push("*".to_string());
+    }
+    semver::VersionReq::parse(&comparators.join(", ")).ok()
+}
+
+impl PackageSelection {
+    #[cfg(test)]
+    pub fn workspace() -> Self {
+        Self::Workspace
+    }
+
+    #[cfg(test)]
+    pub fn root_only() -> Self {
+        Self::Default
+    }
+
+    #[cfg(test)]
+    pub fn packages(packages: Vec<String>) -> Self {
+        Self::Specific(packages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lint::LintOptions;
+    use super::*;
+    use std::path::PathBuf;
+
+    fn create_test_member(name: &str, version: VersionField) -> WorkspaceMember {
+        WorkspaceMember::Cargo {
+            name: name.to_string(),
+            path: PathBuf::from(format!("{}/Cargo.toml", name)),
+            version,
+            dependencies: Vec::new(),
+        }
+    }
+
+    fn create_test_dependency(name: &str, requirement: &str) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            requirement: Some(requirement.to_string()),
+            table: "dependencies".to_string(),
+        }
+    }
+
+    fn create_test_workspace(members: Vec<(&str, VersionField)>) -> Workspace {
+        Workspace {
+            members: members
+                .into_iter()
+                .map(|(name, version)| create_test_member(name, version))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_version_bump_patch() {
+        let bump = VersionBump::Patch(1);
+        assert_eq!(bump.apply_to_version("1.0.0").unwrap(), "1.0.1");
+        assert_eq!(bump.apply_to_version("0.5.9").unwrap(), "0.5.10");
+    }
+
+    #[test]
+    fn test_version_bump_patch_custom_amount() {
+        let bump = VersionBump::Patch(5);
+        assert_eq!(bump.apply_to_version("1.0.0").unwrap(), "1.0.5");
+
+        let bump = VersionBump::Patch(-2);
+        assert_eq!(bump.apply_to_version("1.0.5").unwrap(), "1.0.3");
+    }
+
+    #[test]
+    fn test_version_bump_minor() {
+        let bump = VersionBump::Minor(1);
+        assert_eq!(bump.apply_to_version("1.5.3").unwrap(), "1.6.0");
+
+        let bump = VersionBump::Minor(3);
+        assert_eq!(bump.apply_to_version("0.1.0").unwrap(), "0.4.0");
+    }
+
+    #[test]
+    fn test_version_bump_major() {
+        let bump = VersionBump::Major(1);
+        assert_eq!(bump.apply_to_version("1.5.3").unwrap(), "2.0.0");
+
+        let bump = VersionBump::Major(2);
+        assert_eq!(bump.apply_to_version("0.1.0").unwrap(), "2.0.0");
+    }
+
+    #[test]
+    fn test_version_bump_negative_errors() {
+        // Test that operations resulting in negative versions return errors
+        let bump = VersionBump::Patch(-10);
+        let result = bump.apply_to_version("1.0.3");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("would result in negative version"));
+
+        let bump = VersionBump::Minor(-5);
+        let result = bump.apply_to_version("1.2.0");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("would result in negative version"));
+
+        let bump = VersionBump::Major(-10);
+        let result = bump.apply_to_version("2.0.0");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("would result in negative version"));
+    }
+
+    #[test]
+    fn test_version_bump_patch_invalid_operations() {
+        // Test the exact scenario we saw: patch -2 on 0.1.0 should error
+        let bump = VersionBump::Patch(-2);
+        let result = bump.apply_to_version("0.1.0");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Cannot decrement patch version by 2 from 0.1.0"));
+
+        // Also test patch -1 on 0.1.0 (should also error)
+        let bump = VersionBump::Patch(-1);
+        let result = bump.apply_to_version("0.1.0");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Cannot decrement patch version by 1 from 0.1.0"));
+
+        // Test that positive still works
+        let bump = VersionBump::Patch(2);
+        assert_eq!(bump.apply_to_version("0.1.0").unwrap(), "0.1.2");
+    }
+
+    #[test]
+    fn test_workspace_member_methods() {
+        let mut member =
+            create_test_member("test-crate", VersionField::Concrete("1.0.0".to_string()));
+
+        assert_eq!(member.name(), "test-crate");
+        assert_eq!(
+            member.version(),
+            &VersionField::Concrete("1.0.0".to_string())
+        );
+
+        member.set_version(VersionField::Concrete("2.0.0".to_string()));
+        assert_eq!(
+            member.version(),
+            &VersionField::Concrete("2.0.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_workspace_roll_version_default() {
+        let mut workspace = create_test_workspace(vec![
+            ("app", VersionField::Concrete("1.0.0".to_string())),
+            ("lib", VersionField::Concrete("0.5.0".to_string())),
+        ]);
+        workspace.root = Some(workspace.members[0].manifest_path());
+
+        // Default selection (workspace root only)
+        let selection = PackageSelection::root_only();
+        workspace
+            .roll_version(VersionBump::Patch(1), &selection)
+            .unwrap();
+
+        assert_eq!(
+            workspace.members[0].version(),
+            &VersionField::Concrete("1.0.1".to_string())
+        ); // app bumped
+        assert_eq!(
+            workspace.members[1].version(),
+            &VersionField::Concrete("0.5.0".to_string())
+        ); // lib unchanged
+    }
+
+    #[test]
+    fn test_workspace_roll_version_specific_packages() {
+        let mut workspace = create_test_workspace(vec![
+            ("app", VersionField::Concrete("1.0.0".to_string())),
+            ("lib", VersionField::Concrete("0.5.0".to_string())),
+            ("utils", VersionField::Concrete("0.1.0".to_string())),
+        ]);
+
+        let selection = PackageSelection::packages(vec!["lib".to_string(), "utils".to_string()]);
+
+        workspace
+            .roll_version(VersionBump::Minor(1), &selection)
+            .unwrap();
+
+        assert_eq!(
+            workspace.members[0].version(),
+            &VersionField::Concrete("1.0.0".to_string())
+        ); // app unchanged
+        assert_eq!(
+            workspace.members[1].version(),
+            &VersionField::Concrete("0.6.0".to_string())
+        ); // lib bumped
+        assert_eq!(
+            workspace.members[2].version(),
+            &VersionField::Concrete("0.2.0".to_string())
+        ); // utils bumped
+    }
+
+    #[test]
+    fn test_workspace_roll_version_workspace() {
+        let mut workspace = create_test_workspace(vec![
+            ("app", VersionField::Concrete("1.0.0".to_string())),
+            ("lib", VersionField::Concrete("0.5.0".to_string())),
+            ("utils", VersionField::Concrete("0.1.0".to_string())),
+        ]);
+
+        let selection = PackageSelection::workspace();
+        workspace
+            .roll_version(VersionBump::Patch(1), &selection)
+            .unwrap();
+
+        assert_eq!(
+            workspace.members[0].version(),
+            &VersionField::Concrete("1.0.1".to_string())
+        ); // app bumped
+
+        assert_eq!(
+            workspace.members[1].version(),
+            &VersionField::Concrete("0.5.1".to_string())
+        ); // lib bumped
+
+        assert_eq!(
+            workspace.members[2].version(),
+            &VersionField::Concrete("0.1.1".to_string())
+        ); // utils bumped
+    }
+
+    #[test]
+    fn test_workspace_roll_version_workspace_with_exclude() {
+        let mut workspace = create_test_workspace(vec![
+            ("app", VersionField::Concrete("1.0.0".to_string())),
+            ("lib", VersionField::Concrete("0.5.0".to_string())),
+            ("utils", VersionField::Concrete("0.1.0".to_string())),
+        ]);
+
+        let selection = PackageSelection::Workspace;
+
+        workspace
+            .roll_version(VersionBump::Patch(1), &selection)
+            .unwrap();
+
+        assert_eq!(
+            workspace.members[0].version(),
+            &VersionField::Concrete("1.0.1".to_string())
+        ); // app bumped
+        assert_eq!(
+            workspace.members[1].version(),
+            &VersionField::Concrete("0.5.1".to_string())
+        ); // lib bumped (no longer excluded)
+
+        assert_eq!(
+            workspace.members[2].version(),
+            &VersionField::Concrete("0.1.1".to_string())
+        ); // utils bumped
+    }
+
+    #[test]
+    fn test_workspace_set_version() {
+        let mut workspace = create_test_workspace(vec![
+            ("app", VersionField::Concrete("1.0.0".to_string())),
+            ("lib", VersionField::Concrete("0.5.0".to_string())),
+        ]);
+
+        let selection = PackageSelection::packages(vec!["lib".to_string()]);
+        workspace.set_version("2.0.0", &selection).unwrap();
+
+        assert_eq!(
+            workspace.members[0].version(),
+            &VersionField::Concrete("1.0.0".to_string())
+        ); // app unchanged
+
+        assert_eq!(
+            workspace.members[1].version(),
+            &VersionField::Concrete("2.0.0".to_string())
+        ); // lib set
+    }
+
+    #[test]
+    fn test_workspace_sync_version() {
+        let mut workspace = create_test_workspace(vec![
+            ("app", VersionField::Concrete("1.0.0".to_string())),
+            ("lib", VersionField::Concrete("0.5.0".to_string())),
+            ("utils", VersionField::Concrete("2.1.0".to_string())),
+        ]);
+
+        workspace.sync_version("1.5.0").unwrap();
+
+        assert_eq!(
+            workspace.members[0].version(),
+            &VersionField::Concrete("1.5.0".to_string())
+        );
+
+        assert_eq!(
+            workspace.members[1].version(),
+            &VersionField::Concrete("1.5.0".to_string())
+        );
+
+        assert_eq!(
+            workspace.members[2].version(),
+            &VersionField::Concrete("1.5.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_workspace_show() {
+        let workspace = create_test_workspace(vec![
+            ("app", VersionField::Concrete("1.0.0".to_string())),
+            ("lib", VersionField::Concrete("0.5.0".to_string())),
+        ]);
+
+        let output = show::to_text(&workspace.summarize(&PackageSelection::workspace()).unwrap());
+        assert_eq!(output, "app: 1.0.0\nlib: 0.5.0\n");
+    }
+
+    #[test]
+    fn test_workspace_lint_valid() {
+        let mut workspace = create_test_workspace(vec![
+            ("app", VersionField::Concrete("1.0.0".to_string())),
+            ("lib", VersionField::Concrete("0.5.0".to_string())),
+        ]);
+        workspace.root = Some(workspace.members[0].manifest_path());
+
+        let errors = workspace
+            .lint(&PackageSelection::root_only(), &LintOptions::default())
+            .unwrap();
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_workspace_lint_invalid() {
+        let workspace = create_test_workspace(vec![
+            ("app", VersionField::Concrete("1.0.0".to_string())),
+            ("lib", VersionField::Concrete("invalid-version".to_string())),
+        ]);
+
+        let errors = workspace
+            .lint(&PackageSelection::workspace(), &LintOptions::default())
+            .unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].member, "lib");
+        assert!(errors[0]
+            .message
+            .contains("Invalid version 'invalid-version'"));
+    }
+
+    #[test]
+    fn test_package_selection_not_found() {
+        let mut workspace =
+            create_test_workspace(vec![("app", VersionField::Concrete("1.0.0".to_string()))]);
+
+        let selection = PackageSelection::packages(vec!["nonexistent".to_string()]);
+        let result = workspace.roll_version(VersionBump::Patch(1), &selection);
+
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Package 'nonexistent' not found"));
+    }
+
+    #[test]
+    fn test_workspace_roll_version_invalid_operation() {
+        // Test what happens when a roll operation would result in invalid versions
+        let mut workspace = create_test_workspace(vec![
+            ("app", VersionField::Concrete("0.1.0".to_string())),
+            ("lib", VersionField::Concrete("0.1.0".to_string())),
+        ]);
+
+        let selection = PackageSelection::workspace();
+        let result = workspace.roll_version(VersionBump::Patch(-2), &selection);
+
+        // Should return an error explaining why the operation failed
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Cannot decrement patch version by 2 from 0.1.0"));
+
+        // Versions should remain unchanged
+        assert_eq!(
+            workspace.members[0].version(),
+            &VersionField::Concrete("0.1.0".to_string())
+        );
+
+        assert_eq!(
+            workspace.members[1].version(),
+            &VersionField::Concrete("0.1.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_version_bump_cross_component_boundaries() {
+        // Test that we don't "borrow" from higher components
+        let bump = VersionBump::Patch(-1);
+        let result = bump.apply_to_version("1.2.0");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Cannot decrement patch version by 1 from 1.2.0"));
+
+        let bump = VersionBump::Minor(-1);
+        let result = bump.apply_to_version("1.0.5");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Cannot decrement minor version by 1 from 1.0.5"));
+    }
+
+    #[test]
+    fn test_version_bump_absolute_minimum() {
+        // Test rolling back from 0.0.0
+        let bump = VersionBump::Patch(-1);
+        let result = bump.apply_to_version("0.0.0");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Cannot decrement patch version by 1 from 0.0.0"));
+
+        let bump = VersionBump::Minor(-1);
+        let result = bump.apply_to_version("0.0.0");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Cannot decrement minor version by 1 from 0.0.0"));
+
+        let bump = VersionBump::Major(-1);
+        let result = bump.apply_to_version("0.0.0");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Cannot decrement major version by 1 from 0.0.0"));
+    }
+
+    #[test]
+    fn test_workspace_roll_version_mixed_validity() {
+        // Test workspace with mixed versions where some can handle rollback and others can't
+        let mut workspace = create_test_workspace(vec![
+            ("app", VersionField::Concrete("1.0.2".to_string())), // Can handle patch -2
+            ("lib", VersionField::Concrete("0.1.0".to_string())), // Cannot handle patch -2 (will cause error)
+            ("utils", VersionField::Concrete("2.5.3".to_string())), // Can handle patch -2
+        ]);
+
+        let selection = PackageSelection::workspace();
+        let result = workspace.roll_version(VersionBump::Patch(-2), &selection);
+
+        // Should fail on the invalid package
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Cannot decrement patch version by 2 from 0.1.0"));
+
+        // Note: Even though first package gets modified in-memory during processing,
+        // the CLI layer won't save the workspace when domain operations fail,
+        // so from the user's perspective, no changes occur to disk
+        //
+        // However, this test is only exercising domain logic, so we see the in-memory changes:
+        assert_eq!(
+            workspace.members[0].version(),
+            &VersionField::Concrete("1.0.0".to_string()),
+            "app was modified in-memory"
+        );
+
+        assert_eq!(
+            workspace.members[1].version(),
+            &VersionField::Concrete("0.1.0".to_string()),
+            "lib unchanged (caused error)"
+        );
+
+        assert_eq!(
+            workspace.members[2].version(),
+            &VersionField::Concrete("2.5.3".to_string()),
+            "utils unchanged (not processed)"
+        );
+    }
+
+    #[test]
+    fn test_version_bump_large_negative_numbers() {
+        // Test very large negative numbers
+        let bump = VersionBump::Patch(-999999);
+        let result = bump.apply_to_version("1.0.5");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Cannot decrement patch version by 999999 from 1.0.5"));
+    }
+
+    #[test]
+    fn test_version_bump_prerelease_versions() {
+        // Test how pre-release versions behave with rollback
+        let bump = VersionBump::Patch(-1);
+
+        // Rolling back from 1.0.1-alpha preserves the pre-release identifier
+        assert_eq!(bump.apply_to_version("1.0.1-alpha").unwrap(), "1.0.0-alpha");
+
+        // Rolling back from 1.0.0-alpha should fail (can't go to 0.-1.0-alpha)
+        let result = bump.apply_to_version("1.0.0-alpha");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Cannot decrement patch version by 1 from 1.0.0-alpha"));
+
+        // Test minor rollback with pre-release (resets patch to 0, preserves pre-release)
+        let bump = VersionBump::Minor(-1);
+        assert_eq!(bump.apply_to_version("1.1.5-beta").unwrap(), "1.0.0-beta");
+
+        // Test major rollback with pre-release (resets minor and patch to 0, preserves pre-release)
+        let bump = VersionBump::Major(-1);
+        assert_eq!(bump.apply_to_version("2.3.5-rc.1").unwrap(), "1.0.0-rc.1");
+    }
+
+    #[test]
+    fn test_version_bump_clears_prerelease() {
+        // Bumping the level a prerelease is heading for releases it
+        assert_eq!(
+            VersionBump::Patch(1)
+                .apply_to_version("1.0.0-alpha.1")
+                .unwrap(),
+            "1.0.0"
+        );
+        assert_eq!(
+            VersionBump::Minor(1)
+                .apply_to_version("1.3.0-beta.2")
+                .unwrap(),
+            "1.3.0"
+        );
+        assert_eq!(
+            VersionBump::Major(1)
+                .apply_to_version("2.0.0-rc.1")
+                .unwrap(),
+            "2.0.0"
+        );
+
+        // Bumping past it drops the prerelease
+        assert_eq!(
+            VersionBump::Minor(1)
+                .apply_to_version("1.0.1-alpha.1")
+                .unwrap(),
+            "1.1.0"
+        );
+        assert_eq!(
+            VersionBump::Major(1)
+                .apply_to_version("1.2.0-alpha.1")
+                .unwrap(),
+            "2.0.0"
+        );
+        assert_eq!(
+            VersionBump::Major(1)
+                .apply_to_version("1.3.0-beta.2")
+                .unwrap(),
+            "2.0.0"
+        );
+
+        // Graduating is the first step of a larger bump
+        assert_eq!(
+            VersionBump::Patch(3)
+                .apply_to_version("1.0.0-alpha.1")
+                .unwrap(),
+            "1.0.2"
+        );
+        assert_eq!(
+            VersionBump::Minor(2)
+                .apply_to_version("1.3.0-beta.2")
+                .unwrap(),
+            "1.4.0"
+        );
+    }
+
+    #[test]
+    fn test_version_bump_start_prerelease() {
+        let beta = "beta".to_string();
+        assert_eq!(
+            VersionBump::Premajor(beta.clone())
+                .apply_to_version("1.2.3")
+                .unwrap(),
+            "2.0.0-beta.0"
+        );
+        assert_eq!(
+            VersionBump::Preminor(beta.clone())
+                .apply_to_version("1.2.3")
+                .unwrap(),
+            "1.3.0-beta.0"
+        );
+        assert_eq!(
+            VersionBump::Prepatch(beta.clone())
+                .apply_to_version("1.2.3")
+                .unwrap(),
+            "1.2.4-beta.0"
+        );
+        assert!(VersionBump::Preminor("".to_string())
+            .apply_to_version("1.2.3")
+            .is_err());
+        assert!(VersionBump::Preminor("not valid".to_string())
+            .apply_to_version("1.2.3")
+            .is_err());
+    }
+
+    #[test]
+    fn test_version_bump_prerelease_counter() {
+        let bump = VersionBump::Prerelease(None);
+        assert_eq!(
+            bump.apply_to_version("1.3.0-beta.0").unwrap(),
+            "1.3.0-beta.1"
+        );
+        assert_eq!(
+            bump.apply_to_version("1.3.0-beta.9").unwrap(),
+            "1.3.0-beta.10"
+        );
+        assert_eq!(bump.apply_to_version("1.3.0-beta").unwrap(), "1.3.0-beta.0");
+        assert_eq!(bump.apply_to_version("1.3.0-4").unwrap(), "1.3.0-5");
+
+        // Same channel as the current prerelease just increments
+        let bump = VersionBump::Prerelease(Some("beta".to_string()));
+        assert_eq!(
+            bump.apply_to_version("1.3.0-beta.1").unwrap(),
+            "1.3.0-beta.2"
+        );
+
+        // Not a prerelease yet: needs an identifier
+        let result = VersionBump::Prerelease(None).apply_to_version("1.2.3");
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("1.2.3 is not a prerelease"));
+        assert_eq!(bump.apply_to_version("1.2.3").unwrap(), "1.2.4-beta.0");
+    }
+
+    #[test]
+    fn test_version_bump_prerelease_promotion() {
+        let bump = VersionBump::Prerelease(Some("beta".to_string()));
+        assert_eq!(
+            bump.apply_to_version("1.3.0-alpha.3").unwrap(),
+            "1.3.0-beta.0"
+        );
+
+        let bump = VersionBump::Prerelease(Some("rc".to_string()));
+        assert_eq!(bump.apply_to_version("1.3.0-beta.1").unwrap(), "1.3.0-rc.0");
+
+        // Channels never move backwards
+        let bump = VersionBump::Prerelease(Some("alpha".to_string()));
+        let result = bump.apply_to_version("1.3.0-rc.1");
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("prerelease channels must move forward"));
+    }
+
+    #[test]
+    fn test_version_bump_release() {
+        let bump = VersionBump::Release;
+        assert_eq!(bump.apply_to_version("1.3.0-rc.2").unwrap(), "1.3.0");
+        assert_eq!(bump.apply_to_version("1.3.0").unwrap(), "1.3.0");
+    }
+
+    #[test]
+    fn test_workspace_roll_prerelease_lifecycle() {
+        let mut workspace =
+            create_test_workspace(vec![("app", VersionField::Concrete("1.2.3".to_string()))]);
+        let selection = PackageSelection::workspace();
+
+        for (bump, expected) in [
+            (VersionBump::Preminor("alpha".to_string()), "1.3.0-alpha.0"),
+            (VersionBump::Prerelease(None), "1.3.0-alpha.1"),
+            (
+                VersionBump::Prerelease(Some("beta".to_string())),
+                "1.3.0-beta.0",
+            ),
+            (
+                VersionBump::Prerelease(Some("rc".to_string())),
+                "1.3.0-rc.0",
+            ),
+            (VersionBump::Release, "1.3.0"),
+        ] {
+            workspace.roll_version(bump, &selection).unwrap();
+            assert_eq!(
+                workspace.members[0].version(),
+                &VersionField::Concrete(expected.to_string())
+            );
+        }
+
+        // Releasing a version that is not a prerelease changes nothing
+        let result = workspace
+            .roll_version(VersionBump::Release, &selection)
+            .unwrap();
+        assert!(!result.has_changes());
+        assert_eq!(result.operation, "roll release");
+    }
+
+    #[test]
+    fn test_workspace_roll_calver_member() {
+        use scheme::{Date, FixedClock};
+
+        let mut workspace = create_test_workspace(vec![
+            ("api", VersionField::Concrete("2024.2.3".to_string())),
+            ("lib", VersionField::Concrete("1.2.3".to_string())),
+        ]);
+        workspace.schemes.insert(
+            workspace.members[0].path().clone(),
+            "calver:YYYY.MM.MICRO".parse().unwrap(),
+        );
+        let clock = FixedClock(Date::new(2024, 3, 15));
+
+        let result = workspace
+            .roll_version_with_clock(
+                VersionBump::Patch(1),
+                &PackageSelection::workspace(),
+                &clock,
+            )
+            .unwrap();
+        assert_eq!(result.changes[0].new_version, "2024.3.0");
+        assert_eq!(result.changes[1].new_version, "1.2.4");
+
+        let result = workspace
+            .roll_version_with_clock(
+                VersionBump::Patch(1),
+                &PackageSelection::workspace(),
+                &clock,
+            )
+            .unwrap();
+        assert_eq!(result.changes[0].new_version, "2024.3.1");
+
+        // Lint validates each member against its own scheme
+        let errors = workspace
+            .lint(&PackageSelection::workspace(), &LintOptions::default())
+            .unwrap();
+        assert!(errors.is_empty());
+        workspace.members[0].set_version(VersionField::Concrete("1.0.0".to_string()));
+        let errors = workspace
+            .lint(&PackageSelection::workspace(), &LintOptions::default())
+            .unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("does not match calver format"));
+    }
+
+    fn create_grouped_workspace(kind: GroupKind) -> Workspace {
+        let mut workspace = create_test_workspace(vec![
+            ("core", VersionField::Concrete("1.2.0".to_string())),
+            ("macros", VersionField::Concrete("1.4.0".to_string())),
+            ("cli", VersionField::Concrete("0.3.0".to_string())),
+        ]);
+        workspace.groups.push(VersionGroup {
+            name: "core".to_string(),
+            kind,
+            members: vec!["core".to_string(), "macros".to_string()],
+        });
+        workspace
+    }
+
+    #[test]
+    fn test_workspace_roll_fixed_group() {
+        let mut workspace = create_grouped_workspace(GroupKind::Fixed);
+
+        // Selecting one member bumps the whole group from its highest version
+        let result = workspace
+            .roll_version(
+                VersionBump::Minor(1),
+                &PackageSelection::packages(vec!["core".to_string()]),
+            )
+            .unwrap();
+        assert_eq!(result.changes.len(), 2);
+        assert_eq!(result.changes[0].package, "core");
+        assert_eq!(result.changes[0].new_version, "1.5.0");
+        assert_eq!(result.changes[1].package, "macros");
+        assert_eq!(result.changes[1].new_version, "1.5.0");
+
+        let result = workspace
+            .set_version(
+                "2.0.0",
+                &PackageSelection::packages(vec!["macros".to_string()]),
+            )
+            .unwrap();
+        assert_eq!(result.changes.len(), 2);
+        assert_eq!(
+            *workspace.members[0].version(),
+            VersionField::Concrete("2.0.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_workspace_roll_linked_group() {
+        let mut workspace = create_grouped_workspace(GroupKind::Linked);
+
+        // Only the selected member moves, but from the group's highest version
+        let result = workspace
+            .roll_version(
+                VersionBump::Patch(1),
+                &PackageSelection::packages(vec!["core".to_string()]),
+            )
+            .unwrap();
+        assert_eq!(result.changes.len(), 1);
+        assert_eq!(result.changes[0].old_version, "1.2.0");
+        assert_eq!(result.changes[0].new_version, "1.4.1");
+
+        let result = workspace
+            .roll_version(VersionBump::Minor(1), &PackageSelection::workspace())
+            .unwrap();
+        assert_eq!(result.changes[0].new_version, "1.5.0");
+        assert_eq!(result.changes[1].new_version, "1.5.0");
+        assert_eq!(result.changes[2].new_version, "0.4.0");
+    }
+
+    #[test]
+    fn test_workspace_set_linked_group_below_highest() {
+        let mut workspace = create_grouped_workspace(GroupKind::Linked);
+
+        let err = workspace
+            .set_version(
+                "1.3.0",
+                &PackageSelection::packages(vec!["core".to_string()]),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Cannot set 'core' to 1.3.0: linked group 'core' is already at 1.4.0"
+        );
+
+        // Setting every member of the group may lower it
+        workspace
+            .set_version(
+                "1.3.0",
+                &PackageSelection::packages(vec!["core".to_string(), "macros".to_string()]),
+            )
+            .unwrap();
+        workspace
+            .set_version(
+                "1.3.1",
+                &PackageSelection::packages(vec!["core".to_string()]),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_requirement_accepts() {
+        let cargo = |requirement| requirement_accepts(Ecosystem::Cargo, requirement, "0.5.0");
+        assert_eq!(cargo("0.5"), Some(true));
+        assert_eq!(cargo("0.4"), Some(false));
+        assert_eq!(cargo("=0.5.0"), Some(true));
+        assert_eq!(cargo(">=0.3, <0.5"), Some(false));
+        assert_eq!(cargo("*"), Some(true));
+        assert_eq!(cargo("not a requirement"), None);
+
+        let npm = |requirement| requirement_accepts(Ecosystem::Node, requirement, "1.4.2");
+        assert_eq!(npm("^1.0.0"), Some(true));
+        assert_eq!(npm("~1.3.0"), Some(false));
+        assert_eq!(npm("1.4.2"), Some(true));
+        assert_eq!(npm("1.4.0"), Some(false)); // bare npm versions are exact
+        assert_eq!(npm("1.x"), Some(true));
+        assert_eq!(npm("1.3.x"), Some(false));
+        assert_eq!(npm(">= 1.0.0 < 1.4.0"), Some(false));
+        assert_eq!(npm("1.0.0 - 1.5"), Some(true));
+        assert_eq!(npm("^0.9.0 || ^1.4.0"), Some(true));
+        assert_eq!(npm("workspace:^1.0.0"), Some(true));
+        assert_eq!(npm("workspace:~2.0.0"), Some(false));
+        assert_eq!(npm("workspace:*"), None);
+        assert_eq!(npm("file:../core"), None);
+        assert_eq!(npm("latest"), None);
+        assert_eq!(npm(""), Some(true));
+
+        assert_eq!(
+            requirement_accepts(Ecosystem::Cargo, "1.0", "not-semver"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_workspace_roll_updates_requirements_on_inheriting_members() {
+        let create_workspace = || {
+            let mut workspace = create_test_workspace(vec![
+                ("app", VersionField::Concrete("1.0.0".to_string())),
+                ("core", VersionField::Inherited),
+                ("ws", VersionField::Concrete("1.2.0".to_string())),
+            ]);
+            workspace.root = Some(workspace.members[2].manifest_path());
+            workspace.members[0]
+                .dependencies_mut()
+                .push(create_test_dependency("core", "1.2"));
+            workspace.members[2].dependencies_mut().push(Dependency {
+                table: "workspace.dependencies".to_string(),
+                ..create_test_dependency("core", "1.2")
+            });
+            workspace
+        };
+
+        let mut workspace = create_workspace();
+        let result = workspace
+            .roll_version(VersionBump::Major(1), &PackageSelection::workspace())
+            .unwrap();
+        assert_eq!(result.changes.len(), 2);
+        let rewritten: Vec<_> = result
+            .dependency_changes
+            .iter()
+            .map(|c| {
+                (
+                    c.package.as_str(),
+                    c.table.as_str(),
+                    c.new_requirement.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            rewritten,
+            vec![
+                ("app", "dependencies", "2.0"),
+                ("ws", "workspace.dependencies", "2.0"),
+            ]
+        );
+
+        let mut workspace = create_workspace();
+        let result = workspace.sync_version("3.0.0").unwrap();
+        assert_eq!(result.dependency_changes.len(), 2);
+        assert_eq!(
+            workspace.members[0].dependencies()[0]
+                .requirement
+                .as_deref(),
+            Some("3.0")
+        );
+    }
+
+    #[test]
+    fn test_workspace_lint_requirements() {
+        let mut workspace = create_test_workspace(vec![
+            ("app", VersionField::Concrete("1.0.0".to_string())),
+            ("core", VersionField::Concrete("0.5.0".to_string())),
+            ("util", VersionField::Inherited),
+            ("ws", VersionField::Concrete("2.1.0".to_string())),
+        ]);
+        workspace.root = Some(workspace.members[3].manifest_path());
+        *workspace.members[0].dependencies_mut() = vec![
+            create_test_dependency("core", "0.4"),
+            create_test_dependency("util", "2.1"),
+            create_test_dependency("serde", "0.1"),
+            Dependency {
+                table: "dev-dependencies".to_string(),
+                ..create_test_dependency("util", "=2.0.0")
+            },
+        ];
+        workspace.members.push(WorkspaceMember::Node {
+            name: "web".to_string(),
+            path: PathBuf::from("web"),
+            version: VersionField::Concrete("1.0.0".to_string()),
+            dependencies: vec![create_test_dependency("core", "^9.0.0")],
+        });
+
+        let errors = workspace
+            .lint(&PackageSelection::workspace(), &LintOptions::default())
+            .unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].member, "app");
+        assert_eq!(
+            errors[0].message,
+            "Requirement '0.4' on core does not accept its version 0.5.0"
+        );
+        assert_eq!(errors[0].path, workspace.members[0].manifest_path());
+        assert_eq!(errors[0].location.as_deref(), Some("dependencies.core"));
+        assert_eq!(
+            errors[1].message,
+            "Requirement '=2.0.0' on util does not accept its version 2.1.0"
+        );
+        assert_eq!(errors[1].location.as_deref(), Some("dev-dependencies.util"));
+
+        // Only the selected members' requirements are checked
+        let errors = workspace
+            .lint(
+                &PackageSelection::packages(vec!["core".to_string()]),
+                &LintOptions::default(),
+            )
+            .unwrap();
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_workspace_lint_lockstep() {
+        let mut workspace = create_test_workspace(vec![
+            ("app", VersionField::Concrete("1.3.0".to_string())),
+            ("core", VersionField::Concrete("1.2.0".to_string())),
+            ("macros", VersionField::Concrete("1.3.0".to_string())),
+            ("util", VersionField::Inherited),
+        ]);
+        assert!(workspace
+            .lint(&PackageSelection::workspace(), &LintOptions::default())
+            .unwrap()
+            .is_empty());
+
+        let lockstep = LintOptions {
+            lockstep: true,
+            ..Default::default()
+        };
+        let errors = workspace
+            .lint(&PackageSelection::workspace(), &lockstep)
+            .unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].member, "core");
+        assert_eq!(
+            errors[0].message,
+            "Version '1.2.0' differs from lockstep version 1.3.0 (shared by 2 of 3 members)"
+        );
+
+        // A tie goes to the highest version; unselected members don't count
+        let selection = PackageSelection::packages(vec!["app".to_string(), "core".to_string()]);
+        let errors = workspace.lint(&selection, &lockstep).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].member, "core");
+
+        // Members inside a workspace offering `[workspace.package]` should inherit it
+        workspace.members.push(WorkspaceMember::Cargo {
+            name: "ws".to_string(),
+            path: PathBuf::new(),
+            version: VersionField::Concrete("1.3.0".to_string()),
+            dependencies: Vec::new(),
+        });
+        workspace.root = Some(PathBuf::from("Cargo.toml"));
+        workspace.inheritable_version = true;
+        let errors = workspace
+            .lint(
+                &PackageSelection::packages(vec!["app".to_string(), "ws".to_string()]),
+                &lockstep,
+            )
+            .unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].member, "app");
+        assert_eq!(
+            errors[0].message,
+            "Declares version '1.3.0' instead of inheriting the workspace version \
+             (version.workspace = true)"
+        );
+    }
+
+    #[test]
+    fn test_workspace_lint_groups() {
+        let mut workspace = create_grouped_workspace(GroupKind::Fixed);
+        workspace.groups[0].members.push("missing".to_string());
+
+        let errors = workspace
+            .lint(&PackageSelection::workspace(), &LintOptions::default())
+            .unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].member, "core");
+        assert_eq!(
+            errors[0].message,
+            "Version '1.2.0' differs from fixed group 'core' (1.4.0)"
+        );
+        assert_eq!(errors[1].member, "missing");
+
+        // Linked groups may drift apart
+        workspace.groups[0].kind = GroupKind::Linked;
+        workspace.groups[0].members.pop();
+        assert!(workspace
+            .lint(&PackageSelection::workspace(), &LintOptions::default())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_workspace_roll_auto() {
+        use conventional::{BumpDecision, Commit};
+
+        let commit = |message: &str| Commit {
+            hash: "abc".to_string(),
+            message: message.to_string(),
+        };
+        let mut workspace = create_grouped_workspace(GroupKind::Fixed);
+        let decisions = vec![
+            BumpDecision::from_commits("core", None, &[commit("fix: one")]),
+            BumpDecision::from_commits("macros", None, &[commit("feat: two")]),
+            BumpDecision::from_commits("cli", None, &[commit("feat!: three")]),
+        ];
+
+        let result = workspace
+            .roll_auto(decisions.clone(), ZeroMajor::Shift)
+            .unwrap();
+        assert_eq!(result.operation, "roll auto");
+
+        // The fixed group takes its highest level, and 0.x shifts breaking changes to minor
+        let versions = result
+            .changes
+            .iter()
+            .map(|c| (c.package.as_str(), c.new_version.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            versions,
+            vec![("core", "1.5.0"), ("macros", "1.5.0"), ("cli", "0.4.0")]
+        );
+        assert_eq!(result.decisions[0].bump.as_deref(), Some("minor"));
+        assert_eq!(result.decisions[2].bump.as_deref(), Some("minor"));
+
+        let mut workspace = create_grouped_workspace(GroupKind::Fixed);
+        let result = workspace.roll_auto(decisions, ZeroMajor::Normal).unwrap();
+        assert_eq!(result.changes[2].new_version, "1.0.0");
+
+        // Packages without release-worthy commits are reported but not bumped
+        let mut workspace = create_grouped_workspace(GroupKind::Fixed);
+        let decisions = vec![BumpDecision::from_commits(
+            "cli",
+            None,
+            &[commit("chore: x")],
+        )];
+        let result = workspace.roll_auto(decisions, ZeroMajor::Shift).unwrap();
+        assert!(!result.has_changes());
+        assert_eq!(result.decisions[0].bump, None);
+    }
+
+    #[test]
+    fn test_workspace_roll_levels() {
+        let mut workspace = create_grouped_workspace(GroupKind::Fixed);
+        let levels = vec![
+            ("cli".to_string(), Level::Major),
+            ("macros".to_string(), Level::Patch),
+        ];
+
+        let result = workspace
+            .roll_levels("changeset version", &levels, ZeroMajor::Normal)
+            .unwrap();
+        assert_eq!(result.operation, "changeset version");
+        let versions = result
+            .changes
+            .iter()
+            .map(|c| (c.package.as_str(), c.new_version.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            versions,
+            vec![("core", "1.4.1"), ("macros", "1.4.1"), ("cli", "1.0.0")]
+        );
+
+        let levels = vec![("missing".to_string(), Level::Patch)];
+        let err = workspace
+            .roll_levels("changeset version", &levels, ZeroMajor::Normal)
+            .unwrap_err();
+        assert!(err.to_string().contains("'missing' not found"));
+    }
+
+    #[test]
+    fn test_workspace_roll_levels_inherited() {
+        let mut workspace = create_test_workspace(vec![
+            ("core", VersionField::Inherited),
+            ("macros", VersionField::Inherited),
+            ("ws", VersionField::Concrete("1.2.0".to_string())),
+        ]);
+        workspace.root = Some(workspace.members[2].manifest_path());
+
+        let levels = vec![
+            ("core".to_string(), Level::Minor),
+            ("macros".to_string(), Level::Patch),
+        ];
+        let result = workspace
+            .roll_levels("changeset version", &levels, ZeroMajor::Normal)
+            .unwrap();
+        let versions = result
+            .changes
+            .iter()
+            .map(|c| (c.package.as_str(), c.new_version.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(versions, vec![("ws", "1.3.0")]);
+
+        workspace.root = None;
+        let levels = vec![("core".to_string(), Level::Patch)];
+        let err = workspace
+            .roll_levels("changeset version", &levels, ZeroMajor::Normal)
+            .unwrap_err();
+        assert!(err.to_string().contains("'core' has no version to release"));
+    }
+
+    #[test]
+    fn test_selected_members_sorting() {
+        let workspace = create_test_workspace(vec![
+            ("zebra", VersionField::Concrete("1.0.0".to_string())),
+            ("apple", VersionField::Concrete("2.0.0".to_string())),
+            ("banana", VersionField::Concrete("3.0.0".to_string())),
+        ]);
+
+        let members = workspace.selected_members(&PackageSelection::workspace());
+        assert_eq!(members[0].name(), "apple");
+        assert_eq!(members[1].name(), "banana");
+        assert_eq!(members[2].name(), "zebra");
+    }
+
+    #[test]
+    fn test_selected_members_sorting_with_selection() {
+        let workspace = create_test_workspace(vec![
+            ("zebra", VersionField::Concrete("1.0.0".to_string())),
+            ("apple", VersionField::Concrete("2.0.0".to_string())),
+            ("banana", VersionField::Concrete("3.0.0".to_string())),
+        ]);
+
+        let selection = PackageSelection::packages(vec!["zebra".to_string(), "apple".to_string()]);
+        let members = workspace.selected_members(&selection);
+        assert_eq!(members[0].name(), "apple");
+        assert_eq!(members[1].name(), "zebra");
+    }
+
+    #[test]
+    fn test_version_bump_with_build_metadata() {
+        let bump = VersionBump::Patch(1);
+        let result = bump.apply_to_version("1.2.3+20130313144700").unwrap();
+        assert_eq!(result, "1.2.4+20130313144700");
+    }
+
+    #[test]
+    fn test_version_bump_with_prerelease_and_build() {
+        let bump = VersionBump::Patch(1);
+        let result = bump
+            .apply_to_version("1.2.3-beta.1+20130313144700")
+            .unwrap();
+        assert_eq!(result, "1.2.3+20130313144700");
+    }
+
+    #[test]
+    fn test_version_bump_zero_amount() {
+        let bump = VersionBump::Patch(0);
+        let result = bump.apply_to_version("1.2.3").unwrap();
+        assert_eq!(result, "1.2.3");
+    }
+
+    #[test]
+    fn test_workspace_roll_version_preserves_inherited() {
+        let mut workspace = create_test_workspace(vec![
+            ("pkg1", VersionField::Concrete("1.0.0".to_string())),
+            ("pkg2", VersionField::Inherited),
+        ]);
+        let selection = PackageSelection::workspace();
+        let result = workspace
+            .roll_version(VersionBump::Patch(1), &selection)
+            .unwrap();
+        assert_eq!(result.changes.len(), 1);
+        assert_eq!(result.changes[0].package, "pkg1");
+        assert_eq!(result.changes[0].new_version, "1.0.1");
+    }
+
+    #[test]
+    fn test_workspace_set_version_preserves_inherited() {
+        let mut workspace = create_test_workspace(vec![
+            ("pkg1", VersionField::Concrete("1.0.0".to_string())),
+            ("pkg2", VersionField::Inherited),
+        ]);
+        let selection = PackageSelection::workspace();
+        let result = workspace.set_version("2.0.0", &selection).unwrap();
+        assert_eq!(result.changes.len(), 1);
+        assert_eq!(result.changes[0].package, "pkg1");
+        assert_eq!(result.changes[0].new_version, "2.0.0");
+    }
+
+    #[test]
+    fn test_workspace_sync_version_preserves_inherited() {
+        let mut workspace = create_test_workspace(vec![
+            ("pkg1", VersionField::Concrete("1.0.0".to_string())),
+            ("pkg2", VersionField::Inherited),
+            ("pkg3", VersionField::Concrete("2.0.0".to_string())),
+        ]);
+        let result = workspace.sync_version("3.0.0").unwrap();
+        assert_eq!(result.changes.len(), 2);
+        assert!(result
+            .changes
+            .iter()
+            .any(|c| c.package == "pkg1" && c.new_version == "3.0.0"));
+        assert!(result
+            .changes
+            .iter()
+            .any(|c| c.package == "pkg3" && c.new_version == "3.0.0"));
+    }
+
+    #[test]
+    fn test_workspace_roll_version_skips_dynamic() {
+        let mut workspace = create_test_workspace(vec![
+            ("pkg1", VersionField::Concrete("1.0.0".to_string())),
+            ("pkg2", VersionField::Dynamic),
+        ]);
+        let selection = PackageSelection::workspace();
+        let result = workspace
+            .roll_version(VersionBump::Minor(1), &selection)
+            .unwrap();
+        assert_eq!(result.changes.len(), 1);
+        assert_eq!(result.changes[0].package, "pkg1");
+        assert_eq!(workspace.members[1].version(), &VersionField::Dynamic);
+    }
+
+    #[test]
+    fn test_workspace_show_includes_inherited() {
+        let workspace = create_test_workspace(vec![
+            ("pkg1", VersionField::Concrete("1.0.0".to_string())),
+            ("pkg2", VersionField::Inherited),
+        ]);
+        let selection = PackageSelection::workspace();
+        let output = show::to_text(&workspace.summarize(&selection).unwrap());
+        assert_eq!(output, "pkg1: 1.0.0\npkg2: (inherited)\n");
+    }
+
+    #[test]
+    fn test_workspace_lint_skips_inherited() {
+        let workspace = create_test_workspace(vec![
+            ("pkg1", VersionField::Concrete("1.0.0".to_string())),
+            ("pkg2", VersionField::Inherited),
+            ("pkg3", VersionField::Concrete("invalid".to_string())),
+        ]);
+        let selection = PackageSelection::workspace();
+        let errors = workspace.lint(&selection, &LintOptions::default()).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].member, "pkg3");
+    }
+
+    #[test]
+    fn test_package_selection_default_selects_root() {
+        let mut workspace = create_test_workspace(vec![
+            ("pkg1", VersionField::Concrete("1.0.0".to_string())),
+            ("pkg2", VersionField::Concrete("2.0.0".to_string())),
+            ("pkg3", VersionField::Concrete("3.0.0".to_string())),
+        ]);
+        workspace.root = Some(workspace.members[1].manifest_path());
+        let selection = PackageSelection::root_only();
+        let members = workspace.selected_members(&selection);
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].name(), "pkg2");
+    }
+
+    #[test]
+    fn test_package_selection_default_without_root_errors() {
+        let mut workspace = create_test_workspace(vec![
+            ("pkg1", VersionField::Concrete("1.0.0".to_string())),
+            ("pkg2", VersionField::Concrete("2.0.0".to_string())),
+        ]);
+        let result = workspace.roll_version(VersionBump::Patch(1), &PackageSelection::root_only());
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Could not determine the workspace root"));
+    }
+
+    #[test]
+    fn test_package_selection_default_root_not_a_member() {
+        let mut workspace =
+            create_test_workspace(vec![("pkg1", VersionField::Concrete("1.0.0".to_string()))]);
+        workspace.root = Some(PathBuf::from("elsewhere/Cargo.toml"));
+        let result = workspace.roll_version(VersionBump::Patch(1), &PackageSelection::root_only());
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("is not a workspace member"));
+    }
+
+    #[test]
+    fn test_package_selection_specific_packages() {
+        let workspace = create_test_workspace(vec![
+            ("pkg1", VersionField::Concrete("1.0.0".to_string())),
+            ("pkg2", VersionField::Concrete("2.0.0".to_string())),
+            ("pkg3", VersionField::Concrete("3.0.0".to_string())),
+        ]);
+        let selection = PackageSelection::packages(vec!["pkg1".to_string(), "pkg2".to_string()]);
+        let members = workspace.selected_members(&selection);
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[0].name(), "pkg1");
+        assert_eq!(members[1].name(), "pkg2");
+    }
+
+    #[test]
+    fn test_operation_result_has_changes() {
+        let mut result = OperationResult::new("test".to_string());
+        assert!(!result.has_changes());
+
+        result.add_change(VersionChange {
+            package: "pkg1".to_string(),
+            old_version: "1.0.0".to_string(),
+            new_version: "2.0.0".to_string(),
+            path: PathBuf::from("pkg1"),
+        });
+        assert!(result.has_changes());
+    }
+
+    #[test]
+    fn test_rewrite_requirement_preserves_operator_and_precision() {
+        assert_eq!(rewrite_requirement("1.2", "2.0.0").unwrap(), "2.0");
+        assert_eq!(rewrite_requirement("^1.2.0", "2.0.0").unwrap(), "^2.0.0");
+        assert_eq!(rewrite_requirement("~1.2", "1.3.4").unwrap(), "~1.3");
+        assert_eq!(rewrite_requirement("=1.2.0", "1.2.1").unwrap(), "=1.2.1");
+        assert_eq!(
+            rewrite_requirement("^1.2.3-beta.1", "1.2.3").unwrap(),
+            "^1.2.3"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_requirement_prerelease_uses_full_version() {
+        assert_eq!(
+            rewrite_requirement("^1.2", "2.0.0-rc.1").unwrap(),
+            "^2.0.0-rc.1"
+        );
+        assert_eq!(
+            rewrite_requirement("1.2.0", "1.3.0+build.5").unwrap(),
+            "1.3.0"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_requirement_workspace_protocol() {
+        assert_eq!(
+            rewrite_requirement("workspace:^1.2.0", "2.0.0").unwrap(),
+            "workspace:^2.0.0"
+        );
+        assert_eq!(rewrite_requirement("workspace:*", "2.0.0"), None);
+        assert_eq!(rewrite_requirement("workspace:^", "2.0.0"), None);
+    }
+
+    #[test]
+    fn test_rewrite_requirement_unsupported() {
+        assert_eq!(rewrite_requirement("*", "2.0.0"), None);
+        assert_eq!(rewrite_requirement("1.x", "2.0.0"), None);
+        assert_eq!(rewrite_requirement(">=1", "2.5.0"), None);
+        assert_eq!(rewrite_requirement(">=1.2, <2", "2.0.0"), None);
+        assert_eq!(rewrite_requirement(">=1.2 <2", "2.0.0"), None);
+        assert_eq!(rewrite_requirement("^1 || ^2", "3.0.0"), None);
+        assert_eq!(rewrite_requirement("<2.0.0", "3.0.0"), None);
+        assert_eq!(rewrite_requirement("file:../core", "2.0.0"), None);
+        assert_eq!(rewrite_requirement("^1.2", "not-semver"), None);
+    }
+
+    #[test]
+    fn test_workspace_roll_version_updates_dependents() {
+        let mut workspace = create_test_workspace(vec![
+            ("app", VersionField::Concrete("1.0.0".to_string())),
+            ("core", VersionField::Concrete("1.2.0".to_string())),
+        ]);
+        workspace.members[0]
+            .dependencies_mut()
+            .push(create_test_dependency("core", "^1.2"));
+
+        let selection = PackageSelection::packages(vec!["core".to_string()]);
+        let result = workspace
+            .roll_version(VersionBump::Major(1), &selection)
+            .unwrap();
+
+        assert_eq!(result.dependency_changes.len(), 1);
+        let change = &result.dependency_changes[0];
+        assert_eq!(change.package, "app");
+        assert_eq!(change.dependency, "core");
+        assert_eq!(change.table, "dependencies");
+        assert_eq!(change.old_requirement, "^1.2");
+        assert_eq!(change.new_requirement, "^2.0");
+        assert_eq!(
+            workspace.members[0].dependencies()[0].requirement,
+            Some("^2.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_package_selection_changed() {
+        let mut workspace = create_test_workspace(vec![
+            ("app", VersionField::Concrete("1.0.0".to_string())),
+            ("cli", VersionField::Concrete("1.0.0".to_string())),
+            ("core", VersionField::Concrete("1.0.0".to_string())),
+            ("docs", VersionField::Concrete("1.0.0".to_string())),
+        ]);
+        workspace.members[0]
+            .dependencies_mut()
+            .push(create_test_dependency("cli", "^1.0"));
+        workspace.members[1]
+            .dependencies_mut()
+            .push(create_test_dependency("core", "^1.0"));
+
+        let files = vec![
+            workspace.members[2].path().join("src/lib.rs"),
+            PathBuf::from("README.md"),
+        ];
+        let selection = PackageSelection::Changed {
+            files: files.clone(),
+            include_dependents: false,
+        };
+        let names = |workspace: &Workspace, selection: &PackageSelection| {
+            workspace
+                .select(selection)
+                .unwrap()
+                .iter()
+                .map(|m| m.name().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&workspace, &selection), vec!["core"]);
+
+        // Dependents are followed transitively
+        let selection = PackageSelection::Changed {
+            files,
+            include_dependents: true,
+        };
+        assert_eq!(names(&workspace, &selection), vec!["app", "cli", "core"]);
+
+        let result = workspace
+            .roll_version(VersionBump::Patch(1), &selection)
+            .unwrap();
+        assert_eq!(result.changes.len(), 3);
+
+        let selection = PackageSelection::ChangedSince {
+            reference: "v1.0.0".to_string(),
+            include_dependents: false,
+        };
+        assert!(workspace.select(&selection).is_err());
+    }
+
+    #[test]
+    fn test_workspace_roll_cascade() {
+        let create_workspace = || {
+            let mut workspace = create_test_workspace(vec![
+                ("app", VersionField::Concrete("1.0.0".to_string())),
+                ("cli", VersionField::Concrete("1.0.0".to_string())),
+                ("core", VersionField::Concrete("1.0.0".to_string())),
+                ("tool", VersionField::Concrete("1.0.0".to_string())),
+            ]);
+            workspace.members[0]
+                .dependencies_mut()
+                .push(create_test_dependency("cli", "^1.0"));
+            workspace.members[1]
+                .dependencies_mut()
+                .push(create_test_dependency("core", "^1.0"));
+            workspace.members[3].dependencies_mut().push(Dependency {
+                table: "dev-dependencies".to_string(),
+                ..create_test_dependency("core", "^1.0")
+            });
+            workspace.cascade = Some(Cascade {
+                level: Level::Patch,
+                zero_major: ZeroMajor::Shift,
+            });
+            workspace
+        };
+        let core = PackageSelection::packages(vec!["core".to_string()]);
+        let propagated = |result: &OperationResult| {
+            result
+                .propagated
+                .iter()
+                .map(|p| {
+                    (
+                        p.change.package.clone(),
+                        p.change.new_version.clone(),
+                        p.dependency.clone(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Dependents are bumped transitively; dev-dependencies don't count
+        let mut workspace = create_workspace();
+        let result = workspace
+            .roll_version(VersionBump::Minor(1), &core)
+            .unwrap();
+        assert_eq!(result.changes.len(), 1);
+        assert_eq!(
+            propagated(&result),
+            vec![
+                ("app".to_string(), "1.0.1".to_string(), "cli".to_string()),
+                ("cli".to_string(), "1.0.1".to_string(), "core".to_string()),
+            ]
+        );
+        assert_eq!(result.all_changes().count(), 3);
+
+        // A new major version of a dependency is a major change for its dependents
+        let mut workspace = create_workspace();
+        let result = workspace
+            .roll_version(VersionBump::Major(1), &core)
+            .unwrap();
+        assert_eq!(
+            propagated(&result),
+            vec![
+                ("app".to_string(), "2.0.0".to_string(), "cli".to_string()),
+                ("cli".to_string(), "2.0.0".to_string(), "core".to_string()),
+            ]
+        );
+        assert_eq!(result.dependency_changes[0].new_requirement, "^2.0");
+
+        // Members bumped by the operation itself are not bumped again
+        let mut workspace = create_workspace();
+        let both = PackageSelection::packages(vec!["core".to_string(), "cli".to_string()]);
+        let result = workspace
+            .roll_version(VersionBump::Patch(1), &both)
+            .unwrap();
+        assert_eq!(
+            propagated(&result),
+            vec![("app".to_string(), "1.0.1".to_string(), "cli".to_string())]
+        );
+
+        let mut workspace = create_workspace();
+        workspace.cascade = None;
+        let result = workspace
+            .roll_version(VersionBump::Minor(1), &core)
+            .unwrap();
+        assert!(result.propagated.is_empty());
+    }
+
+    #[test]
+    fn test_workspace_roll_cascade_zero_major() {
+        let create_workspace = |zero_major| {
+            let mut workspace = create_test_workspace(vec![
+                ("app", VersionField::Concrete("1.0.0".to_string())),
+                ("cli", VersionField::Concrete("0.2.0".to_string())),
+                ("core", VersionField::Concrete("0.3.0".to_string())),
+            ]);
+            workspace.members[0]
+                .dependencies_mut()
+                .push(create_test_dependency("cli", "^0.2"));
+            workspace.members[1]
+                .dependencies_mut()
+                .push(create_test_dependency("core", "^0.3"));
+            workspace.cascade = Some(Cascade {
+                level: Level::Patch,
+                zero_major,
+            });
+            workspace
+        };
+        let core = PackageSelection::packages(vec!["core".to_string()]);
+        let new_versions = |result: &OperationResult| {
+            result
+                .propagated
+                .iter()
+                .map(|p| (p.change.package.clone(), p.change.new_version.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        // 0.3.0 → 0.4.0 is breaking at 0.x: cli takes a (shifted) major bump,
+        // which is breaking for app in turn
+        let mut workspace = create_workspace(ZeroMajor::Shift);
+        let result = workspace
+            .roll_version(VersionBump::Minor(1), &core)
+            .unwrap();
+        assert_eq!(
+            new_versions(&result),
+            vec![
+                ("app".to_string(), "2.0.0".to_string()),
+                ("cli".to_string(), "0.3.0".to_string()),
+            ]
+        );
+
+        // A patch at 0.x is compatible
+        let mut workspace = create_workspace(ZeroMajor::Shift);
+        let result = workspace
+            .roll_version(VersionBump::Patch(1), &core)
+            .unwrap();
+        assert_eq!(
+            new_versions(&result),
+            vec![
+                ("app".to_string(), "1.0.1".to_string()),
+                ("cli".to_string(), "0.2.1".to_string()),
+            ]
+        );
+
+        // Without the shift only a new major version is breaking
+        let mut workspace = create_workspace(ZeroMajor::Normal);
+        let result = workspace
+            .roll_version(VersionBump::Minor(1), &core)
+            .unwrap();
+        assert_eq!(
+            new_versions(&result),
+            vec![
+                ("app".to_string(), "1.0.1".to_string()),
+                ("cli".to_string(), "0.2.1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_workspace_roll_cascade_inherited() {
+        let mut workspace = create_test_workspace(vec![
+            ("app", VersionField::Concrete("1.0.0".to_string())),
+            ("core", VersionField::Inherited),
+            ("ext", VersionField::Concrete("0.5.0".to_string())),
+            ("ws", VersionField::Concrete("1.2.0".to_string())),
+        ]);
+        workspace.root = Some(workspace.members[3].manifest_path());
+        workspace.members[0]
+            .dependencies_mut()
+            .push(create_test_dependency("core", "1.2"));
+        workspace.members[1]
+            .dependencies_mut()
+            .push(create_test_dependency("ext", "0.5"));
+        workspace.cascade = Some(Cascade {
+            level: Level::Patch,
+            zero_major: ZeroMajor::Normal,
+        });
+        let new_versions = |result: &OperationResult| {
+            result
+                .propagated
+                .iter()
+                .map(|p| (p.change.package.clone(), p.change.new_version.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        // core moves with the root, so app depends on a changed package
+        let ws = PackageSelection::packages(vec!["ws".to_string()]);
+        let result = workspace.roll_version(VersionBump::Minor(1), &ws).unwrap();
+        assert_eq!(
+            new_versions(&result),
+            vec![("app".to_string(), "1.0.1".to_string())]
+        );
+
+        // A dependency of core raises the root it inherits from
+        let ext = PackageSelection::packages(vec!["ext".to_string()]);
+        let result = workspace.roll_version(VersionBump::Patch(1), &ext).unwrap();
+        assert_eq!(
+            new_versions(&result),
+            vec![
+                ("app".to_string(), "1.0.2".to_string()),
+                ("ws".to_string(), "1.3.1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_workspace_sync_version_updates_dependents() {
+        let mut workspace = create_test_workspace(vec![
+            ("app", VersionField::Concrete("1.0.0".to_string())),
+            ("core", VersionField::Concrete("1.2.0".to_string())),
+            ("utils", VersionField::Concrete("0.3.0".to_string())),
+        ]);
+        workspace.members[0]
+            .dependencies_mut()
+            .push(create_test_dependency("core", "=1.2.0"));
+        workspace.members[0]
+            .dependencies_mut()
+            .push(create_test_dependency("utils", "0.3"));
+
+        let result = workspace.sync_version("2.0.0").unwrap();
+
+        assert_eq!(result.dependency_changes.len(), 2);
+        assert_eq!(
+            workspace.members[0].dependencies()[0].requirement,
+            Some("=2.0.0".to_string())
+        );
+        assert_eq!(
+            workspace.members[0].dependencies()[1].requirement,
+            Some("2.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_workspace_roll_version_ignores_other_ecosystems() {
+        let mut workspace =
+            create_test_workspace(vec![("core", VersionField::Concrete("1.2.0".to_string()))]);
+        workspace.members.push(WorkspaceMember::Node {
+            name: "web".to_string(),
+            path: PathBuf::from("web"),
+            version: VersionField::Concrete("1.0.0".to_string()),
+            dependencies: vec![create_test_dependency("core", "^1.2.0")],
+        });
+
+        let selection = PackageSelection::packages(vec!["core".to_string()]);
+        let result = workspace
+            .roll_version(VersionBump::Major(1), &selection)
+            .unwrap();
+
+        assert!(result.dependency_changes.is_empty());
+        assert_eq!(
+            workspace.members[1].dependencies()[0].requirement,
+            Some("^1.2.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_workspace_roll_version_unchanged_requirement_not_reported() {
+        let mut workspace = create_test_workspace(vec![
+            ("app", VersionField::Concrete("1.0.0".to_string())),
+            ("core", VersionField::Concrete("1.2.0".to_string())),
+        ]);
+        workspace.members[0]
+            .dependencies_mut()
+            .push(create_test_dependency("core", "1.2"));
+
+        let selection = PackageSelection::packages(vec!["core".to_string()]);
+        let result = workspace
+            .roll_version(VersionBump::Patch(1), &selection)
+            .unwrap();
+
+        assert_eq!(result.changes.len(), 1);
+        assert!(result.dependency_changes.is_empty());
+    }
+}
@@ -0,0 +1,554 @@
+use anyhow::Context;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::VersionBump;
+
+/// How a package's version string is structured and advanced
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum VersionScheme {
+    /// Semantic versioning (`MAJOR.MINOR.PATCH[-PRE][+BUILD]`)
+    #[default]
+    Semver,
+    /// Calendar versioning, e.g. `YYYY.MM.MICRO` or `YY.0W.N`
+    CalVer(CalVerFormat),
+}
+
+impl VersionScheme {
+    /// Compute the version that `bump` produces from `current`
+    ///
+    /// CalVer versions advance to today's date (per `clock`), or increment
+    /// their micro counter when the date is unchanged, whatever the bump kind
+    /// among major, minor and patch.
+    pub fn bump(
+        &self,
+        current: &str,
+        bump: &VersionBump,
+        clock: &dyn Clock,
+    ) -> anyhow::Result<String> {
+        match self {
+            VersionScheme::Semver => bump.apply_to_version(current),
+            VersionScheme::CalVer(format) => match bump {
+                VersionBump::Major(amount)
+                | VersionBump::Minor(amount)
+                | VersionBump::Patch(amount) => {
+                    if *amount < 1 {
+                        anyhow::bail!(
+                            "Cannot roll {} by {}: calver versions only move forward",
+                            current,
+                            amount
+                        );
+                    }
+                    format.advance(current, *amount as u64, clock.today())
+                }
+                _ => anyhow::bail!("Prerelease bumps are not supported by the {} scheme", self),
+            },
+        }
+    }
+
+    /// Check that `version` is valid under this scheme
+    pub fn validate(&self, version: &str) -> anyhow::Result<()> {
+        match self {
+            VersionScheme::Semver => {
+                semver::Version::parse(version)?;
+                Ok(())
+            }
+            VersionScheme::CalVer(format) => format.parse(version).map(|_| ()),
+        }
+    }
+}
+
+impl FromStr for VersionScheme {
+    type Err = anyhow::Error;
+
+    /// Parse `semver`, `calver` (`YYYY.MM.MICRO`) or `calver:<FORMAT>`
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.split_once(':') {
+            None if s == "semver" => Ok(VersionScheme::Semver),
+            None if s == "calver" => Ok(VersionScheme::CalVer("YYYY.MM.MICRO".parse()?)),
+            Some(("calver", format)) => Ok(VersionScheme::CalVer(format.parse()?)),
+            _ => anyhow::bail!(
+                "Unknown version scheme '{}' (expected 'semver', 'calver' or 'calver:<FORMAT>')",
+                s
+            ),
+        }
+    }
+}
+
+impl fmt::Display for VersionScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersionScheme::Semver => write!(f, "semver"),
+            VersionScheme::CalVer(format) => write!(f, "calver:{}", format),
+        }
+    }
+}
+
+/// A CalVer format such as `YYYY.0M.MICRO`
+///
+/// Supported tokens (see calver.org): `YYYY` (2024), `YY` (24), `0Y` (06),
+/// `MM` (1), `0M` (01), `WW` (1), `0W` (01), `DD` (1), `0D` (01) and the
+/// counter `MICRO` (alias `N`). Tokens are separated by `.`, `-` or `_`. Weeks
+/// are ISO 8601 weeks, and formats that use them take the year from the ISO
+/// week-numbering year.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalVerFormat {
+    format: String,
+    tokens: Vec<Token>,
+    separators: Vec<char>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    FullYear,
+    ShortYear,
+    PaddedYear,
+    Month,
+    PaddedMonth,
+    Week,
+    PaddedWeek,
+    Day,
+    PaddedDay,
+    Micro,
+}
+
+impl Token {
+    fn is_padded(self) -> bool {
+        matches!(
+            self,
+            Token::PaddedYear | Token::PaddedMonth | Token::PaddedWeek | Token::PaddedDay
+        )
+    }
+
+    fn is_week(self) -> bool {
+        matches!(self, Token::Week | Token::PaddedWeek)
+    }
+}
+
+impl FromStr for CalVerFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(format: &str) -> anyhow::Result<Self> {
+        let separators: Vec<char> = format.chars().filter(|c| is_separator(*c)).collect();
+        let tokens = format
+            .split(is_separator)
+            .map(|segment| {
+                Ok(match segment {
+                    "YYYY" => Token::FullYear,
+                    "YY" => Token::ShortYear,
+                    "0Y" => Token::PaddedYear,
+                    "MM" => Token::Month,
+                    "0M" => Token::PaddedMonth,
+                    "WW" => Token::Week,
+                    "0W" => Token::PaddedWeek,
+                    "DD" => Token::Day,
+                    "0D" => Token::PaddedDay,
+                    "MICRO" | "N" => Token::Micro,
+                    _ => anyhow::bail!("Unknown calver token '{}' in '{}'", segment, format),
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        if tokens.iter().filter(|t| **t == Token::Micro).count() > 1 {
+            anyhow::bail!("Calver format '{}' has more than one MICRO token", format);
+        }
+        if tokens.iter().all(|t| *t == Token::Micro) {
+            anyhow::bail!("Calver format '{}' has no date tokens", format);
+        }
+
+        Ok(Self {
+            format: format.to_string(),
+            tokens,
+            separators,
+        })
+    }
+}
+
+impl fmt::Display for CalVerFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.format)
+    }
+}
+
+impl CalVerFormat {
+    /// Parse `version` into one value per token
+    fn parse(&self, version: &str) -> anyhow::Result<Vec<u64>> {
+        let mismatch = || {
+            anyhow::anyhow!(
+                "Version '{}' does not match calver format '{}'",
+                version,
+                self.format
+            )
+        };
+
+        let separators: Vec<char> = version.chars().filter(|c| is_separator(*c)).collect();
+        let segments: Vec<&str> = version.split(is_separator).collect();
+        if separators != self.separators || segments.len() != self.tokens.len() {
+            return Err(mismatch());
+        }
+
+        let mut values = Vec::new();
+        for (token, segment) in self.tokens.iter().zip(&segments) {
+            let well_formed = !segment.is_empty()
+                && segment.chars().all(|c| c.is_ascii_digit())
+                && if token.is_padded() {
+                    segment.len() == 2 || (segment.len() > 2 && !segment.starts_with('0'))
+                } else {
+                    segment.len() == 1 || !segment.starts_with('0')
+                };
+            if !well_formed {
+                return Err(mismatch());
+            }
+
+            let value: u64 = segment.parse().map_err(|_| mismatch())?;
+            let in_range = match token {
+                Token::Month | Token::PaddedMonth => (1..=12).contains(&value),
+                Token::Week | Token::PaddedWeek => (1..=53).contains(&value),
+                Token::Day | Token::PaddedDay => (1..=31).contains(&value),
+                _ => true,
+            };
+            if !in_range {
+                return Err(mismatch());
+            }
+            values.push(value);
+        }
+
+        Ok(values)
+    }
+
+    /// Advance `current` to `today`, or bump its counter by `amount` on the same date
+    fn advance(&self, current: &str, amount: u64, today: Date) -> anyhow::Result<String> {
+        let values = self.parse(current)?;
+        let today_values = self.date_values(today);
+
+        let current_date = self.date_part(&values);
+        let today_date = self.date_part(&today_values);
+
+        let micro = if today_date > current_date {
+            0
+        } else if today_date == current_date {
+            let index = self
+                .tokens
+                .iter()
+                .position(|t| *t == Token::Micro)
+                .with_context(|| {
+                    format!(
+                        "{} is already today's version and '{}' has no MICRO counter",
+                        current, self.format
+                    )
+                })?;
+            values[index] + amount
+        } else {
+            anyhow::bail!(
+                "Cannot roll {}: it is dated after today ({})",
+                current,
+                today
+            );
+        };
+
+        let mut rendered = String::new();
+        for (i, (token, value)) in self.tokens.iter().zip(&today_values).enumerate() {
+            if i > 0 {
+                rendered.push(self.separators[i - 1]);
+            }
+            let value = if *token == Token::Micro {
+                micro
+            } else {
+                *value
+            };
+            if token.is_padded() {
+                rendered.push_str(&format!("{:02}", value));
+            } else {
+                rendered.push_str(&value.to_string());
+            }
+        }
+
+        Ok(rendered)
+    }
+
+    /// The value of each token for `date` (the counter is left at 0)
+    fn date_values(&self, date: Date) -> Vec<u64> {
+        let (week_year, week) = date.iso_week();
+        let year = if self.tokens.iter().any(|t| t.is_week()) {
+            week_year
+        } else {
+            date.year
+        } as u64;
+
+        self.tokens
+            .iter()
+            .map(|token| match token {
+                Token::FullYear => year,
+                Token::ShortYear | Token::PaddedYear => year.saturating_sub(2000),
+                Token::Month | Token::PaddedMonth => date.month as u64,
+                Token::Week | Token::PaddedWeek => week as u64,
+                Token::Day | Token::PaddedDay => date.day as u64,
+                Token::Micro => 0,
+            })
+            .collect()
+    }
+
+    /// The date components of `values`, most significant first
+    fn date_part(&self, values: &[u64]) -> Vec<u64> {
+        self.tokens
+            .iter()
+            .zip(values)
+            .filter(|(token, _)| **token != Token::Micro)
+            .map(|(_, value)| *value)
+            .collect()
+    }
+}
+
+fn is_separator(c: char) -> bool {
+    matches!(c, '.' | '-' | '_')
+}
+
+/// A calendar date (proleptic Gregorian, UTC)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    pub fn new(year: i32, month: u32, day: u32) -> Self {
+        Self { year, month, day }
+    }
+
+    /// Convert days since 1970-01-01 to a date
+    fn from_days(days: i64) -> Self {
+        // Howard Hinnant's civil_from_days
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+        Self { year, month, day }
+    }
+
+    /// Days since 1970-01-01
+    fn days(&self) -> i64 {
+        // Howard Hinnant's days_from_civil
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);
+        let month = i64::from(self.month);
+        let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5
+            + i64::from(self.day)
+            - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    /// ISO 8601 weekday, Monday = 1 through Sunday = 7
+    fn weekday(&self) -> i64 {
+        // 1970-01-01 was a Thursday
+        (self.days() + 3).rem_euclid(7) + 1
+    }
+
+    /// ISO 8601 (week-numbering year, week)
+    fn iso_week(&self) -> (i32, u32) {
+        let ordinal = self.days() - Date::new(self.year, 1, 1).days() + 1;
+        let week = (ordinal - self.weekday() + 10) / 7;
+
+        if week < 1 {
+            (self.year - 1, weeks_in_year(self.year - 1))
+        } else if week > i64::from(weeks_in_year(self.year)) {
+            (self.year + 1, 1)
+        } else {
+            (self.year, week as u32)
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Number of ISO weeks in `year` (52 or 53)
+fn weeks_in_year(year: i32) -> u32 {
+    let jan1 = Date::new(year, 1, 1).weekday();
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    if jan1 == 4 || (leap && jan1 == 3) {
+        53
+    } else {
+        52
+    }
+}
+
+/// Source of today's date, injectable so calver rolls are testable
+pub trait Clock {
+    fn today(&self) -> Date;
+}
+
+/// The system clock, in UTC
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn today(&self) -> Date {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        Date::from_days(seconds.div_euclid(86_400))
+    }
+}
+
+/// A clock that always returns the same date
+pub struct FixedClock(pub Date);
+
+impl Clock for FixedClock {
+    fn today(&self) -> Date {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calver(format: &str) -> VersionScheme {
+        VersionScheme::CalVer(format.parse().unwrap())
+    }
+
+    #[test]
+    fn test_parse_scheme() {
+        assert_eq!(
+            "semver".parse::<VersionScheme>().unwrap(),
+            VersionScheme::Semver
+        );
+        assert_eq!(
+            "calver".parse::<VersionScheme>().unwrap(),
+            calver("YYYY.MM.MICRO")
+        );
+        assert_eq!(
+            "calver:YY.0W.N".parse::<VersionScheme>().unwrap(),
+            calver("YY.0W.N")
+        );
+        assert!("calver:YYYY.QQ".parse::<VersionScheme>().is_err());
+        assert!("calver:MICRO".parse::<VersionScheme>().is_err());
+        assert!("romver".parse::<VersionScheme>().is_err());
+    }
+
+    #[test]
+    fn test_calver_roll_to_new_date() {
+        let scheme = calver("YYYY.MM.MICRO");
+        let clock = FixedClock(Date::new(2024, 3, 15));
+        assert_eq!(
+            scheme
+                .bump("2024.2.4", &VersionBump::Patch(1), &clock)
+                .unwrap(),
+            "2024.3.0"
+        );
+    }
+
+    #[test]
+    fn test_calver_roll_same_date_increments_micro() {
+        let scheme = calver("YYYY.0M.MICRO");
+        let clock = FixedClock(Date::new(2024, 3, 15));
+        assert_eq!(
+            scheme
+                .bump("2024.03.4", &VersionBump::Minor(1), &clock)
+                .unwrap(),
+            "2024.03.5"
+        );
+        assert_eq!(
+            scheme
+                .bump("2024.03.4", &VersionBump::Patch(3), &clock)
+                .unwrap(),
+            "2024.03.7"
+        );
+    }
+
+    #[test]
+    fn test_calver_roll_errors() {
+        let clock = FixedClock(Date::new(2024, 3, 15));
+        let scheme = calver("YYYY.0M.0D");
+
+        let result = scheme.bump("2024.03.15", &VersionBump::Patch(1), &clock);
+        assert!(result.unwrap_err().to_string().contains("no MICRO counter"));
+
+        let result = scheme.bump("2024.04.01", &VersionBump::Patch(1), &clock);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("dated after today (2024-03-15)"));
+
+        let result = scheme.bump("2024.03.01", &VersionBump::Patch(-1), &clock);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("only move forward"));
+
+        let result = scheme.bump("2024.03.01", &VersionBump::Release, &clock);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_calver_short_year_and_iso_week() {
+        let scheme = calver("YY.0W.N");
+        // 2021-01-03 is a Sunday in ISO week 53 of 2020
+        let clock = FixedClock(Date::new(2021, 1, 3));
+        assert_eq!(
+            scheme
+                .bump("20.52.3", &VersionBump::Patch(1), &clock)
+                .unwrap(),
+            "20.53.0"
+        );
+        // 2024-12-30 is a Monday in ISO week 1 of 2025
+        let clock = FixedClock(Date::new(2024, 12, 30));
+        assert_eq!(
+            scheme
+                .bump("24.52.1", &VersionBump::Patch(1), &clock)
+                .unwrap(),
+            "25.01.0"
+        );
+    }
+
+    #[test]
+    fn test_calver_validate() {
+        let scheme = calver("YYYY.0M.MICRO");
+        assert!(scheme.validate("2024.03.0").is_ok());
+        assert!(scheme.validate("2024.3.0").is_err());
+        assert!(scheme.validate("2024.13.0").is_err());
+        assert!(scheme.validate("2024.03").is_err());
+        assert!(scheme.validate("1.2.3-beta").is_err());
+
+        let scheme = calver("YYYY-MM-DD");
+        assert!(scheme.validate("2024-3-15").is_ok());
+        assert!(scheme.validate("2024-03-15").is_err());
+    }
+
+    #[test]
+    fn test_semver_scheme_delegates_to_bump() {
+        let clock = FixedClock(Date::new(2024, 3, 15));
+        assert_eq!(
+            VersionScheme::Semver
+                .bump("1.2.3", &VersionBump::Minor(1), &clock)
+                .unwrap(),
+            "1.3.0"
+        );
+        assert!(VersionScheme::Semver.validate("2024.03.0").is_err());
+    }
+
+    #[test]
+    fn test_date_conversions() {
+        assert_eq!(Date::from_days(0), Date::new(1970, 1, 1));
+        assert_eq!(Date::from_days(19_797), Date::new(2024, 3, 15));
+        assert_eq!(Date::new(2024, 3, 15).days(), 19_797);
+        assert_eq!(Date::new(2000, 2, 29).days(), 11_016);
+        assert_eq!(Date::from_days(11_016), Date::new(2000, 2, 29));
+        // 2024-03-15 was a Friday in ISO week 11
+        assert_eq!(Date::new(2024, 3, 15).weekday(), 5);
+        assert_eq!(Date::new(2024, 3, 15).iso_week(), (2024, 11));
+        assert_eq!(Date::new(2026, 1, 1).iso_week(), (2026, 1));
+    }
+}
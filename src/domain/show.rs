@@ -0,0 +1,302 @@
+use super::{Ecosystem, PackageSelection, VersionField, Workspace, WorkspaceMember};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// A workspace member as listed by `odo show`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MemberSummary {
+    pub name: String,
+    pub ecosystem: Ecosystem,
+    /// The member's version, resolved from the workspace root when inherited
+    pub version: Option<String>,
+    /// Where the version comes from
+    pub source: VersionSource,
+    pub manifest: PathBuf,
+    /// Whether this is the workspace root manifest
+    pub root: bool,
+}
+
+/// How a member's manifest declares its version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionSource {
+    /// Written in the manifest itself
+    Concrete,
+    /// `version.workspace = true`, taken from the workspace root
+    Inherited,
+    /// Not declared
+    Absent,
+    /// Computed at build time
+    Dynamic,
+}
+
+impl MemberSummary {
+    /// The version as shown in text output, with its source when not concrete
+    fn version_text(&self) -> String {
+        match (&self.version, self.source) {
+            (Some(version), VersionSource::Concrete) => version.clone(),
+            (Some(version), VersionSource::Inherited) => format!("{} (inherited)", version),
+            (None, VersionSource::Inherited) => "(inherited)".to_string(),
+            (_, VersionSource::Dynamic) => "(dynamic)".to_string(),
+            _ => "(no version)".to_string(),
+        }
+    }
+}
+
+impl Workspace {
+    /// Summaries of the selected members, in name order
+    pub fn summarize(&self, selection: &PackageSelection) -> anyhow::Result<Vec<MemberSummary>> {
+        let mut members = self.select(selection)?;
+        members.sort_by(|a, b| a.name().cmp(b.name()));
+
+        Ok(members
+            .into_iter()
+            .map(|member| MemberSummary {
+                name: member.name().to_string(),
+                ecosystem: member.ecosystem(),
+                version: self.resolved_version(member).map(str::to_string),
+                source: source(member),
+                manifest: member.manifest_path(),
+                root: self.root.as_ref() == Some(&member.manifest_path()),
+            })
+            .collect())
+    }
+}
+
+fn source(member: &WorkspaceMember) -> VersionSource {
+    match member.version() {
+        VersionField::Concrete(_) => VersionSource::Concrete,
+        VersionField::Inherited => VersionSource::Inherited,
+        VersionField::Absent => VersionSource::Absent,
+        VersionField::Dynamic => VersionSource::Dynamic,
+    }
+}
+
+/// Render one `name: version` line per member
+pub fn to_text(summaries: &[MemberSummary]) -> String {
+    summaries
+        .iter()
+        .map(|summary| format!("{}: {}\n", summary.name, summary.version_text()))
+        .collect()
+}
+
+/// Render an aligned table with a header row; manifest paths are shown relative to `base`
+pub fn to_table(summaries: &[MemberSummary], base: &Path) -> String {
+    let mut rows = vec![[
+        "NAME".to_string(),
+        "VERSION".to_string(),
+        "SOURCE".to_string(),
+        "ECOSYSTEM".to_string(),
+        "ROOT".to_string(),
+        "MANIFEST".to_string(),
+    ]];
+    for summary in summaries {
+        rows.push([
+            summary.name.clone(),
+            summary.version.clone().unwrap_or_else(|| "-".to_string()),
+            source_name(summary.source).to_string(),
+            summary.ecosystem.to_string(),
+            if summary.root { "yes" } else { "" }.to_string(),
+            relative(&summary.manifest, base),
+        ]);
+    }
+
+    let mut widths = [0; 6];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut output = String::new();
+    for row in &rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        output.push_str(cells.join("  ").trim_end());
+        output.push('\n');
+    }
+    output
+}
+
+/// Render members nested under the member whose directory contains theirs
+///
+/// Manifest paths are shown relative to `base`.
+pub fn to_tree(summaries: &[MemberSummary], base: &Path) -> String {
+    let dir = |index: usize| summaries[index].manifest.parent().unwrap_or(Path::new(""));
+    let parent = |index: usize| {
+        (0..summaries.len())
+            .filter(|&other| other != index && dir(index).starts_with(dir(other)))
+            .filter(|&other| dir(other) != dir(index))
+            .max_by_key(|&other| dir(other).components().count())
+    };
+
+    let mut output = String::new();
+    let mut stack: Vec<(usize, usize)> = (0..summaries.len())
+        .rev()
+        .filter(|&index| parent(index).is_none())
+        .map(|index| (index, 0))
+        .collect();
+    while let Some((index, depth)) = stack.pop() {
+        let summary = &summaries[index];
+        output.push_str(&format!(
+            "{}{} {} — {}\n",
+            "  ".repeat(depth),
+            summary.name,
+            summary.version_text(),
+            relative(&summary.manifest, base)
+        ));
+        stack.extend(
+            (0..summaries.len())
+                .rev()
+                .filter(|&child| parent(child) == Some(index))
+                .map(|child| (child, depth + 1)),
+        );
+    }
+    output
+}
+
+/// Render a pretty-printed JSON array; manifest paths are relative to `base`
+pub fn to_json(summaries: &[MemberSummary], base: &Path) -> serde_json::Result<String> {
+    let summaries: Vec<MemberSummary> = summaries
+        .iter()
+        .map(|summary| MemberSummary {
+            manifest: PathBuf::from(relative(&summary.manifest, base)),
+            ..summary.clone()
+        })
+        .collect();
+    serde_json::to_string_pretty(&summaries)
+}
+
+/// Render tab-separated values with a header row; manifest paths are relative to `base`
+pub fn to_tsv(summaries: &[MemberSummary], base: &Path) -> String {
+    let mut output = String::from("name\tversion\tsource\tecosystem\troot\tmanifest\n");
+    for summary in summaries {
+        output.push_str(&format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\n",
+            summary.name,
+            summary.version.as_deref().unwrap_or_default(),
+            source_name(summary.source),
+            summary.ecosystem,
+            summary.root,
+            relative(&summary.manifest, base)
+        ));
+    }
+    output
+}
+
+fn source_name(source: VersionSource) -> &'static str {
+    match source {
+        VersionSource::Concrete => "concrete",
+        VersionSource::Inherited => "inherited",
+        VersionSource::Absent => "absent",
+        VersionSource::Dynamic => "dynamic",
+    }
+}
+
+fn relative(path: &Path, base: &Path) -> String {
+    path.strip_prefix(base)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace() -> Workspace {
+        let member = |name: &str, path: &str, version: VersionField| WorkspaceMember::Cargo {
+            name: name.to_string(),
+            path: PathBuf::from(path),
+            version,
+            dependencies: Vec::new(),
+        };
+        Workspace {
+            members: vec![
+                member("ws", "/ws", VersionField::Concrete("2.0.0".to_string())),
+                member("core", "/ws/crates/core", VersionField::Inherited),
+                member("macros", "/ws/crates/core/macros", VersionField::Absent),
+                WorkspaceMember::Node {
+                    name: "web".to_string(),
+                    path: PathBuf::from("/ws/web"),
+                    version: VersionField::Concrete("0.3.0".to_string()),
+                    dependencies: Vec::new(),
+                },
+            ],
+            root: Some(PathBuf::from("/ws/Cargo.toml")),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_summarize() {
+        let summaries = workspace().summarize(&PackageSelection::Workspace).unwrap();
+        let names: Vec<&str> = summaries.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["core", "macros", "web", "ws"]);
+        assert_eq!(
+            summaries[0],
+            MemberSummary {
+                name: "core".to_string(),
+                ecosystem: Ecosystem::Cargo,
+                version: Some("2.0.0".to_string()),
+                source: VersionSource::Inherited,
+                manifest: PathBuf::from("/ws/crates/core/Cargo.toml"),
+                root: false,
+            }
+        );
+        assert!(summaries[3].root);
+
+        let json = serde_json::to_value(&summaries[1]).unwrap();
+        assert_eq!(json["version"], serde_json::Value::Null);
+        assert_eq!(json["source"], "absent");
+        assert_eq!(json["ecosystem"], "cargo");
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let summaries = workspace().summarize(&PackageSelection::Workspace).unwrap();
+        let base = Path::new("/ws");
+
+        assert_eq!(
+            to_text(&summaries),
+            "core: 2.0.0 (inherited)\nmacros: (no version)\nweb: 0.3.0\nws: 2.0.0\n"
+        );
+        assert_eq!(
+            to_table(&summaries, base),
+            "\
+NAME    VERSION  SOURCE     ECOSYSTEM  ROOT  MANIFEST
+core    2.0.0    inherited  cargo            crates/core/Cargo.toml
+macros  -        absent     cargo            crates/core/macros/Cargo.toml
+web     0.3.0    concrete   node             web/package.json
+ws      2.0.0    concrete   cargo      yes   Cargo.toml
+"
+        );
+        assert_eq!(
+            to_tree(&summaries, base),
+            "\
+ws 2.0.0 — Cargo.toml
+  core 2.0.0 (inherited) — crates/core/Cargo.toml
+    macros (no version) — crates/core/macros/Cargo.toml
+  web 0.3.0 — web/package.json
+"
+        );
+        assert_eq!(
+            to_tsv(&summaries, base),
+            "\
+name\tversion\tsource\tecosystem\troot\tmanifest
+core\t2.0.0\tinherited\tcargo\tfalse\tcrates/core/Cargo.toml
+macros\t\tabsent\tcargo\tfalse\tcrates/core/macros/Cargo.toml
+web\t0.3.0\tconcrete\tnode\tfalse\tweb/package.json
+ws\t2.0.0\tconcrete\tcargo\ttrue\tCargo.toml
+"
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&to_json(&summaries, base).unwrap()).unwrap();
+        assert_eq!(json[0]["manifest"], "crates/core/Cargo.toml");
+        assert_eq!(json[3]["manifest"], "Cargo.toml");
+    }
+}
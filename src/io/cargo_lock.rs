@@ -0,0 +1,146 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
+use toml_edit::{DocumentMut, Value};
+
+/// Name of the lockfile beside a Cargo workspace root
+pub const LOCKFILE: &str = "Cargo.lock";
+
+/// Render the contents of a Cargo.lock file with workspace package versions updated
+///
+/// `versions` maps package names to their new versions. Only `[[package]]`
+/// entries without a `source` are workspace packages; registry and git
+/// packages of the same name are left alone. References to an updated package
+/// written as `"name version"` in other entries' `dependencies` follow along.
+/// Returns `content` unchanged when no entry differs; `path` is only used for
+/// error messages.
+pub fn render(path: &Path, content: &str, versions: &HashMap<String, String>) -> Result<String> {
+    let mut doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    let packages = match doc
+        .get_mut("package")
+        .and_then(|p| p.as_array_of_tables_mut())
+    {
+        Some(packages) => packages,
+        None => return Ok(content.to_string()),
+    };
+
+    // "name old" → "name new", for dependency references
+    let mut renamed: HashMap<String, String> = HashMap::new();
+    for package in packages.iter_mut() {
+        if package.contains_key("source") {
+            continue;
+        }
+        let name = match package.get("name").and_then(|n| n.as_str()) {
+            Some(name) => name.to_string(),
+            None => continue,
+        };
+        let new_version = match versions.get(&name) {
+            Some(version) => version,
+            None => continue,
+        };
+
+        if let Some(version) = package.get_mut("version").and_then(|v| v.as_value_mut()) {
+            let old_version = version.as_str().unwrap_or_default().to_string();
+            if old_version != *new_version {
+                replace_string(version, new_version);
+                renamed.insert(
+                    format!("{} {}", name, old_version),
+                    format!("{} {}", name, new_version),
+                );
+            }
+        }
+    }
+
+    if renamed.is_empty() {
+        return Ok(content.to_string());
+    }
+
+    for package in packages.iter_mut() {
+        let dependencies = match package
+            .get_mut("dependencies")
+            .and_then(|d| d.as_array_mut())
+        {
+            Some(dependencies) => dependencies,
+            None => continue,
+        };
+        for dependency in dependencies.iter_mut() {
+            if let Some(new) = dependency.as_str().and_then(|d| renamed.get(d)) {
+                let new = new.clone();
+                replace_string(dependency, &new);
+            }
+        }
+    }
+
+    Ok(doc.to_string())
+}
+
+/// Replace a string value, keeping its surrounding whitespace and comments
+fn replace_string(value: &mut Value, new: &str) {
+    let decor = value.decor().clone();
+    *value = Value::from(new);
+    *value.decor_mut() = decor;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCK: &str = r#"# This file is automatically @generated by Cargo.
+# It is not intended for manual editing.
+version = 4
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = [
+ "core 1.2.0",
+ "serde",
+]
+
+[[package]]
+name = "core"
+version = "1.2.0"
+
+[[package]]
+name = "core"
+version = "0.9.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "abc"
+
+[[package]]
+name = "serde"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "def"
+"#;
+
+    #[test]
+    fn test_render_updates_workspace_packages() {
+        let versions = HashMap::from([
+            ("core".to_string(), "2.0.0".to_string()),
+            ("serde".to_string(), "9.9.9".to_string()),
+        ]);
+
+        let updated = render(Path::new("Cargo.lock"), LOCK, &versions).unwrap();
+        assert_eq!(
+            updated,
+            LOCK.replace("\"core 1.2.0\"", "\"core 2.0.0\"").replace(
+                "name = \"core\"\nversion = \"1.2.0\"",
+                "name = \"core\"\nversion = \"2.0.0\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_unchanged() {
+        let versions = HashMap::from([("core".to_string(), "1.2.0".to_string())]);
+        assert_eq!(
+            render(Path::new("Cargo.lock"), LOCK, &versions).unwrap(),
+            LOCK
+        );
+        assert!(render(Path::new("Cargo.lock"), "not = [toml", &versions).is_err());
+    }
+}
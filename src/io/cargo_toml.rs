@@ -1,8 +1,11 @@
 use anyhow::{Context, Result};
 use std::{fs, path::Path};
-use toml_edit::{DocumentMut, Formatted, Item, Value};
+use toml_edit::{DocumentMut, Formatted, InlineTable, Item, TableLike, Value};
 
-use crate::domain::VersionField;
+use crate::domain::{Dependency, VersionField};
+
+/// Dependency tables that may appear at the top level or under `[target.<cfg>]`
+const DEPENDENCY_TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
 
 /// Parse a Cargo.toml file and return (name, version, has_workspace_inheritance)
 pub fn parse(path: &Path) -> Result<(Option<String>, VersionField)> {
@@ -35,22 +38,91 @@ pub fn parse(path: &Path) -> Result<(Option<String>, VersionField)> {
     Ok((name, version))
 }
 
-/// Update the version in a Cargo.toml file, preserving formatting
+/// Parse the versioning scheme from `[package.metadata.odometer]`, if any
+pub fn parse_scheme(path: &Path) -> Result<Option<String>> {
+    let content = fs::read_to_string(path). //-
+        with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    Ok(doc
+        .get("package")
+        .and_then(|p| p.get("metadata"))
+        .and_then(|m| m.get("odometer"))
+        .and_then(|o| o.get("scheme"))
+        .and_then(|s| s.as_str())
+        .map(|s| s.to_string()))
+}
+
+/// Check whether a Cargo.toml file is a workspace root (has a `[workspace]` section)
+pub fn is_workspace_root(path: &Path) -> Result<bool> {
+    let content = fs::read_to_string(path). //-
+        with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    Ok(doc.get("workspace").is_some())
+}
+
+/// Check whether a Cargo.toml file declares a `[workspace.package]` version for members to inherit
+pub fn has_workspace_version(path: &Path) -> Result<bool> {
+    let content = fs::read_to_string(path). //-
+        with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    Ok(doc
+        .get("workspace")
+        .and_then(|w| w.get("package"))
+        .and_then(|p| p.get("version"))
+        .is_some())
+}
+
+/// Parse the `[workspace]` `members` and `exclude` lists of a Cargo.toml file
 ///
-/// This function will update the version in the Cargo.toml file at the given path.
-/// It will preserve the existing formatting of the version field, including comments.
+/// Returns `None` when the manifest does not declare workspace members.
+pub fn parse_workspace_members(path: &Path) -> Result<Option<(Vec<String>, Vec<String>)>> {
+    let content = fs::read_to_string(path). //-
+        with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    let workspace = match doc.get("workspace") {
+        Some(workspace) => workspace,
+        None => return Ok(None),
+    };
+
+    let string_list = |key: &str| -> Option<Vec<String>> {
+        workspace.get(key).and_then(|v| v.as_array()).map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+    };
+
+    Ok(string_list("members").map(|members| (members, string_list("exclude").unwrap_or_default())))
+}
+
+/// Render the contents of a Cargo.toml file with its version updated
 ///
-/// # Arguments
-/// * `path` - The path to the Cargo.toml file to update.
-pub fn update_version(path: &Path, new_version: &VersionField) -> Result<()> {
+/// The existing formatting of the version field, including comments, is
+/// preserved. `path` is only used for error messages.
+pub fn render_version(path: &Path, content: &str, new_version: &VersionField) -> Result<String> {
     let new_version = match new_version {
         VersionField::Concrete(version) => version,
-        _ => return Ok(()),
+        VersionField::Inherited => return render_inherited_version(path, content),
+        _ => return Ok(content.to_string()),
     };
 
-    let content = fs::read_to_string(path). //-
-        with_context(|| format!("Failed to read {}", path.display()))?;
-
     let mut doc = content
         .parse::<DocumentMut>()
         .with_context(|| format!("Failed to parse {}", path.display()))?;
@@ -81,10 +153,228 @@ pub fn update_version(path: &Path, new_version: &VersionField) -> Result<()> {
 
     package["version"] = Item::Value(new_value);
 
-    fs::write(path, doc.to_string())
-        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(doc.to_string())
+}
+
+/// Render the contents of a member Cargo.toml that inherits the workspace version
+///
+/// Sets `version.workspace = true` in `[package]`, unless it already inherits.
+fn render_inherited_version(path: &Path, content: &str) -> Result<String> {
+    let mut doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    if uses_workspace_inheritance(&doc, "package", "version") {
+        return Ok(content.to_string());
+    }
+    if doc.get("workspace").is_some() {
+        anyhow::bail!(
+            "{} is a workspace root and can't inherit its own version",
+            path.display()
+        );
+    }
+    let package = doc
+        .get_mut("package")
+        .ok_or_else(|| anyhow::anyhow!("No package section found in {}", path.display()))?;
+
+    // Keep a trailing comment on the old version line
+    let mut workspace = Value::from(true);
+    if let Some(suffix) = package
+        .get("version")
+        .and_then(|v| v.as_value())
+        .and_then(|v| v.decor().suffix())
+        .and_then(|s| s.as_str())
+    {
+        workspace.decor_mut().set_suffix(suffix.to_string());
+    }
+
+    let mut inherited = InlineTable::new();
+    inherited.insert("workspace", workspace);
+    inherited.set_dotted(true);
+    package["version"] = Item::Value(Value::InlineTable(inherited));
+
+    Ok(doc.to_string())
+}
+
+/// Parse the dependency tables of a Cargo.toml file
+///
+/// Covers `[dependencies]`, `[dev-dependencies]`, `[build-dependencies]`, their
+/// `[target.<cfg>.*]` variants and `[workspace.dependencies]`.
+pub fn parse_dependencies(path: &Path) -> Result<Vec<Dependency>> {
+    let content = fs::read_to_string(path). //-
+        with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    let mut dependencies = Vec::new();
+    for (table_name, table) in dependency_tables(&doc) {
+        for (key, item) in table.iter() {
+            let (name, requirement) = match item {
+                Item::Value(Value::String(s)) => (key.to_string(), Some(s.value().to_string())),
+                _ => match item.as_table_like() {
+                    Some(entry) => (
+                        entry
+                            .get("package")
+                            .and_then(|p| p.as_str())
+                            .unwrap_or(key)
+                            .to_string(),
+                        entry
+                            .get("version")
+                            .and_then(|v| v.as_str())
+                            .map(|v| v.to_string()),
+                    ),
+                    None => continue,
+                },
+            };
+
+            dependencies.push(Dependency {
+                name,
+                requirement,
+                table: table_name.clone(),
+            });
+        }
+    }
+
+    Ok(dependencies)
+}
+
+/// Render the contents of a Cargo.toml file with its dependency requirements updated
+///
+/// Each entry whose (table, package name) matches one of `dependencies` gets that
+/// dependency's requirement, preserving formatting. Returns `content` unchanged
+/// when no requirement differs.
+pub fn render_dependencies(
+    path: &Path,
+    content: &str,
+    dependencies: &[Dependency],
+) -> Result<String> {
+    let mut doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    let mut changed = false;
+    for (table_name, table) in dependency_tables_mut(&mut doc) {
+        for (key, item) in table.iter_mut() {
+            let key = key.get().to_string();
+            let name = item
+                .as_table_like()
+                .and_then(|entry| entry.get("package"))
+                .and_then(|p| p.as_str())
+                .unwrap_or(&key)
+                .to_string();
 
-    Ok(())
+            let requirement = match dependencies
+                .iter()
+                .find(|d| d.table == table_name && d.name == name)
+                .and_then(|d| d.requirement.as_deref())
+            {
+                Some(requirement) => requirement,
+                None => continue,
+            };
+
+            let value = match item {
+                Item::Value(value @ Value::String(_)) => Some(value),
+                _ => item
+                    .as_table_like_mut()
+                    .and_then(|entry| entry.get_mut("version"))
+                    .and_then(|v| v.as_value_mut()),
+            };
+
+            if let Some(value) = value {
+                if value.as_str() != Some(requirement) {
+                    replace_string(value, requirement);
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    if !changed {
+        return Ok(content.to_string());
+    }
+
+    Ok(doc.to_string())
+}
+
+/// Replace a string value, keeping its surrounding whitespace and comments
+fn replace_string(value: &mut Value, new: &str) {
+    let decor = value.decor().clone();
+    *value = Value::from(new);
+    *value.decor_mut() = decor;
+}
+
+/// Collect every dependency table in the document, keyed by its dotted name
+fn dependency_tables(doc: &DocumentMut) -> Vec<(String, &dyn TableLike)> {
+    let mut tables = Vec::new();
+
+    for name in DEPENDENCY_TABLES {
+        if let Some(table) = doc.get(name).and_then(|t| t.as_table_like()) {
+            tables.push((name.to_string(), table));
+        }
+    }
+
+    if let Some(targets) = doc.get("target").and_then(|t| t.as_table_like()) {
+        for (cfg, target) in targets.iter() {
+            for name in DEPENDENCY_TABLES {
+                if let Some(table) = target.get(name).and_then(|t| t.as_table_like()) {
+                    tables.push((format!("target.{}.{}", cfg, name), table));
+                }
+            }
+        }
+    }
+
+    if let Some(table) = doc
+        .get("workspace")
+        .and_then(|w| w.get("dependencies"))
+        .and_then(|t| t.as_table_like())
+    {
+        tables.push(("workspace.dependencies".to_string(), table));
+    }
+
+    tables
+}
+
+/// Mutable counterpart of [`dependency_tables`]
+fn dependency_tables_mut(doc: &mut DocumentMut) -> Vec<(String, &mut dyn TableLike)> {
+    let mut tables = Vec::new();
+    let root = doc.as_table_mut();
+
+    for (key, item) in root.iter_mut() {
+        let key = key.get().to_string();
+        if DEPENDENCY_TABLES.contains(&key.as_str()) {
+            if let Some(table) = item.as_table_like_mut() {
+                tables.push((key, table));
+            }
+        } else if key == "target" {
+            if let Some(targets) = item.as_table_like_mut() {
+                for (cfg, target) in targets.iter_mut() {
+                    let cfg = cfg.get().to_string();
+                    if let Some(target) = target.as_table_like_mut() {
+                        for (name, table) in target.iter_mut() {
+                            let name = name.get().to_string();
+                            if DEPENDENCY_TABLES.contains(&name.as_str()) {
+                                if let Some(table) = table.as_table_like_mut() {
+                                    tables.push((format!("target.{}.{}", cfg, name), table));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        } else if key == "workspace" {
+            if let Some(table) = item
+                .as_table_like_mut()
+                .and_then(|w| w.get_mut("dependencies"))
+                .and_then(|t| t.as_table_like_mut())
+            {
+                tables.push(("workspace.dependencies".to_string(), table));
+            }
+        }
+    }
+
+    tables
 }
 
 /// Get the package section from either workspace.package or package
@@ -141,7 +431,6 @@ fn uses_workspace_inheritance(doc: &DocumentMut, section: &str, field: &str) ->
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -191,7 +480,7 @@ mod tests {
     }
 
     #[test]
-    fn test_update_version_basic() {
+    fn test_render_version_basic() {
         let toml = r#"
             [package]
             name = "my-package"
@@ -199,13 +488,12 @@ mod tests {
         "#;
         let file = write_temp_toml(toml);
         let new_version = VersionField::Concrete("2.0.0".to_string());
-        update_version(file.path(), &new_version).unwrap();
-        let content = fs::read_to_string(file.path()).unwrap();
+        let content = render_version(file.path(), toml, &new_version).unwrap();
         assert!(content.contains("version = \"2.0.0\""));
     }
 
     #[test]
-    fn test_update_version_workspace_inheritance() {
+    fn test_render_version_workspace_inheritance() {
         let toml = r#"
             [package]
             name = "my-package"
@@ -213,11 +501,31 @@ mod tests {
         "#;
         let file = write_temp_toml(toml);
         let new_version = VersionField::Concrete("2.0.0".to_string());
-        update_version(file.path(), &new_version).unwrap();
-        let content = fs::read_to_string(file.path()).unwrap();
+        let content = render_version(file.path(), toml, &new_version).unwrap();
         assert!(content.contains("version = \"2.0.0\""));
     }
 
+    #[test]
+    fn test_render_inherited_version() {
+        let toml =
+            "[package]\nname = \"my-package\"\nversion = \"1.2.3\" # own\nedition = \"2021\"\n";
+        let updated =
+            render_version(Path::new("Cargo.toml"), toml, &VersionField::Inherited).unwrap();
+        assert_eq!(
+            updated,
+            "[package]\nname = \"my-package\"\nversion.workspace = true # own\nedition = \"2021\"\n"
+        );
+        let file = write_temp_toml(&updated);
+        assert_eq!(parse(file.path()).unwrap().1, VersionField::Inherited);
+        assert_eq!(
+            render_version(Path::new("Cargo.toml"), &updated, &VersionField::Inherited).unwrap(),
+            updated
+        );
+
+        let root = "[workspace.package]\nversion = \"1.0.0\"\n";
+        assert!(render_version(Path::new("Cargo.toml"), root, &VersionField::Inherited).is_err());
+    }
+
     // Workspace package tests
     #[test]
     fn test_parse_workspace_package() {
@@ -247,8 +555,7 @@ mod tests {
         "#;
         let file = write_temp_toml(toml);
         let new_version = VersionField::Concrete("2.0.0".to_string());
-        update_version(file.path(), &new_version).unwrap();
-        let content = fs::read_to_string(file.path()).unwrap();
+        let content = render_version(file.path(), toml, &new_version).unwrap();
         assert!(content.contains("version = \"2.0.0\""));
     }
 
@@ -291,14 +598,14 @@ mod tests {
     }
 
     #[test]
-    fn test_update_version_no_package_or_workspace() {
+    fn test_render_version_no_package_or_workspace() {
         let toml = r#"
             [dependencies]
             serde = "1.0"
         "#;
         let file = write_temp_toml(toml);
         let new_version = VersionField::Concrete("2.0.0".to_string());
-        let result = update_version(file.path(), &new_version);
+        let result = render_version(file.path(), toml, &new_version);
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -347,7 +654,7 @@ mod tests {
 
     // Formatting preservation test
     #[test]
-    fn test_update_version_preserves_formatting() {
+    fn test_render_version_preserves_formatting() {
         let toml = r#"
 # This is a comment
 [package]
@@ -357,8 +664,7 @@ description = "A test package"
         "#;
         let file = write_temp_toml(toml);
         let new_version = VersionField::Concrete("2.0.0".to_string());
-        update_version(file.path(), &new_version).unwrap();
-        let content = fs::read_to_string(file.path()).unwrap();
+        let content = render_version(file.path(), toml, &new_version).unwrap();
 
         // Check that version was updated
         assert!(content.contains("version = \"2.0.0\""));
@@ -466,7 +772,7 @@ description = "A test package"
     }
 
     #[test]
-    fn test_update_version_preserves_inline_table() {
+    fn test_render_version_preserves_inline_table() {
         let toml = r#"
             [package]
             name = "my-package"
@@ -474,8 +780,7 @@ description = "A test package"
         "#;
         let file = write_temp_toml(toml);
         let new_version = VersionField::Concrete("2.0.0".to_string());
-        update_version(file.path(), &new_version).unwrap();
-        let content = fs::read_to_string(file.path()).unwrap();
+        let content = render_version(file.path(), toml, &new_version).unwrap();
         assert!(content.contains("version = \"2.0.0\""));
     }
 
@@ -515,7 +820,7 @@ description = "A test package"
 
     #[test]
     fn test_update_mixed_workspace_with_root_package() {
-        // Test that update_version works for mixed workspace + root package
+        // Test that render_version works for mixed workspace + root package
         let toml = r#"
             [workspace]
             members = [".", "ecosystem/*"]
@@ -532,8 +837,7 @@ description = "A test package"
         println!("Parsed: name={:?}, version={:?}", name, version);
 
         // This should also fail because get_package_section_mut should return None
-        update_version(file.path(), &new_version).unwrap();
-        let content = fs::read_to_string(file.path()).unwrap();
+        let content = render_version(file.path(), toml, &new_version).unwrap();
         println!("Updated content: {}", content);
         assert!(content.contains("version = \"0.4.0\""));
     }
@@ -564,7 +868,7 @@ description = "A test package"
 
     #[test]
     fn test_virtual_workspace_update_precedence() {
-        // Ensure update_version targets workspace.package when both sections exist
+        // Ensure render_version targets workspace.package when both sections exist
         let toml = r#"
             [workspace.package]
             name = "workspace-pkg"
@@ -580,8 +884,7 @@ description = "A test package"
         let file = write_temp_toml(toml);
         let new_version = VersionField::Concrete("3.0.0".to_string());
 
-        update_version(file.path(), &new_version).unwrap();
-        let content = fs::read_to_string(file.path()).unwrap();
+        let content = render_version(file.path(), toml, &new_version).unwrap();
 
         // Should update workspace.package, not package
         assert!(content.contains("[workspace.package]"));
@@ -611,9 +914,164 @@ description = "A test package"
 
         // Update should work
         let new_version = VersionField::Concrete("1.6.0".to_string());
-        update_version(file.path(), &new_version).unwrap();
-        let content = fs::read_to_string(file.path()).unwrap();
+        let content = render_version(file.path(), toml, &new_version).unwrap();
         assert!(content.contains("version = \"1.6.0\""));
         assert!(content.contains("simple-pkg"));
     }
+
+    #[test]
+    fn test_is_workspace_root() {
+        let file = write_temp_toml("[workspace]\nmembers = [\"crates/*\"]\n");
+        assert!(is_workspace_root(file.path()).unwrap());
+
+        let file = write_temp_toml("[workspace.package]\nversion = \"1.0.0\"\n");
+        assert!(is_workspace_root(file.path()).unwrap());
+
+        let file = write_temp_toml("[package]\nname = \"member\"\nversion = \"1.0.0\"\n");
+        assert!(!is_workspace_root(file.path()).unwrap());
+    }
+
+    #[test]
+    fn test_has_workspace_version() {
+        let file = write_temp_toml("[workspace.package]\nversion = \"1.0.0\"\n");
+        assert!(has_workspace_version(file.path()).unwrap());
+
+        let file = write_temp_toml(
+            "[workspace]\nmembers = [\"a\"]\n\n[package]\nname = \"root\"\nversion = \"1.0.0\"\n",
+        );
+        assert!(!has_workspace_version(file.path()).unwrap());
+    }
+
+    #[test]
+    fn test_parse_workspace_members() {
+        let toml = r#"
+            [workspace]
+            members = ["crates/*", "tools/cli"]
+            exclude = ["crates/experimental"]
+        "#;
+        let file = write_temp_toml(toml);
+        let (members, exclude) = parse_workspace_members(file.path()).unwrap().unwrap();
+        assert_eq!(members, vec!["crates/*", "tools/cli"]);
+        assert_eq!(exclude, vec!["crates/experimental"]);
+
+        let file = write_temp_toml("[workspace]\nresolver = \"2\"\n");
+        assert_eq!(parse_workspace_members(file.path()).unwrap(), None);
+
+        let file = write_temp_toml("[package]\nname = \"solo\"\n");
+        assert_eq!(parse_workspace_members(file.path()).unwrap(), None);
+    }
+
+    // Dependency tests
+    #[test]
+    fn test_parse_dependencies() {
+        let toml = r#"
+            [package]
+            name = "app"
+            version = "1.0.0"
+
+            [dependencies]
+            core = { path = "../core", version = "1.2" }
+            serde = "1.0"
+            utils = { path = "../utils" }
+            renamed = { package = "helpers", path = "../helpers", version = "^0.3.0" }
+            shared = { workspace = true }
+
+            [dev-dependencies.testkit]
+            path = "../testkit"
+            version = "=0.1.0"
+
+            [target.'cfg(unix)'.build-dependencies]
+            builder = "2"
+        "#;
+        let file = write_temp_toml(toml);
+        let dependencies = parse_dependencies(file.path()).unwrap();
+
+        let find = |name: &str| dependencies.iter().find(|d| d.name == name).unwrap();
+        assert_eq!(dependencies.len(), 7);
+        assert_eq!(find("core").requirement, Some("1.2".to_string()));
+        assert_eq!(find("core").table, "dependencies");
+        assert_eq!(find("serde").requirement, Some("1.0".to_string()));
+        assert_eq!(find("utils").requirement, None);
+        assert_eq!(find("helpers").requirement, Some("^0.3.0".to_string()));
+        assert_eq!(find("shared").requirement, None);
+        assert_eq!(find("testkit").table, "dev-dependencies");
+        assert_eq!(find("testkit").requirement, Some("=0.1.0".to_string()));
+        assert_eq!(find("builder").table, "target.cfg(unix).build-dependencies");
+    }
+
+    #[test]
+    fn test_parse_workspace_dependencies() {
+        let toml = r#"
+            [workspace]
+            members = ["core"]
+
+            [workspace.dependencies]
+            core = { path = "core", version = "0.4.0" }
+        "#;
+        let file = write_temp_toml(toml);
+        let dependencies = parse_dependencies(file.path()).unwrap();
+        assert_eq!(
+            dependencies,
+            vec![Dependency {
+                name: "core".to_string(),
+                requirement: Some("0.4.0".to_string()),
+                table: "workspace.dependencies".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_render_dependencies_preserves_formatting() {
+        let toml = r#"[package]
+name = "app"
+version = "1.0.0"
+
+[dependencies]
+core = { path = "../core", version = "1.2" } # keep me
+renamed = { package = "helpers", path = "../helpers", version = "^0.3.0" }
+plain = "0.1"
+serde = "1.0"
+
+[dev-dependencies.testkit]
+path = "../testkit"
+version = "=0.1.0"
+
+[target.'cfg(unix)'.dependencies]
+core = "1.2"
+
+[workspace.dependencies]
+core = { path = "core", version = "1.2" }
+"#;
+        let file = write_temp_toml(toml);
+        let dependency = |name: &str, requirement: &str, table: &str| Dependency {
+            name: name.to_string(),
+            requirement: Some(requirement.to_string()),
+            table: table.to_string(),
+        };
+        let dependencies = vec![
+            dependency("core", "2.0", "dependencies"),
+            dependency("helpers", "^0.4.0", "dependencies"),
+            dependency("plain", "0.2", "dependencies"),
+            dependency("serde", "1.0", "dependencies"),
+            dependency("testkit", "=0.2.0", "dev-dependencies"),
+            dependency("core", "2.0", "target.cfg(unix).dependencies"),
+            dependency("core", "2.0", "workspace.dependencies"),
+        ];
+
+        let content = render_dependencies(file.path(), toml, &dependencies).unwrap();
+        let expected = toml
+            .replace(
+                r#"core = { path = "../core", version = "1.2" } # keep me"#,
+                r#"core = { path = "../core", version = "2.0" } # keep me"#,
+            )
+            .replace(r#"version = "^0.3.0""#, r#"version = "^0.4.0""#)
+            .replace(r#"plain = "0.1""#, r#"plain = "0.2""#)
+            .replace(r#"version = "=0.1.0""#, r#"version = "=0.2.0""#)
+            .replace(r#"core = "1.2""#, r#"core = "2.0""#)
+            .replace(
+                r#"core = { path = "core", version = "1.2" }"#,
+                r#"core = { path = "core", version = "2.0" }"#,
+            );
+        assert_eq!(content, expected);
+    }
 }
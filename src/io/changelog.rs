@@ -0,0 +1,539 @@
+//! Release sections for CHANGELOG.md files in the Keep a Changelog format
+//!
+//! See <https://keepachangelog.com/en/1.1.0/>.
+
+use super::FileUpdate;
+use crate::domain::changeset::Changeset;
+use crate::domain::conventional::{self, Commit, Level};
+use crate::domain::scheme::VersionScheme;
+use anyhow::{Context, Result};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Name of the changelog file in each member's directory
+pub const CHANGELOG_FILE: &str = "CHANGELOG.md";
+
+/// Text of a newly created changelog, before the first release section
+const HEADER: &str = "# Changelog
+
+All notable changes to this project will be documented in this file.
+
+The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/)";
+
+/// End of the header's last sentence for packages versioned with semver
+const SEMVER_CLAUSE: &str =
+    ",\nand this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html)";
+
+/// A Keep a Changelog change type, in the order sections are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Category {
+    Added,
+    Changed,
+    Deprecated,
+    Removed,
+    Fixed,
+    Security,
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Category::Added => "Added",
+            Category::Changed => "Changed",
+            Category::Deprecated => "Deprecated",
+            Category::Removed => "Removed",
+            Category::Fixed => "Fixed",
+            Category::Security => "Security",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A single changelog line
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub category: Category,
+    pub text: String,
+}
+
+/// Derive changelog entries from commit messages, oldest first
+///
+/// Conventional Commits are sorted by type: `feat` → Added, `fix` → Fixed,
+/// `perf`/`refactor`/`revert` and breaking changes → Changed, `security` →
+/// Security, and `deprecate`/`remove` to their namesakes. Maintenance types
+/// such as `chore`, `docs` or `ci` are left out. Other messages are listed
+/// under Changed, except merge commits.
+pub fn entries_from_commits(commits: &[Commit]) -> Vec<Entry> {
+    commits
+        .iter()
+        .rev()
+        .filter_map(|commit| {
+            let parsed = match conventional::parse(&commit.message) {
+                Some(parsed) => parsed,
+                None => {
+                    let subject = commit.message.lines().next().unwrap_or_default().trim();
+                    if subject.is_empty() || subject.starts_with("Merge ") {
+                        return None;
+                    }
+                    return Some(Entry {
+                        category: Category::Changed,
+                        text: subject.to_string(),
+                    });
+                }
+            };
+
+            let category = match parsed.commit_type.to_ascii_lowercase().as_str() {
+                _ if parsed.breaking => Category::Changed,
+                "feat" => Category::Added,
+                "fix" => Category::Fixed,
+                "perf" | "refactor" | "revert" => Category::Changed,
+                "deprecate" => Category::Deprecated,
+                "remove" => Category::Removed,
+                "security" => Category::Security,
+                _ => return None,
+            };
+
+            let mut text = String::new();
+            if parsed.breaking {
+                text.push_str("**Breaking:** ");
+            }
+            if let Some(scope) = parsed.scope {
+                text.push_str(&format!("**{}:** ", scope));
+            }
+            text.push_str(parsed.description);
+
+            Some(Entry { category, text })
+        })
+        .collect()
+}
+
+/// Derive changelog entries for `package` from the changesets that release it
+///
+/// Major releases are listed under Changed as breaking, minor releases under
+/// Added and patch releases under Fixed. Only the first line of each summary
+/// is used.
+pub fn entries_from_changesets(package: &str, changesets: &[Changeset]) -> Vec<Entry> {
+    changesets
+        .iter()
+        .filter_map(|changeset| {
+            let (_, level) = changeset
+                .releases
+                .iter()
+                .find(|(name, _)| name == package)?;
+            let summary = changeset.summary.lines().next().unwrap_or_default().trim();
+
+            let (category, text) = match level {
+                Level::Major => (Category::Changed, format!("**Breaking:** {}", summary)),
+                Level::Minor => (Category::Added, summary.to_string()),
+                Level::Patch => (Category::Fixed, summary.to_string()),
+            };
+            Some(Entry { category, text })
+        })
+        .collect()
+}
+
+/// Render the section for a release
+///
+/// A release without entries is noted as a version bump only.
+pub fn render_release(version: &str, date: &str, entries: &[Entry]) -> String {
+    render_section(version, date, "", entries)
+}
+
+/// A `### Category` subsection of a changelog section, with the lines below it
+struct Subsection {
+    heading: Option<String>,
+    lines: Vec<String>,
+}
+
+/// Render the section for a release, starting from the body of an `[Unreleased]`
+/// section and adding each entry to the subsection of its category
+fn render_section(version: &str, date: &str, unreleased: &str, entries: &[Entry]) -> String {
+    let mut subsections = subsections(unreleased);
+
+    let mut entries: Vec<&Entry> = entries.iter().collect();
+    entries.sort_by_key(|entry| entry.category);
+    for entry in entries {
+        let heading = entry.category.to_string();
+        let line = format!("- {}", entry.text);
+        match subsections.iter_mut().find(|subsection| {
+            subsection
+                .heading
+                .as_deref()
+                .is_some_and(|h| h.eq_ignore_ascii_case(&heading))
+        }) {
+            Some(subsection) => subsection.lines.push(line),
+            None => subsections.push(Subsection {
+                heading: Some(heading),
+                lines: vec![line],
+            }),
+        }
+    }
+
+    let mut section = format!("## [{}] - {}\n", version, date);
+    if subsections.is_empty() {
+        section.push_str("\n### Changed\n\n- Version bump only\n");
+    }
+    for subsection in subsections {
+        if let Some(heading) = subsection.heading {
+            section.push_str(&format!("\n### {}\n", heading));
+        }
+        section.push('\n');
+        for line in subsection.lines {
+            section.push_str(&line);
+            section.push('\n');
+        }
+    }
+    section
+}
+
+/// Split a section body into its `###` subsections, dropping empty ones
+///
+/// Lines before the first heading form a subsection without a heading.
+fn subsections(body: &str) -> Vec<Subsection> {
+    let mut subsections: Vec<Subsection> = Vec::new();
+    for line in body.lines() {
+        let line = line.trim_end();
+        match line.strip_prefix("### ") {
+            Some(heading) => subsections.push(Subsection {
+                heading: Some(heading.trim().to_string()),
+                lines: Vec::new(),
+            }),
+            None => match subsections.last_mut() {
+                Some(subsection) => subsection.lines.push(line.to_string()),
+                None => subsections.push(Subsection {
+                    heading: None,
+                    lines: vec![line.to_string()],
+                }),
+            },
+        }
+    }
+
+    for subsection in &mut subsections {
+        while subsection.lines.last().is_some_and(|line| line.is_empty()) {
+            subsection.lines.pop();
+        }
+        let leading = subsection
+            .lines
+            .iter()
+            .take_while(|line| line.is_empty())
+            .count();
+        subsection.lines.drain(..leading);
+    }
+    subsections.retain(|subsection| !subsection.lines.is_empty());
+    subsections
+}
+
+/// Insert a release section into a changelog, or start a new one
+///
+/// As Keep a Changelog describes, the entries of an `[Unreleased]` section
+/// move into the new release, leaving the empty `[Unreleased]` heading above
+/// it. Otherwise the section goes above the most recent release, or at the
+/// end when there are no releases yet. A new changelog only claims Semantic
+/// Versioning for semver packages.
+pub fn insert_release(
+    content: Option<&str>,
+    version: &str,
+    date: &str,
+    entries: &[Entry],
+    scheme: &VersionScheme,
+) -> String {
+    let content = match content {
+        Some(content) => content,
+        None => {
+            let clause = match scheme {
+                VersionScheme::Semver => SEMVER_CLAUSE,
+                VersionScheme::CalVer(_) => "",
+            };
+            return format!(
+                "{}{}.\n\n{}",
+                HEADER,
+                clause,
+                render_release(version, date, entries)
+            );
+        }
+    };
+
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+
+    // Where the body of an `[Unreleased]` section starts, and where the
+    // section after it (or the first release) starts
+    let mut unreleased = None;
+    let mut next = None;
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        let heading = line.trim_end();
+        if heading.starts_with("## ") {
+            if unreleased.is_none() && heading.to_ascii_lowercase().contains("unreleased") {
+                unreleased = Some(offset + line.len());
+            } else {
+                next = Some(offset);
+                break;
+            }
+        }
+        offset += line.len();
+    }
+
+    if let Some(start) = unreleased {
+        let end = next.unwrap_or(content.len());
+        let section =
+            render_section(version, date, &content[start..end], entries).replace('\n', newline);
+        let mut updated = content[..start].to_string();
+        if !updated.ends_with('\n') {
+            updated.push_str(newline);
+        }
+        updated.push_str(newline);
+        updated.push_str(&section);
+        if end < content.len() {
+            updated.push_str(newline);
+            updated.push_str(&content[end..]);
+        }
+        return updated;
+    }
+
+    let section = render_release(version, date, entries).replace('\n', newline);
+    if let Some(offset) = next {
+        return format!(
+            "{}{}{}{}",
+            &content[..offset],
+            section,
+            newline,
+            &content[offset..]
+        );
+    }
+
+    let mut updated = content.trim_end().to_string();
+    updated.push_str(newline);
+    updated.push_str(newline);
+    updated.push_str(&section);
+    updated
+}
+
+/// Compute the edit that adds a release section to the changelog in `dir`
+pub fn render_changelog(
+    dir: &Path,
+    version: &str,
+    date: &str,
+    entries: &[Entry],
+    scheme: &VersionScheme,
+) -> Result<FileUpdate> {
+    let path = dir.join(CHANGELOG_FILE);
+    let original = if path.is_file() {
+        Some(
+            fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?,
+        )
+    } else {
+        None
+    };
+
+    let updated = insert_release(original.as_deref(), version, date, entries, scheme);
+
+    Ok(FileUpdate {
+        path,
+        created: original.is_none(),
+        deleted: false,
+        original: original.unwrap_or_default(),
+        updated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn commit(message: &str) -> Commit {
+        Commit {
+            hash: "abc".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_entries_from_commits() {
+        // Newest first, as git log lists them
+        let commits = vec![
+            commit("Merge branch 'main'"),
+            commit("chore: release"),
+            commit("feat(cli)!: rename --all"),
+            commit("Tweak the parser"),
+            commit("fix: crash on empty input"),
+            commit("feat: add --tag"),
+        ];
+
+        let entries = entries_from_commits(&commits);
+        let rendered: Vec<(Category, &str)> = entries
+            .iter()
+            .map(|entry| (entry.category, entry.text.as_str()))
+            .collect();
+        assert_eq!(
+            rendered,
+            vec![
+                (Category::Added, "add --tag"),
+                (Category::Fixed, "crash on empty input"),
+                (Category::Changed, "Tweak the parser"),
+                (Category::Changed, "**Breaking:** **cli:** rename --all"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_entries_from_changesets() {
+        let changesets = vec![
+            Changeset::parse(
+                "a",
+                "---\ncore: minor\ncli: patch\n---\n\nAdd arrays\n\nDetails\n",
+            )
+            .unwrap(),
+            Changeset::parse("b", "---\ncore: major\n---\n\nDrop v1 API\n").unwrap(),
+        ];
+
+        assert_eq!(
+            entries_from_changesets("core", &changesets),
+            vec![
+                Entry {
+                    category: Category::Added,
+                    text: "Add arrays".to_string(),
+                },
+                Entry {
+                    category: Category::Changed,
+                    text: "**Breaking:** Drop v1 API".to_string(),
+                },
+            ]
+        );
+        assert_eq!(entries_from_changesets("cli", &changesets).len(), 1);
+        assert!(entries_from_changesets("web", &changesets).is_empty());
+    }
+
+    #[test]
+    fn test_render_release() {
+        let entries = vec![
+            Entry {
+                category: Category::Fixed,
+                text: "crash".to_string(),
+            },
+            Entry {
+                category: Category::Added,
+                text: "flag".to_string(),
+            },
+        ];
+        assert_eq!(
+            render_release("1.1.0", "2024-03-15", &entries),
+            "## [1.1.0] - 2024-03-15\n\n### Added\n\n- flag\n\n### Fixed\n\n- crash\n"
+        );
+        assert_eq!(
+            render_release("1.1.1", "2024-03-16", &[]),
+            "## [1.1.1] - 2024-03-16\n\n### Changed\n\n- Version bump only\n"
+        );
+    }
+
+    #[test]
+    fn test_insert_release() {
+        let entries = vec![Entry {
+            category: Category::Added,
+            text: "flag".to_string(),
+        }];
+        let insert = |content| {
+            insert_release(
+                content,
+                "1.1.0",
+                "2024-03-15",
+                &entries,
+                &VersionScheme::Semver,
+            )
+        };
+
+        let existing = "# Changelog\n\n## [1.0.0] - 2024-01-01\n\n- first\n";
+        assert_eq!(
+            insert(Some(existing)),
+            "# Changelog\n\n## [1.1.0] - 2024-03-15\n\n### Added\n\n- flag\n\n## [1.0.0] - 2024-01-01\n\n- first\n"
+        );
+
+        // Without earlier releases the section is appended, keeping CRLF line endings
+        assert_eq!(
+            insert(Some("# Changelog\r\n")),
+            "# Changelog\r\n\r\n## [1.1.0] - 2024-03-15\r\n\r\n### Added\r\n\r\n- flag\r\n"
+        );
+
+        let created = insert(None);
+        assert!(created.starts_with("# Changelog\n\nAll notable changes"));
+        assert!(created.ends_with("Semantic Versioning](https://semver.org/spec/v2.0.0.html).\n\n## [1.1.0] - 2024-03-15\n\n### Added\n\n- flag\n"));
+
+        // Calver packages don't claim to follow Semantic Versioning
+        let calver = "calver:YYYY.MM.MICRO".parse::<VersionScheme>().unwrap();
+        let created = insert_release(None, "2024.03.0", "2024-03-15", &[], &calver);
+        assert!(created.contains(
+            "[Keep a Changelog](https://keepachangelog.com/en/1.1.0/).\n\n## [2024.03.0]"
+        ));
+        assert!(!created.contains("Semantic Versioning"));
+    }
+
+    #[test]
+    fn test_insert_release_moves_unreleased_entries() {
+        let entries = vec![
+            Entry {
+                category: Category::Added,
+                text: "flag".to_string(),
+            },
+            Entry {
+                category: Category::Fixed,
+                text: "crash".to_string(),
+            },
+        ];
+        let insert = |content| {
+            insert_release(
+                Some(content),
+                "1.1.0",
+                "2024-03-15",
+                &entries,
+                &VersionScheme::Semver,
+            )
+        };
+
+        let existing = "# Changelog\n\n## [Unreleased]\n\n### Added\n\n- wip\n\n### Removed\n\n- old API\n\n### Security\n\n## [1.0.0] - 2024-01-01\n\n- first\n";
+        assert_eq!(
+            insert(existing),
+            "# Changelog\n\n## [Unreleased]\n\n## [1.1.0] - 2024-03-15\n\n### Added\n\n- wip\n- flag\n\n### Removed\n\n- old API\n\n### Fixed\n\n- crash\n\n## [1.0.0] - 2024-01-01\n\n- first\n"
+        );
+
+        // Loose entries and an unreleased section at the end, with CRLF line endings
+        assert_eq!(
+            insert("## [Unreleased]\r\n\r\n- wip\r\n"),
+            "## [Unreleased]\r\n\r\n## [1.1.0] - 2024-03-15\r\n\r\n- wip\r\n\r\n### Added\r\n\r\n- flag\r\n\r\n### Fixed\r\n\r\n- crash\r\n"
+        );
+
+        // Moved entries replace the version bump placeholder
+        assert_eq!(
+            insert_release(
+                Some("## [Unreleased]\n\n### Changed\n\n- tweak\n"),
+                "1.0.1",
+                "2024-03-16",
+                &[],
+                &VersionScheme::Semver
+            ),
+            "## [Unreleased]\n\n## [1.0.1] - 2024-03-16\n\n### Changed\n\n- tweak\n"
+        );
+    }
+
+    #[test]
+    fn test_render_changelog_creates_file() {
+        let dir = tempdir().unwrap();
+
+        let semver = VersionScheme::Semver;
+        let update = render_changelog(dir.path(), "0.1.0", "2024-03-15", &[], &semver).unwrap();
+        assert!(update.created);
+        assert_eq!(update.original, "");
+        assert_eq!(update.path, dir.path().join(CHANGELOG_FILE));
+
+        fs::write(&update.path, &update.updated).unwrap();
+        let update = render_changelog(dir.path(), "0.2.0", "2024-03-16", &[], &semver).unwrap();
+        assert!(!update.created);
+        let first = update.updated.find("## [0.2.0]").unwrap();
+        assert!(first < update.updated.find("## [0.1.0]").unwrap());
+    }
+}
@@ -0,0 +1,149 @@
+//! Changeset files in the `.changeset` directory at the workspace root
+
+use super::FileUpdate;
+use crate::domain::changeset::{self, Changeset};
+use crate::domain::conventional::Level;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory holding pending changesets, relative to the workspace root
+pub const CHANGESET_DIR: &str = ".changeset";
+
+/// A changeset read from disk
+#[derive(Debug, Clone)]
+pub struct ChangesetFile {
+    pub path: PathBuf,
+    pub content: String,
+    pub changeset: Changeset,
+}
+
+impl ChangesetFile {
+    /// The edit that removes this file once its releases have been applied
+    pub fn deletion(&self) -> FileUpdate {
+        FileUpdate {
+            path: self.path.clone(),
+            original: self.content.clone(),
+            updated: String::new(),
+            created: false,
+            deleted: true,
+        }
+    }
+}
+
+/// Read every pending changeset under `root`, ordered by file name
+///
+/// Markdown files other than README.md are changesets. A missing directory
+/// means there are none.
+pub fn read_all(root: &Path) -> Result<Vec<ChangesetFile>> {
+    let dir = root.join(CHANGESET_DIR);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let path = entry
+            .with_context(|| format!("Failed to read {}", dir.display()))?
+            .path();
+        let is_markdown = path.extension().is_some_and(|ext| ext == "md");
+        let is_readme = path
+            .file_name()
+            .is_some_and(|name| name.eq_ignore_ascii_case("README.md"));
+        if path.is_file() && is_markdown && !is_readme {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let id = path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned();
+            let changeset = Changeset::parse(&id, &content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            Ok(ChangesetFile {
+                path,
+                content,
+                changeset,
+            })
+        })
+        .collect()
+}
+
+/// Write a new changeset under `root`, named after its summary
+///
+/// A numeric suffix is added when the name is taken. Returns the path written.
+pub fn write_new(root: &Path, releases: Vec<(String, Level)>, summary: &str) -> Result<PathBuf> {
+    let dir = root.join(CHANGESET_DIR);
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    let stem = changeset::slug(summary);
+    let mut path = dir.join(format!("{}.md", stem));
+    let mut suffix = 2;
+    while path.exists() {
+        path = dir.join(format!("{}-{}.md", stem, suffix));
+        suffix += 1;
+    }
+
+    let changeset = Changeset {
+        id: path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned(),
+        releases,
+        summary: summary.to_string(),
+    };
+    fs::write(&path, changeset.render())
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_write_and_read_changesets() {
+        let dir = tempdir().unwrap();
+        assert!(read_all(dir.path()).unwrap().is_empty());
+
+        let releases = vec![("core".to_string(), Level::Minor)];
+        let first = write_new(dir.path(), releases.clone(), "Add arrays").unwrap();
+        let second = write_new(dir.path(), releases, "Add arrays").unwrap();
+        assert_eq!(first, dir.path().join(".changeset/add-arrays.md"));
+        assert_eq!(second, dir.path().join(".changeset/add-arrays-2.md"));
+        fs::write(dir.path().join(".changeset/README.md"), "# Changesets\n").unwrap();
+
+        let files = read_all(dir.path()).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].changeset.id, "add-arrays-2");
+        assert_eq!(
+            files[1].changeset.releases,
+            vec![("core".to_string(), Level::Minor)]
+        );
+        assert_eq!(files[1].changeset.summary, "Add arrays");
+
+        let deletion = files[1].deletion();
+        assert!(deletion.deleted);
+        assert_eq!(deletion.original, fs::read_to_string(&first).unwrap());
+    }
+
+    #[test]
+    fn test_read_all_reports_invalid_files() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join(CHANGESET_DIR)).unwrap();
+        fs::write(dir.path().join(".changeset/bad.md"), "no front matter\n").unwrap();
+
+        let err = read_all(dir.path()).unwrap_err();
+        assert!(format!("{:#}", err).contains("bad.md"));
+    }
+}
@@ -0,0 +1,314 @@
+use crate::domain::conventional::{Level, ZeroMajor};
+use crate::domain::lint::Severity;
+use crate::domain::GroupKind;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::{fs, path::Path};
+use toml_edit::{DocumentMut, Item};
+
+/// Name of the standalone configuration file at the workspace root
+pub const CONFIG_FILE: &str = "odometer.toml";
+
+/// Project configuration: defaults for CLI flags and per-package policy
+///
+/// Read from the first of these found in the workspace root directory:
+/// `odometer.toml`, `[workspace.metadata.odometer]` in Cargo.toml, or the
+/// `"odometer-config"` key of package.json (its `"odometer"` key holds the root
+/// package's own settings, as for any package). Keys are kebab-case in every format.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    pub no_ignore_git: bool,
+    #[serde(default)]
+    pub no_ignore: bool,
+    #[serde(default)]
+    pub no_ignore_global: bool,
+    #[serde(default)]
+    pub hidden: bool,
+    #[serde(default)]
+    pub no_ignore_all: bool,
+    /// Glob patterns (relative to the workspace root) of package directories to skip
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Packages targeted when no `--package`/`--workspace` flag is given
+    pub default_selection: Option<DefaultSelection>,
+    /// Output format used when `--format` is not given
+    pub format: Option<String>,
+    /// Versioning scheme for packages that don't declare their own
+    pub scheme: Option<String>,
+    /// Template for tags created by `--tag`, e.g. "{name}-v{version}" or "v{version}"
+    pub tag_template: Option<String>,
+    /// How `roll auto` treats breaking changes and features while a package is at 0.x
+    #[serde(default)]
+    pub zero_major: ZeroMajor,
+    /// Always write changelog sections, as if `--changelog` were given
+    #[serde(default)]
+    pub changelog: bool,
+    /// Template for commits created by `--commit`
+    pub commit_message: Option<String>,
+    /// Always bump dependents of changed packages at this level, as if `--cascade` were given
+    pub cascade: Option<Level>,
+    /// Lint as if `--lockstep` were given: all members share one version
+    #[serde(default)]
+    pub lockstep: bool,
+    /// Severities of lint rules, keyed by rule id
+    #[serde(default)]
+    pub lint: BTreeMap<String, Severity>,
+    /// Per-package settings, keyed by package name
+    #[serde(default)]
+    pub packages: BTreeMap<String, PackageConfig>,
+    /// Named version groups, keyed by group name
+    #[serde(default)]
+    pub groups: BTreeMap<String, GroupConfig>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DefaultSelection {
+    /// Only the workspace root package
+    Root,
+    /// Every workspace member
+    Workspace,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct PackageConfig {
+    /// Versioning scheme, e.g. "semver" or "calver:YYYY.MM.MICRO"
+    pub scheme: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct GroupConfig {
+    /// "fixed" (one shared version) or "linked" (bumped members move to the group's highest)
+    pub kind: GroupKind,
+    /// Names of the packages in the group
+    pub members: Vec<String>,
+}
+
+/// Load the configuration for the workspace rooted at `dir`
+///
+/// Returns the default configuration when none of the sources exist.
+pub fn load(dir: &Path) -> Result<Config> {
+    let config_file = dir.join(CONFIG_FILE);
+    if config_file.is_file() {
+        let doc = read_toml(&config_file)?;
+        return from_value(&config_file, toml_to_json(doc.as_item()));
+    }
+
+    let cargo_manifest = dir.join("Cargo.toml");
+    if cargo_manifest.is_file() {
+        let doc = read_toml(&cargo_manifest)?;
+        let section = doc
+            .get("workspace")
+            .and_then(|w| w.get("metadata"))
+            .and_then(|m| m.get("odometer"));
+        if let Some(section) = section {
+            return from_value(&cargo_manifest, toml_to_json(section));
+        }
+    }
+
+    let node_manifest = dir.join("package.json");
+    if node_manifest.is_file() {
+        let content = fs::read_to_string(&node_manifest). //-
+            with_context(|| format!("Failed to read {}", node_manifest.display()))?;
+        let mut value: Value = serde_json::from_str(content.trim_start_matches('\u{feff}'))
+            .with_context(|| format!("Failed to parse {}", node_manifest.display()))?;
+        if let Some(section) = value.get_mut("odometer-config").map(Value::take) {
+            return from_value(&node_manifest, section);
+        }
+    }
+
+    Ok(Config::default())
+}
+
+fn read_toml(path: &Path) -> Result<DocumentMut> {
+    let content = fs::read_to_string(path). //-
+        with_context(|| format!("Failed to read {}", path.display()))?;
+
+    content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+fn from_value(path: &Path, value: Value) -> Result<Config> {
+    serde_json::from_value(value)
+        .with_context(|| format!("Invalid odometer configuration in {}", path.display()))
+}
+
+/// Convert a TOML item into the equivalent JSON value
+fn toml_to_json(item: &Item) -> Value {
+    match item {
+        Item::None => Value::Null,
+        Item::Value(value) => toml_value_to_json(value),
+        Item::Table(table) => Value::Object(
+            table
+                .iter()
+                .map(|(key, item)| (key.to_string(), toml_to_json(item)))
+                .collect(),
+        ),
+        Item::ArrayOfTables(tables) => Value::Array(
+            tables
+                .iter()
+                .map(|table| toml_to_json(&Item::Table(table.clone())))
+                .collect(),
+        ),
+    }
+}
+
+fn toml_value_to_json(value: &toml_edit::Value) -> Value {
+    match value {
+        toml_edit::Value::String(s) => Value::from(s.value().as_str()),
+        toml_edit::Value::Integer(i) => Value::from(*i.value()),
+        toml_edit::Value::Float(f) => Value::from(*f.value()),
+        toml_edit::Value::Boolean(b) => Value::from(*b.value()),
+        toml_edit::Value::Datetime(d) => Value::from(d.value().to_string()),
+        toml_edit::Value::Array(array) => {
+            Value::Array(array.iter().map(toml_value_to_json).collect())
+        }
+        toml_edit::Value::InlineTable(table) => Value::Object(
+            table
+                .iter()
+                .map(|(key, value)| (key.to_string(), toml_value_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_load_odometer_toml() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join(CONFIG_FILE),
+            r#"
+no-ignore-git = true
+exclude = ["vendor/*"]
+default-selection = "workspace"
+format = "json"
+scheme = "semver"
+tag-template = "v{version}"
+zero-major = "normal"
+changelog = true
+commit-message = "chore: release {packages}"
+cascade = "minor"
+lockstep = true
+
+[lint]
+missing-version = "error"
+
+[packages.api]
+scheme = "calver:YYYY.MM.MICRO"
+
+[groups.core]
+kind = "fixed"
+members = ["core", "macros"]
+"#,
+        )
+        .unwrap();
+
+        let config = load(dir.path()).unwrap();
+        assert!(config.no_ignore_git);
+        assert!(!config.hidden);
+        assert_eq!(config.exclude, vec!["vendor/*"]);
+        assert_eq!(config.default_selection, Some(DefaultSelection::Workspace));
+        assert_eq!(config.format.as_deref(), Some("json"));
+        assert_eq!(config.tag_template.as_deref(), Some("v{version}"));
+        assert_eq!(config.zero_major, ZeroMajor::Normal);
+        assert!(config.changelog);
+        assert_eq!(
+            config.commit_message.as_deref(),
+            Some("chore: release {packages}")
+        );
+        assert_eq!(config.cascade, Some(Level::Minor));
+        assert!(config.lockstep);
+        assert_eq!(config.lint["missing-version"], Severity::Error);
+        assert_eq!(
+            config.packages["api"].scheme.as_deref(),
+            Some("calver:YYYY.MM.MICRO")
+        );
+        assert_eq!(config.groups["core"].kind, GroupKind::Fixed);
+        assert_eq!(config.groups["core"].members, vec!["core", "macros"]);
+    }
+
+    #[test]
+    fn test_load_cargo_workspace_metadata() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            r#"
+[workspace]
+members = ["crates/*"]
+
+[workspace.metadata.odometer]
+hidden = true
+packages = { api = { scheme = "calver" } }
+"#,
+        )
+        .unwrap();
+
+        let config = load(dir.path()).unwrap();
+        assert!(config.hidden);
+        assert_eq!(config.packages["api"].scheme.as_deref(), Some("calver"));
+    }
+
+    #[test]
+    fn test_load_package_json_key() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{
+  "name": "root",
+  "odometer": { "scheme": "calver" },
+  "odometer-config": { "default-selection": "root", "exclude": ["examples/**"], "scheme": "semver" }
+}"#,
+        )
+        .unwrap();
+
+        // The root package's own scheme doesn't become the workspace default
+        let config = load(dir.path()).unwrap();
+        assert_eq!(config.default_selection, Some(DefaultSelection::Root));
+        assert_eq!(config.exclude, vec!["examples/**"]);
+        assert_eq!(config.scheme.as_deref(), Some("semver"));
+    }
+
+    #[test]
+    fn test_odometer_toml_takes_precedence() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(CONFIG_FILE), "hidden = true\n").unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{ "odometer-config": { "no-ignore": true } }"#,
+        )
+        .unwrap();
+
+        let config = load(dir.path()).unwrap();
+        assert!(config.hidden);
+        assert!(!config.no_ignore);
+    }
+
+    #[test]
+    fn test_load_missing_config() {
+        let dir = tempdir().unwrap();
+        assert_eq!(load(dir.path()).unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_load_unknown_key() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(CONFIG_FILE), "no-ignroe-git = true\n").unwrap();
+
+        let err = load(dir.path()).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Invalid odometer configuration in"));
+    }
+}
@@ -0,0 +1,530 @@
+use crate::domain::conventional::Commit;
+use crate::domain::{OperationResult, VersionChange};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Tag template used when none is configured
+pub const DEFAULT_TAG_TEMPLATE: &str = "{name}-v{version}";
+
+/// Commit message template used when none is configured
+pub const DEFAULT_COMMIT_TEMPLATE: &str = "Release {packages}\n\n{changes}";
+
+/// An annotated tag to create for one or more version changes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tag {
+    pub name: String,
+    pub message: String,
+}
+
+/// Render the tags for `changes` from `template`
+///
+/// `{name}` and `{version}` in the template are replaced with the package name
+/// and its new version. Changes that render to the same tag (e.g. `v{version}`
+/// in a lockstep workspace) share a single tag.
+pub fn plan_tags(template: &str, changes: &[VersionChange]) -> Result<Vec<Tag>> {
+    if !template.contains("{version}") {
+        anyhow::bail!("Tag template '{}' must contain {{version}}", template);
+    }
+
+    let mut tags: Vec<(String, Vec<&str>, &str)> = Vec::new();
+    for change in changes {
+        let name = render_tag(template, &change.package, &change.new_version);
+
+        match tags.iter_mut().find(|(tag, _, _)| *tag == name) {
+            Some((_, packages, _)) => packages.push(&change.package),
+            None => tags.push((name, vec![&change.package], &change.new_version)),
+        }
+    }
+
+    Ok(tags
+        .into_iter()
+        .map(|(name, packages, version)| Tag {
+            name,
+            message: format!("{} {}", packages.join(", "), version),
+        })
+        .collect())
+}
+
+/// Render a tag name from `template` for `package` at `version`
+pub fn render_tag(template: &str, package: &str, version: &str) -> String {
+    template
+        .replace("{name}", package)
+        .replace("{version}", version)
+}
+
+/// Find the tag `package` was last released under
+///
+/// That is the tag for its current `version` when it exists, otherwise the
+/// most recent tag reachable from HEAD that matches the template for `package`.
+pub fn last_release_tag(
+    dir: &Path,
+    template: &str,
+    package: &str,
+    version: &str,
+) -> Result<Option<String>> {
+    let current = render_tag(template, package, version);
+    if tag_exists(dir, &current)? {
+        return Ok(Some(current));
+    }
+
+    let pattern = render_tag(template, package, "*");
+    match git(
+        dir,
+        &["describe", "--tags", "--abbrev=0", "--match", &pattern],
+    ) {
+        Ok(tag) => Ok(Some(tag.trim().to_string())),
+        // No matching tag
+        Err(_) => Ok(None),
+    }
+}
+
+/// List the commits since `since` (or all of history) that touch files under
+/// `path`, ignoring the directories in `exclude`, newest first
+pub fn commits_since(
+    dir: &Path,
+    since: Option<&str>,
+    path: &Path,
+    exclude: &[PathBuf],
+) -> Result<Vec<Commit>> {
+    let range = match since {
+        Some(tag) => format!("{}..HEAD", tag),
+        None => "HEAD".to_string(),
+    };
+    let mut pathspecs = vec![path.to_string_lossy().into_owned()];
+    pathspecs.extend(
+        exclude
+            .iter()
+            .map(|path| format!(":(exclude){}", path.display())),
+    );
+
+    let mut args = vec!["log", "--format=%H%x1f%B%x1e", &range, "--"];
+    args.extend(pathspecs.iter().map(String::as_str));
+    let log = git(dir, &args)
+        .with_context(|| format!("Failed to read the history of {}", path.display()))?;
+
+    Ok(log
+        .split('\x1e')
+        .filter_map(|entry| entry.trim_start().split_once('\x1f'))
+        .map(|(hash, message)| Commit {
+            hash: hash.to_string(),
+            message: message.trim_end().to_string(),
+        })
+        .collect())
+}
+
+/// List the files under `dir` changed on HEAD since it branched off `reference`, joined onto `dir`
+///
+/// The diff starts at the merge base of `reference` and HEAD (`reference...HEAD`),
+/// so commits that landed on `reference` after the branch point don't count.
+/// Both sides of a rename are included, so moving a file out of a package
+/// counts as a change to it.
+pub fn changed_files(dir: &Path, reference: &str) -> Result<Vec<PathBuf>> {
+    let diff = git(
+        dir,
+        &[
+            "diff",
+            "--name-only",
+            "--no-renames",
+            "--relative",
+            "-z",
+            &format!("{}...HEAD", reference),
+        ],
+    )
+    .with_context(|| format!("Failed to list changes since '{}'", reference))?;
+
+    Ok(diff
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(|path| dir.join(path))
+        .collect())
+}
+
+/// The path of `dir` relative to the root of its repository, as `sub/dir/` (empty at the root)
+pub fn repo_prefix(dir: &Path) -> Result<String> {
+    Ok(git(dir, &["rev-parse", "--show-prefix"])?
+        .trim_end()
+        .to_string())
+}
+
+/// Render a commit message for `result` from `template`
+///
+/// `{packages}` is replaced with the changed package names, `{changes}` with
+/// one `name: old → new` line per change and `{operation}` with the operation
+/// (e.g. "roll minor 1").
+pub fn commit_message(template: &str, result: &OperationResult) -> String {
+    let packages = result
+        .all_changes()
+        .map(|change| change.package.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let changes = result
+        .all_changes()
+        .map(|change| {
+            format!(
+                "{}: {} → {}",
+                change.package, change.old_version, change.new_version
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    template
+        .replace("{packages}", &packages)
+        .replace("{changes}", &changes)
+        .replace("{operation}", &result.operation)
+}
+
+/// List the files with uncommitted changes (including untracked files) in the
+/// repository at `dir`
+pub fn dirty_files(dir: &Path) -> Result<Vec<String>> {
+    let status = git(dir, &["status", "--porcelain"])?;
+    Ok(status
+        .lines()
+        .filter_map(|line| line.get(3..))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Fail if the repository at `dir` has uncommitted changes
+pub fn ensure_clean(dir: &Path) -> Result<()> {
+    let dirty = dirty_files(dir)?;
+    if !dirty.is_empty() {
+        anyhow::bail!(
+            "{} file(s) in the working directory have uncommitted changes; commit them or pass --allow-dirty:\n  {}",
+            dirty.len(),
+            dirty.join("\n  ")
+        );
+    }
+    Ok(())
+}
+
+/// Commit exactly `paths` in the repository at `dir`, returning the new commit's hash
+///
+/// Anything else that is staged stays staged and out of the commit. Deleted
+/// paths that git never tracked are skipped.
+pub fn commit_files(dir: &Path, paths: &[PathBuf], message: &str) -> Result<String> {
+    let mut kept = Vec::new();
+    for path in paths {
+        if path.exists() || is_tracked(dir, path)? {
+            kept.push(path);
+        }
+    }
+    let paths = kept
+        .iter()
+        .map(|path| path.to_string_lossy())
+        .collect::<Vec<_>>();
+    let paths = paths.iter().map(|path| path.as_ref());
+
+    let add = ["add", "--"].into_iter().chain(paths.clone());
+    git(dir, &add.collect::<Vec<_>>()).with_context(|| "Failed to stage manifests")?;
+
+    let commit = ["commit", "--quiet", "--message", message, "--only", "--"]
+        .into_iter()
+        .chain(paths);
+    git(dir, &commit.collect::<Vec<_>>()).with_context(|| "Failed to create commit")?;
+
+    let head = git(dir, &["rev-parse", "HEAD"])?;
+    Ok(head.trim().to_string())
+}
+
+/// Check whether git tracks `path` in the repository at `dir`
+fn is_tracked(dir: &Path, path: &Path) -> Result<bool> {
+    let path = path.to_string_lossy();
+    Ok(!git(dir, &["ls-files", "--", &path])?.trim().is_empty())
+}
+
+/// Fail if `dir` is not inside a git repository
+pub fn ensure_repo(dir: &Path) -> Result<()> {
+    git(dir, &["rev-parse", "--git-dir"])
+        .map(|_| ())
+        .with_context(|| format!("{} is not inside a git repository", dir.display()))
+}
+
+/// Fail if any of `tags` already exists in the repository at `dir`
+pub fn ensure_tags_available(dir: &Path, tags: &[Tag]) -> Result<()> {
+    let mut existing = Vec::new();
+    for tag in tags {
+        if tag_exists(dir, &tag.name)? {
+            existing.push(tag.name.as_str());
+        }
+    }
+
+    if !existing.is_empty() {
+        anyhow::bail!("Tag already exists: {}", existing.join(", "));
+    }
+    Ok(())
+}
+
+/// Check whether `tag` exists in the repository at `dir`
+pub fn tag_exists(dir: &Path, tag: &str) -> Result<bool> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet"])
+        .arg(format!("refs/tags/{}", tag))
+        .current_dir(dir)
+        .output()
+        .with_context(|| "Failed to run git")?;
+    Ok(output.status.success())
+}
+
+/// Create an annotated tag at HEAD of the repository at `dir`
+pub fn create_tag(dir: &Path, tag: &Tag) -> Result<()> {
+    git(
+        dir,
+        &["tag", "--annotate", &tag.name, "--message", &tag.message],
+    )
+    .with_context(|| format!("Failed to create tag {}", tag.name))
+    .map(|_| ())
+}
+
+/// Run git in `dir`, returning its standard output
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .with_context(|| "Failed to run git")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use tempfile::tempdir;
+
+    fn change(package: &str, new_version: &str) -> VersionChange {
+        VersionChange {
+            package: package.to_string(),
+            old_version: "0.1.0".to_string(),
+            new_version: new_version.to_string(),
+            path: PathBuf::from(package),
+        }
+    }
+
+    fn init_repo(dir: &Path) {
+        for args in [
+            &["init", "--quiet"][..],
+            &["config", "user.name", "Test"],
+            &["config", "user.email", "test@example.com"],
+            &["commit", "--quiet", "--allow-empty", "--message", "initial"],
+        ] {
+            git(dir, args).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_commit_message() {
+        let mut result = OperationResult::new("roll patch 1".to_string());
+        result.add_change(change("core", "0.1.1"));
+        result.add_change(change("cli", "0.1.1"));
+
+        assert_eq!(
+            commit_message(DEFAULT_COMMIT_TEMPLATE, &result),
+            "Release core, cli\n\ncore: 0.1.0 → 0.1.1\ncli: 0.1.0 → 0.1.1"
+        );
+        assert_eq!(
+            commit_message("chore: {operation}", &result),
+            "chore: roll patch 1"
+        );
+    }
+
+    #[test]
+    fn test_commit_files_only_commits_given_paths() {
+        let dir = tempdir().unwrap();
+        init_repo(dir.path());
+        for name in ["Cargo.toml", "notes.txt"] {
+            std::fs::write(dir.path().join(name), "old\n").unwrap();
+        }
+        git(dir.path(), &["add", "."]).unwrap();
+        git(dir.path(), &["commit", "--quiet", "--message", "files"]).unwrap();
+        assert!(dirty_files(dir.path()).unwrap().is_empty());
+
+        std::fs::write(dir.path().join("Cargo.toml"), "new\n").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "new\n").unwrap();
+        git(dir.path(), &["add", "notes.txt"]).unwrap();
+        assert_eq!(
+            dirty_files(dir.path()).unwrap(),
+            vec!["Cargo.toml", "notes.txt"]
+        );
+        assert!(ensure_clean(dir.path())
+            .unwrap_err()
+            .to_string()
+            .contains("--allow-dirty"));
+
+        let hash =
+            commit_files(dir.path(), &[dir.path().join("Cargo.toml")], "Release app").unwrap();
+        assert_eq!(hash.len(), 40);
+
+        // The unrelated staged change is left out of the commit
+        assert_eq!(dirty_files(dir.path()).unwrap(), vec!["notes.txt"]);
+        let committed = git(dir.path(), &["show", "--name-only", "--format=%s", "HEAD"]).unwrap();
+        assert_eq!(committed, "Release app\n\nCargo.toml\n");
+    }
+
+    #[test]
+    fn test_commit_files_with_deleted_paths() {
+        let dir = tempdir().unwrap();
+        init_repo(dir.path());
+        for name in ["Cargo.toml", "tracked.md"] {
+            std::fs::write(dir.path().join(name), "old\n").unwrap();
+        }
+        git(dir.path(), &["add", "."]).unwrap();
+        git(dir.path(), &["commit", "--quiet", "--message", "files"]).unwrap();
+
+        std::fs::write(dir.path().join("Cargo.toml"), "new\n").unwrap();
+        std::fs::remove_file(dir.path().join("tracked.md")).unwrap();
+        let paths = ["Cargo.toml", "tracked.md", "untracked.md"].map(|name| dir.path().join(name));
+        commit_files(dir.path(), &paths, "Release app").unwrap();
+
+        assert!(dirty_files(dir.path()).unwrap().is_empty());
+        let committed = git(dir.path(), &["show", "--name-status", "--format=", "HEAD"]).unwrap();
+        assert_eq!(committed, "M\tCargo.toml\nD\ttracked.md\n");
+    }
+
+    #[test]
+    fn test_history_since_last_release() {
+        let dir = tempdir().unwrap();
+        init_repo(dir.path());
+        let core = dir.path().join("core");
+        let nested = core.join("nested");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        let commit = |file: &Path, message: &str| {
+            std::fs::write(file, message).unwrap();
+            git(dir.path(), &["add", "."]).unwrap();
+            git(dir.path(), &["commit", "--quiet", "--message", message]).unwrap();
+        };
+
+        assert_eq!(
+            last_release_tag(dir.path(), DEFAULT_TAG_TEMPLATE, "core", "1.0.0").unwrap(),
+            None
+        );
+
+        commit(&core.join("lib.rs"), "feat: first");
+        git(dir.path(), &["tag", "core-v0.9.0"]).unwrap();
+        commit(&core.join("lib.rs"), "fix: in core");
+        commit(&nested.join("lib.rs"), "feat: in nested member");
+        commit(&dir.path().join("README"), "docs: elsewhere");
+
+        // The tag for the current version doesn't exist, so the latest matching one is used
+        let since = last_release_tag(dir.path(), DEFAULT_TAG_TEMPLATE, "core", "1.0.0").unwrap();
+        assert_eq!(since.as_deref(), Some("core-v0.9.0"));
+
+        let commits = commits_since(dir.path(), since.as_deref(), &core, &[nested]).unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].message, "fix: in core");
+        assert_eq!(commits[0].hash.len(), 40);
+
+        let commits = commits_since(dir.path(), None, &core, &[]).unwrap();
+        assert_eq!(commits.len(), 3);
+    }
+
+    #[test]
+    fn test_changed_files() {
+        let dir = tempdir().unwrap();
+        init_repo(dir.path());
+        let root = dir.path();
+        std::fs::create_dir(root.join("core")).unwrap();
+        std::fs::write(root.join("core/old.rs"), "x").unwrap();
+        git(root, &["add", "."]).unwrap();
+        git(root, &["commit", "--quiet", "--message", "add"]).unwrap();
+        git(root, &["tag", "v1.0.0"]).unwrap();
+
+        git(root, &["mv", "core/old.rs", "moved.rs"]).unwrap();
+        git(root, &["commit", "--quiet", "--message", "move"]).unwrap();
+
+        let mut files = changed_files(root, "v1.0.0").unwrap();
+        files.sort();
+        assert_eq!(files, vec![root.join("core/old.rs"), root.join("moved.rs")]);
+
+        // Only changes beneath the directory are listed
+        let core = root.join("core");
+        assert_eq!(
+            changed_files(&core, "v1.0.0").unwrap(),
+            vec![core.join("old.rs")]
+        );
+
+        assert!(changed_files(root, "no-such-ref").is_err());
+    }
+
+    #[test]
+    fn test_changed_files_since_branch_point() {
+        let dir = tempdir().unwrap();
+        init_repo(dir.path());
+        let root = dir.path();
+        let commit = |file: &str, message: &str| {
+            std::fs::write(root.join(file), message).unwrap();
+            git(root, &["add", "."]).unwrap();
+            git(root, &["commit", "--quiet", "--message", message]).unwrap();
+        };
+        git(root, &["branch", "-M", "main"]).unwrap();
+
+        git(root, &["checkout", "--quiet", "-b", "feature"]).unwrap();
+        commit("feature.rs", "on the branch");
+        git(root, &["checkout", "--quiet", "main"]).unwrap();
+        commit("main.rs", "on main after branching");
+        git(root, &["checkout", "--quiet", "feature"]).unwrap();
+
+        // Only the branch's own changes, not what landed on main since
+        assert_eq!(
+            changed_files(root, "main").unwrap(),
+            vec![root.join("feature.rs")]
+        );
+    }
+
+    #[test]
+    fn test_plan_tags() {
+        let changes = vec![change("core", "1.0.0"), change("cli", "1.0.0")];
+
+        let tags = plan_tags(DEFAULT_TAG_TEMPLATE, &changes).unwrap();
+        assert_eq!(
+            tags.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(),
+            vec!["core-v1.0.0", "cli-v1.0.0"]
+        );
+        assert_eq!(tags[0].message, "core 1.0.0");
+
+        // A lockstep template collapses to one tag
+        let tags = plan_tags("v{version}", &changes).unwrap();
+        assert_eq!(
+            tags,
+            vec![Tag {
+                name: "v1.0.0".to_string(),
+                message: "core, cli 1.0.0".to_string(),
+            }]
+        );
+
+        assert!(plan_tags("{name}", &changes).is_err());
+    }
+
+    #[test]
+    fn test_ensure_repo() {
+        let dir = tempdir().unwrap();
+        assert!(ensure_repo(dir.path()).is_err());
+        init_repo(dir.path());
+        ensure_repo(dir.path()).unwrap();
+    }
+
+    #[test]
+    fn test_create_tag_refuses_existing() {
+        let dir = tempdir().unwrap();
+        init_repo(dir.path());
+
+        let tags = plan_tags("v{version}", &[change("app", "1.0.0")]).unwrap();
+        ensure_tags_available(dir.path(), &tags).unwrap();
+        create_tag(dir.path(), &tags[0]).unwrap();
+        assert!(tag_exists(dir.path(), "v1.0.0").unwrap());
+
+        let err = ensure_tags_available(dir.path(), &tags).unwrap_err();
+        assert_eq!(err.to_string(), "Tag already exists: v1.0.0");
+        assert!(create_tag(dir.path(), &tags[0]).is_err());
+    }
+}
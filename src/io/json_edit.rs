@@ -0,0 +1,389 @@
+//! Formatting-preserving edits of JSON text
+//!
+//! Rather than round-tripping through `serde_json`, these functions locate the
+//! byte span of a value and splice new text into the original, so indentation,
+//! key order, escapes, line endings, a trailing newline and any BOM survive.
+//!
+//! When an object repeats a key, the last occurrence is the one edited, as it
+//! is the one `serde_json` reads.
+
+use anyhow::{Context, Result};
+use std::ops::Range;
+
+/// Replace the value at `path` (a list of object keys) with a JSON string
+///
+/// Returns `None` when `path` does not exist.
+pub fn set_string(content: &str, path: &[&str], value: &str) -> Result<Option<String>> {
+    let (object_path, key) = match path.split_last() {
+        Some((key, object_path)) => (object_path, *key),
+        None => anyhow::bail!("Cannot replace the root of a JSON document"),
+    };
+
+    let member = match find_object(content, object_path)? {
+        Some(object) => object.members.into_iter().rfind(|m| m.name == key),
+        None => None,
+    };
+
+    match member {
+        Some(member) => Ok(Some(splice(content, member.value, &quote(value)?))),
+        None => Ok(None),
+    }
+}
+
+/// Add a string member to the object at `object_path`
+///
+/// The member is inserted right after the `after` key when present, otherwise
+/// first, copying the whitespace and key/value separator of its neighbour.
+/// Returns `None` when `object_path` does not exist.
+pub fn insert_string(
+    content: &str,
+    object_path: &[&str],
+    key: &str,
+    value: &str,
+    after: Option<&str>,
+) -> Result<Option<String>> {
+    let object = match find_object(content, object_path)? {
+        Some(object) => object,
+        None => return Ok(None),
+    };
+
+    let key = quote(key)?;
+    let value = quote(value)?;
+
+    let anchor = after.and_then(|after| object.members.iter().rfind(|m| m.name == after));
+    let updated = match (anchor, object.members.first()) {
+        (Some(member), _) => {
+            let insert = format!(
+                ",{}{}{}{}",
+                &content[member.leading.clone()],
+                key,
+                &content[member.key.end..member.value.start],
+                value
+            );
+            splice(content, member.value.end..member.value.end, &insert)
+        }
+        (None, Some(first)) => {
+            let insert = format!(
+                "{}{}{},{}",
+                key,
+                &content[first.key.end..first.value.start],
+                value,
+                &content[first.leading.clone()]
+            );
+            splice(content, first.key.start..first.key.start, &insert)
+        }
+        (None, None) => {
+            let insert = format!("{}: {}", key, value);
+            splice(content, object.inner.start..object.inner.start, &insert)
+        }
+    };
+
+    Ok(Some(updated))
+}
+
+/// A member of a JSON object, as byte spans into the source text
+struct Member {
+    name: String,
+    /// Whitespace between the previous token and the key
+    leading: Range<usize>,
+    key: Range<usize>,
+    value: Range<usize>,
+}
+
+struct Object {
+    /// Span between the braces
+    inner: Range<usize>,
+    members: Vec<Member>,
+}
+
+/// Locate the object at `path`, returning `None` when a key is missing or not an object
+fn find_object(content: &str, path: &[&str]) -> Result<Option<Object>> {
+    let mut scanner = Scanner::new(content);
+    scanner.skip_bom();
+    scanner.skip_whitespace();
+
+    let mut object = match scanner.object()? {
+        Some(object) => object,
+        None => return Ok(None),
+    };
+
+    for key in path {
+        let member = match object.members.iter().rfind(|m| m.name == *key) {
+            Some(member) => member,
+            None => return Ok(None),
+        };
+        let mut scanner = Scanner::at(content, member.value.start);
+        object = match scanner.object()? {
+            Some(object) => object,
+            None => return Ok(None),
+        };
+    }
+
+    Ok(Some(object))
+}
+
+fn splice(content: &str, range: Range<usize>, text: &str) -> String {
+    let mut updated = String::with_capacity(content.len() + text.len());
+    updated.push_str(&content[..range.start]);
+    updated.push_str(text);
+    updated.push_str(&content[range.end..]);
+    updated
+}
+
+fn quote(value: &str) -> Result<String> {
+    serde_json::to_string(value).with_context(|| format!("Failed to encode '{}'", value))
+}
+
+struct Scanner<'a> {
+    content: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(content: &'a str) -> Self {
+        Self::at(content, 0)
+    }
+
+    fn at(content: &'a str, pos: usize) -> Self {
+        Self {
+            content,
+            bytes: content.as_bytes(),
+            pos,
+        }
+    }
+
+    fn skip_bom(&mut self) {
+        if self.content[self.pos..].starts_with('\u{feff}') {
+            self.pos += '\u{feff}'.len_utf8();
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<()> {
+        match self.peek() {
+            Some(b) if b == byte => {
+                self.pos += 1;
+                Ok(())
+            }
+            _ => anyhow::bail!(
+                "Invalid JSON: expected '{}' at byte {}",
+                byte as char,
+                self.pos
+            ),
+        }
+    }
+
+    /// Scan an object at the current position, or return `None` if the value is not an object
+    fn object(&mut self) -> Result<Option<Object>> {
+        if self.peek() != Some(b'{') {
+            return Ok(None);
+        }
+        self.pos += 1;
+        let inner_start = self.pos;
+
+        let mut members = Vec::new();
+        loop {
+            let leading_start = self.pos;
+            self.skip_whitespace();
+            if self.peek() == Some(b'}') && members.is_empty() {
+                break;
+            }
+            let leading = leading_start..self.pos;
+
+            let key_start = self.pos;
+            self.string()?;
+            let key = key_start..self.pos;
+            let name = serde_json::from_str(&self.content[key.clone()])
+                .with_context(|| format!("Invalid JSON: bad object key at byte {}", key_start))?;
+
+            self.skip_whitespace();
+            self.expect(b':')?;
+            self.skip_whitespace();
+
+            let value_start = self.pos;
+            self.value()?;
+            let value = value_start..self.pos;
+
+            members.push(Member {
+                name,
+                leading,
+                key,
+                value,
+            });
+
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => break,
+                _ => anyhow::bail!("Invalid JSON: expected ',' or '}}' at byte {}", self.pos),
+            }
+        }
+
+        let inner = inner_start..self.pos;
+        self.pos += 1;
+        Ok(Some(Object { inner, members }))
+    }
+
+    /// Skip over any JSON value
+    fn value(&mut self) -> Result<()> {
+        match self.peek() {
+            Some(b'"') => self.string(),
+            Some(b'{') => self.object().map(|_| ()),
+            Some(b'[') => {
+                self.pos += 1;
+                self.skip_whitespace();
+                if self.peek() == Some(b']') {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                loop {
+                    self.skip_whitespace();
+                    self.value()?;
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b']') => {
+                            self.pos += 1;
+                            return Ok(());
+                        }
+                        _ => {
+                            anyhow::bail!("Invalid JSON: expected ',' or ']' at byte {}", self.pos)
+                        }
+                    }
+                }
+            }
+            Some(b'-' | b'0'..=b'9' | b't' | b'f' | b'n') => {
+                let start = self.pos;
+                while matches!(
+                    self.peek(),
+                    Some(b'-' | b'+' | b'.' | b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z')
+                ) {
+                    self.pos += 1;
+                }
+                serde_json::from_str::<serde_json::Value>(&self.content[start..self.pos])
+                    .with_context(|| format!("Invalid JSON value at byte {}", start))?;
+                Ok(())
+            }
+            _ => anyhow::bail!("Invalid JSON: unexpected input at byte {}", self.pos),
+        }
+    }
+
+    fn string(&mut self) -> Result<()> {
+        let start = self.pos;
+        self.expect(b'"')?;
+        loop {
+            match self.peek() {
+                Some(b'\\') => self.pos += 2,
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                Some(_) => self.pos += 1,
+                None => anyhow::bail!("Invalid JSON: unterminated string at byte {}", start),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_string_preserves_formatting() {
+        let json = "\u{feff}{\r\n\t\"name\": \"app\",\r\n\t\"version\":\"1.0.0\",\r\n\t\"x\": \"\\u00e9\"\r\n}";
+        let updated = set_string(json, &["version"], "1.1.0").unwrap().unwrap();
+        assert_eq!(updated, json.replace("1.0.0", "1.1.0"));
+    }
+
+    #[test]
+    fn test_set_string_nested() {
+        let json = r#"{
+    "dependencies": { "core": "^1.0.0", "other": { "core": "x" } },
+    "devDependencies": { "core": "^1.0.0" }
+}
+"#;
+        let updated = set_string(json, &["devDependencies", "core"], "^2.0.0")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            updated,
+            r#"{
+    "dependencies": { "core": "^1.0.0", "other": { "core": "x" } },
+    "devDependencies": { "core": "^2.0.0" }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_set_string_duplicate_keys() {
+        let json = r#"{ "version": "1.0.0", "deps": { "a": "1" }, "version": "1.1.0", "deps": { "a": "2" } }"#;
+        let updated = set_string(json, &["version"], "2.0.0").unwrap().unwrap();
+        assert_eq!(
+            updated,
+            r#"{ "version": "1.0.0", "deps": { "a": "1" }, "version": "2.0.0", "deps": { "a": "2" } }"#
+        );
+        // The edited value is the one serde_json reads
+        let value: serde_json::Value = serde_json::from_str(&updated).unwrap();
+        assert_eq!(value["version"], "2.0.0");
+
+        let updated = set_string(json, &["deps", "a"], "3").unwrap().unwrap();
+        assert!(updated.ends_with(r#""deps": { "a": "3" } }"#));
+    }
+
+    #[test]
+    fn test_set_string_missing_path() {
+        let json = r#"{ "name": "app", "version": [1, 2, {"a": null}] }"#;
+        assert_eq!(
+            set_string(json, &["dependencies", "core"], "1").unwrap(),
+            None
+        );
+        assert_eq!(set_string(json, &["missing"], "1").unwrap(), None);
+    }
+
+    #[test]
+    fn test_insert_string_after_key() {
+        let json = "{\n    \"name\": \"app\",\n    \"private\": true\n}\n";
+        let updated = insert_string(json, &[], "version", "1.0.0", Some("name"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            updated,
+            "{\n    \"name\": \"app\",\n    \"version\": \"1.0.0\",\n    \"private\": true\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_insert_string_first_and_empty() {
+        let json = "{\n  \"private\": true\n}";
+        let updated = insert_string(json, &[], "version", "1.0.0", Some("name"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            updated,
+            "{\n  \"version\": \"1.0.0\",\n  \"private\": true\n}"
+        );
+
+        let updated = insert_string("{}", &[], "version", "1.0.0", None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated, r#"{"version": "1.0.0"}"#);
+    }
+
+    #[test]
+    fn test_invalid_json() {
+        assert!(set_string(r#"{ "name": "app" "#, &["name"], "x").is_err());
+        assert!(set_string(r#"{ "name": "app }"#, &["name"], "x").is_err());
+    }
+}
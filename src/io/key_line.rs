@@ -0,0 +1,101 @@
+use std::fs;
+use std::path::Path;
+
+/// The 1-based line declaring `key` in the manifest at `path`, if it can be found
+pub fn locate(path: &Path, key: &str) -> Option<usize> {
+    line_of(&fs::read_to_string(path).ok()?, key)
+}
+
+/// The 1-based line declaring `key` (dot-separated, e.g. `dependencies.core`) in TOML or JSON
+///
+/// Best effort: the segments of the key are matched in order against the
+/// dotted keys and table headers of the lines that follow each other, so
+/// `[dependencies]` followed by `core = ...`, `[dependencies.core]` and
+/// `"dependencies": { "core": ... }` all locate `dependencies.core`.
+pub fn line_of(content: &str, key: &str) -> Option<usize> {
+    let segments: Vec<&str> = key.split('.').collect();
+    let mut matched = 0;
+    for (index, line) in content.lines().enumerate() {
+        let keys = declared_keys(line);
+        // A TOML table header starts over
+        if line.trim_start().starts_with('[') {
+            matched = 0;
+        }
+        let remaining = &segments[matched..];
+        let common = keys.len().min(remaining.len());
+        if common > 0 && keys[..common] == remaining[..common] {
+            matched += common;
+            if matched == segments.len() {
+                return Some(index + 1);
+            }
+        }
+    }
+    None
+}
+
+/// The dotted key or table header declared by `line`, split into its parts
+fn declared_keys(line: &str) -> Vec<&str> {
+    let trimmed = line.trim_start();
+    let keys = match trimmed.strip_prefix('[') {
+        Some(header) => header.trim_start_matches('[').split(']').next(),
+        None => trimmed.split(['=', ':']).next(),
+    };
+    keys.unwrap_or_default()
+        .split('.')
+        .map(|key| key.trim().trim_matches(['"', '\'']))
+        .filter(|key| !key.is_empty() && !key.starts_with(['{', '}']))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_of_toml() {
+        let toml = r#"[workspace.package]
+version = "1.0.0"
+
+[package]
+name = "app"
+version.workspace = true
+
+[dev-dependencies]
+core = "0.4"
+
+[dependencies]
+serde = "1"
+"core" = { path = "../core", version = "0.4" }
+"#;
+        assert_eq!(line_of(toml, "workspace.package.version"), Some(2));
+        assert_eq!(line_of(toml, "package.version"), Some(6));
+        assert_eq!(line_of(toml, "dev-dependencies.core"), Some(9));
+        assert_eq!(line_of(toml, "dependencies.core"), Some(13));
+        assert_eq!(line_of(toml, "dependencies.missing"), None);
+        assert_eq!(line_of(toml, "package.edition"), None);
+        assert_eq!(
+            line_of(
+                "[dependencies.core]\nversion = \"1\"\n",
+                "dependencies.core"
+            ),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_line_of_json() {
+        let json = r#"{
+  "name": "web",
+  "version": "1.0.0",
+  "devDependencies": {
+    "ui": "^1.0.0"
+  },
+  "dependencies": {
+    "ui": "^1.0.0"
+  }
+}
+"#;
+        assert_eq!(line_of(json, "version"), Some(3));
+        assert_eq!(line_of(json, "dependencies.ui"), Some(8));
+    }
+}
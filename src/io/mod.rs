@@ -1,42 +1,463 @@
+pub mod cargo_lock;
 pub mod cargo_toml;
+pub mod changelog;
+pub mod changeset;
+pub mod config;
+pub mod git;
+pub mod json_edit;
+pub mod key_line;
 pub mod package_json;
+pub mod package_lock;
+pub mod pnpm_lock;
+pub mod pnpm_workspace;
+pub mod pyproject_toml;
+pub mod workspace_members;
 
 use crate::cli::IgnoreOptions;
-use crate::domain::{Workspace, WorkspaceMember};
+use crate::domain::scheme::VersionScheme;
+use crate::domain::{Ecosystem, VersionField, VersionGroup, Workspace, WorkspaceMember};
 use anyhow::{Context, Result};
+use config::Config;
 use ignore::WalkBuilder;
-use std::path::Path;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Load the project configuration for the current workspace
+///
+/// The configuration is read from the workspace root directory, or the
+/// current directory when no root can be determined.
+pub fn load_config() -> Result<Config> {
+    let current_dir = std::env::current_dir().with_context(|| "Failed to get current directory")?;
+
+    let root = find_workspace_root(&current_dir)?;
+    let dir = root
+        .as_deref()
+        .and_then(|r| r.parent())
+        .unwrap_or(&current_dir);
+
+    config::load(dir)
+}
 
 /// Load the current workspace from the file system
 ///
-/// This function discovers members from all supported ecosystems
-/// and builds a composite workspace.
-pub fn load_workspace(ignore_options: &IgnoreOptions) -> Result<Workspace> {
+/// This function locates the workspace root, discovers members from all
+/// supported ecosystems beneath it and builds a composite workspace.
+pub fn load_workspace(ignore_options: &IgnoreOptions, config: &Config) -> Result<Workspace> {
     let current_dir = std::env::current_dir().with_context(|| "Failed to get current directory")?;
 
-    let members = discover_members(&current_dir, ignore_options)?;
+    let root = find_workspace_root(&current_dir)?;
+    let discovery_root = root
+        .as_deref()
+        .and_then(|r| r.parent())
+        .unwrap_or(&current_dir);
+
+    let members = discover_members(discovery_root, ignore_options)?;
+    let schemes = read_schemes(&members, config)?;
+    let groups = read_groups(config)?;
+    let inheritable_version = match &root {
+        Some(root) if root.file_name().is_some_and(|f| f == "Cargo.toml") => {
+            cargo_toml::has_workspace_version(root)?
+        }
+        _ => false,
+    };
+
+    Ok(Workspace {
+        members,
+        root,
+        schemes,
+        groups,
+        cascade: None,
+        inheritable_version,
+    })
+}
+
+/// Read the version groups declared in the configuration
+///
+/// A package may belong to at most one group.
+pub fn read_groups(config: &Config) -> Result<Vec<VersionGroup>> {
+    let mut groups: Vec<VersionGroup> = Vec::new();
+    for (name, group) in &config.groups {
+        for package in &group.members {
+            if let Some(other) = groups.iter().find(|g| g.members.contains(package)) {
+                anyhow::bail!(
+                    "Package '{}' is listed in both group '{}' and group '{}'",
+                    package,
+                    other.name,
+                    name
+                );
+            }
+        }
+
+        groups.push(VersionGroup {
+            name: name.clone(),
+            kind: group.kind,
+            members: group.members.clone(),
+        });
+    }
+    Ok(groups)
+}
+
+/// Read the versioning schemes of workspace members
+///
+/// A scheme declared in the member's own manifest wins, then its
+/// `[packages.<name>]` entry in the configuration, then the configuration's
+/// default `scheme`. Members that end up without one use semver and are left
+/// out of the map.
+pub fn read_schemes(
+    members: &[WorkspaceMember],
+    config: &Config,
+) -> Result<HashMap<PathBuf, VersionScheme>> {
+    let mut schemes = HashMap::new();
+    for member in members {
+        let manifest = member.manifest_path();
+        let declared = match member.ecosystem() {
+            Ecosystem::Cargo => cargo_toml::parse_scheme(&manifest)?,
+            Ecosystem::Node => package_json::parse_scheme(&manifest)?,
+            Ecosystem::Python => pyproject_toml::parse_scheme(&manifest)?,
+        };
+
+        let configured = config
+            .packages
+            .get(member.name())
+            .and_then(|p| p.scheme.clone())
+            .or_else(|| config.scheme.clone());
 
-    Ok(Workspace { members })
+        let scheme = match (declared, configured) {
+            (Some(scheme), _) => scheme
+                .parse::<VersionScheme>()
+                .with_context(|| format!("Invalid version scheme in {}", manifest.display()))?,
+            (None, Some(scheme)) => scheme.parse::<VersionScheme>().with_context(|| {
+                format!("Invalid version scheme configured for {}", member.name())
+            })?,
+            (None, None) => continue,
+        };
+
+        if scheme != VersionScheme::Semver {
+            schemes.insert(member.path().clone(), scheme);
+        }
+    }
+    Ok(schemes)
+}
+
+/// Find the manifest of the workspace root for `start`
+///
+/// The nearest Cargo.toml with a `[workspace]` section, or package.json with a
+/// `workspaces` field or sibling pnpm-workspace.yaml, at or above `start` wins
+/// (Cargo first when both live in the same directory). Without one, a manifest
+/// in `start` itself is treated as the root of a single-package project.
+pub fn find_workspace_root(start: &Path) -> Result<Option<PathBuf>> {
+    for dir in start.ancestors() {
+        let cargo_manifest = dir.join("Cargo.toml");
+        if cargo_manifest.is_file() && cargo_toml::is_workspace_root(&cargo_manifest)? {
+            return Ok(Some(cargo_manifest));
+        }
+
+        let node_manifest = dir.join("package.json");
+        if node_manifest.is_file()
+            && (dir.join("pnpm-workspace.yaml").is_file()
+                || package_json::is_workspace_root(&node_manifest)?)
+        {
+            return Ok(Some(node_manifest));
+        }
+    }
+
+    let single_package = ["Cargo.toml", "package.json", "pyproject.toml"]
+        .into_iter()
+        .map(|name| start.join(name))
+        .find(|manifest| manifest.is_file());
+
+    Ok(single_package)
+}
+
+/// A pending edit to a manifest file
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileUpdate {
+    pub path: PathBuf,
+    pub original: String,
+    pub updated: String,
+    /// The file doesn't exist yet; restoring it means removing it
+    pub created: bool,
+    /// The file is removed rather than rewritten; `updated` is empty
+    pub deleted: bool,
+}
+
+impl FileUpdate {
+    /// Render this update as a unified diff, labelling both sides with `display_path`
+    pub fn unified_diff(&self, display_path: &str) -> String {
+        let original = if self.created {
+            "/dev/null".to_string()
+        } else {
+            format!("a/{}", display_path)
+        };
+        let updated = if self.deleted {
+            "/dev/null".to_string()
+        } else {
+            format!("b/{}", display_path)
+        };
+        similar::TextDiff::from_lines(&self.original, &self.updated)
+            .unified_diff()
+            .header(&original, &updated)
+            .to_string()
+    }
 }
 
-/// Save workspace changes back to the file system
+/// Compute the manifest edits needed to save `workspace`, without writing them
 ///
-/// This function delegates to the appropriate ecosystem-specific saver
-/// based on the WorkspaceMember types.
-pub fn save_workspace(workspace: &Workspace) -> Result<()> {
+/// Only files whose contents would change are returned.
+pub fn render_workspace(workspace: &Workspace) -> Result<Vec<FileUpdate>> {
+    let mut updates = Vec::new();
+
     for member in &workspace.members {
-        match member {
-            WorkspaceMember::Cargo { path, version, .. } => {
-                cargo_toml::update_version(&path.join("Cargo.toml"), version)?;
+        let path = member.manifest_path();
+        let original = fs::read_to_string(&path). //-
+            with_context(|| format!("Failed to read {}", path.display()))?;
+
+        let updated = match member {
+            WorkspaceMember::Cargo {
+                version,
+                dependencies,
+                ..
+            } => {
+                let updated = cargo_toml::render_version(&path, &original, version)?;
+                cargo_toml::render_dependencies(&path, &updated, dependencies)?
             }
-            WorkspaceMember::Node { path, version, .. } => {
-                package_json::update_version(&path.join("package.json"), version)?;
+            WorkspaceMember::Node {
+                version,
+                dependencies,
+                ..
+            } => {
+                let updated = package_json::render_version(&path, &original, version)?;
+                package_json::render_dependencies(&path, &updated, dependencies)?
+            }
+            WorkspaceMember::Python { version, .. } => {
+                pyproject_toml::render_version(&path, &original, version)?
             }
+        };
+
+        if updated != original {
+            updates.push(FileUpdate {
+                path,
+                original,
+                updated,
+                created: false,
+                deleted: false,
+            });
+        }
+    }
+
+    updates.extend(render_lockfiles(workspace)?);
+    Ok(updates)
+}
+
+/// Compute the lockfile edits that keep Cargo.lock, package-lock.json and
+/// pnpm-lock.yaml in step with the workspace members' versions and requirements
+///
+/// Lockfiles are looked for beside the workspace root and each member's
+/// manifest. Nothing outside the entries for workspace members is touched.
+fn render_lockfiles(workspace: &Workspace) -> Result<Vec<FileUpdate>> {
+    let mut dirs: Vec<&Path> = workspace
+        .root
+        .as_deref()
+        .and_then(|root| root.parent())
+        .into_iter()
+        .chain(workspace.members.iter().map(|m| m.path().as_path()))
+        .collect();
+    dirs.sort();
+    dirs.dedup();
+
+    let members_in = |ecosystem: Ecosystem, dir: &Path| {
+        workspace
+            .members
+            .iter()
+            .filter(|m| m.ecosystem() == ecosystem && m.path().starts_with(dir))
+            .collect::<Vec<_>>()
+    };
+
+    let mut updates = Vec::new();
+    for dir in dirs {
+        let cargo = members_in(Ecosystem::Cargo, dir);
+        let node = members_in(Ecosystem::Node, dir);
+
+        let cargo_lock = dir.join(cargo_lock::LOCKFILE);
+        if !cargo.is_empty() && cargo_lock.is_file() {
+            // Members inheriting `version.workspace = true` take the version of the
+            // workspace root manifest beside the lockfile
+            let root_version =
+                cargo
+                    .iter()
+                    .find(|m| m.path() == dir)
+                    .and_then(|m| match m.version() {
+                        VersionField::Concrete(version) => Some(version.clone()),
+                        _ => None,
+                    });
+            let versions = cargo
+                .iter()
+                .filter_map(|m| match m.version() {
+                    VersionField::Concrete(version) => {
+                        Some((m.name().to_string(), version.clone()))
+                    }
+                    VersionField::Inherited => root_version
+                        .clone()
+                        .map(|version| (m.name().to_string(), version)),
+                    _ => None,
+                })
+                .collect();
+            updates.extend(render_lockfile(&cargo_lock, |original| {
+                cargo_lock::render(&cargo_lock, original, &versions)
+            })?);
+        }
+
+        if node.is_empty() {
+            continue;
+        }
+        let package_lock = dir.join(package_lock::LOCKFILE);
+        if package_lock.is_file() {
+            updates.extend(render_lockfile(&package_lock, |original| {
+                package_lock::render(&package_lock, original, dir, &node)
+            })?);
+        }
+        let pnpm_lock = dir.join(pnpm_lock::LOCKFILE);
+        if pnpm_lock.is_file() {
+            updates.extend(render_lockfile(&pnpm_lock, |original| {
+                Ok(pnpm_lock::render(original, dir, &node))
+            })?);
+        }
+    }
+
+    Ok(updates)
+}
+
+/// Read the lockfile at `path` and apply `render`, returning an update if it changed
+fn render_lockfile(
+    path: &Path,
+    render: impl FnOnce(&str) -> Result<String>,
+) -> Result<Option<FileUpdate>> {
+    let original =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let updated = render(&original)?;
+
+    Ok((updated != original).then(|| FileUpdate {
+        path: path.to_path_buf(),
+        original,
+        updated,
+        created: false,
+        deleted: false,
+    }))
+}
+
+/// Write rendered manifest edits to the file system, all or nothing
+///
+/// Every update is first written to a temp file beside its original, then the
+/// temp files are renamed into place and deleted files are removed. If any
+/// step fails, temp files are removed and files that were already replaced or
+/// deleted get their original contents back. Returns the paths of the files
+/// written or deleted.
+pub fn write_updates(updates: &[FileUpdate]) -> Result<Vec<PathBuf>> {
+    let (deletions, writes): (Vec<&FileUpdate>, Vec<&FileUpdate>) =
+        updates.iter().partition(|update| update.deleted);
+
+    let mut staged = Vec::new();
+    for update in &writes {
+        match stage(&update.path, &update.updated) {
+            Ok(temp_path) => staged.push(temp_path),
+            Err(e) => {
+                discard(&staged);
+                return Err(e);
+            }
+        }
+    }
+
+    let mut committed: Vec<&FileUpdate> = Vec::new();
+    for (i, (update, temp_path)) in writes.iter().zip(&staged).enumerate() {
+        if let Err(e) = fs::rename(temp_path, &update.path) {
+            discard(&staged[i..]);
+            return Err(rollback(e, &update.path, &committed));
+        }
+        committed.push(update);
+    }
+
+    for update in deletions {
+        if let Err(e) = fs::remove_file(&update.path) {
+            return Err(rollback(e, &update.path, &committed));
+        }
+        committed.push(update);
+    }
+
+    Ok(committed.iter().map(|update| update.path.clone()).collect())
+}
+
+/// Restore already-committed updates after failing to write `path`
+fn rollback(e: std::io::Error, path: &Path, committed: &[&FileUpdate]) -> anyhow::Error {
+    let restore_errors = restore(committed);
+
+    let mut error = anyhow::Error::new(e).context(format!("Failed to write {}", path.display()));
+    if !restore_errors.is_empty() {
+        error = error.context(format!(
+            "Failed to restore original contents of: {}",
+            restore_errors.join(", ")
+        ));
+    }
+    error
+}
+
+/// Write `contents` to a temp file beside `path`, keeping the original's permissions
+fn stage(path: &Path, contents: &str) -> Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .with_context(|| format!("Invalid file path: {}", path.display()))?;
+    let temp_path = path.with_file_name(format!(".{}.odometer-tmp", file_name.to_string_lossy()));
+
+    fs::write(&temp_path, contents)
+        .with_context(|| format!("Failed to write {}", temp_path.display()))?;
+
+    if let Ok(metadata) = fs::metadata(path) {
+        if let Err(e) = fs::set_permissions(&temp_path, metadata.permissions()) {
+            discard(std::slice::from_ref(&temp_path));
+            return Err(e)
+                .with_context(|| format!("Failed to set permissions on {}", temp_path.display()));
         }
     }
-    Ok(())
+
+    Ok(temp_path)
 }
 
+/// Best-effort removal of temp files
+fn discard(temp_paths: &[PathBuf]) {
+    for temp_path in temp_paths {
+        let _ = fs::remove_file(temp_path);
+    }
+}
+
+/// Put back the original contents of already-committed updates, removing created files
+///
+/// Returns the paths that could not be restored.
+fn restore(committed: &[&FileUpdate]) -> Vec<String> {
+    committed
+        .iter()
+        .filter(|update| {
+            if update.created {
+                return fs::remove_file(&update.path).is_err();
+            }
+            stage(&update.path, &update.original)
+                .and_then(|temp_path| {
+                    fs::rename(&temp_path, &update.path).map_err(|e| {
+                        discard(std::slice::from_ref(&temp_path));
+                        anyhow::Error::new(e)
+                    })
+                })
+                .is_err()
+        })
+        .map(|update| update.path.display().to_string())
+        .collect()
+}
+
+/// Discover workspace members beneath `root`
+///
+/// When the manifests in `root` declare their members (Cargo `[workspace]`
+/// `members`/`exclude`, npm `workspaces` or pnpm-workspace.yaml `packages`),
+/// only those members are loaded for that ecosystem. Ecosystems without a
+/// declaration include every manifest found by walking the directory tree.
 pub fn discover_members(
     root: &Path,
     ignore_options: &IgnoreOptions,
@@ -48,6 +469,9 @@ pub fn discover_members(
         ));
     }
 
+    let declared = workspace_members::DeclaredMembers::read(root)?
+        .with_exclude(&ignore_options.exclude)
+        .with_context(|| "Invalid --exclude pattern")?;
     let mut members = Vec::new();
 
     // Configure WalkBuilder based on ignore options
@@ -83,27 +507,52 @@ pub fn discover_members(
             .parent()
             .with_context(|| format!("Invalid path structure: {}", path.display()))?;
 
+        let ecosystem = match path.file_name().and_then(|f| f.to_str()) {
+            Some("Cargo.toml") => Ecosystem::Cargo,
+            Some("package.json") => Ecosystem::Node,
+            Some("pyproject.toml") => Ecosystem::Python,
+            _ => continue,
+        };
+
+        let relative_dir = parent_path.strip_prefix(root).unwrap_or(parent_path);
+        if !declared.includes(ecosystem, relative_dir) {
+            continue;
+        }
+
         let basename = parent_path
             .file_name()
             .with_context(|| format!("Cannot determine directory name for {}", path.display()))?
             .to_string_lossy()
             .to_string();
 
-        if path.file_name() == Some("Cargo.toml".as_ref()) {
+        if ecosystem == Ecosystem::Cargo {
             let (name, version) = cargo_toml::parse(path)?;
+            let dependencies = cargo_toml::parse_dependencies(path)?;
 
             members.push(WorkspaceMember::Cargo {
                 name: name.unwrap_or(basename),
                 path: parent_path.to_path_buf(),
                 version,
+                dependencies,
             });
-        } else if path.file_name() == Some("package.json".as_ref()) {
+        } else if ecosystem == Ecosystem::Node {
             let (name, version) = package_json::parse(path)?;
+            let dependencies = package_json::parse_dependencies(path)?;
 
             members.push(WorkspaceMember::Node {
                 name: name.unwrap_or(basename),
                 path: parent_path.to_path_buf(),
                 version,
+                dependencies,
+            });
+        } else {
+            let (name, version) = pyproject_toml::parse(path)?;
+
+            members.push(WorkspaceMember::Python {
+                name: name.unwrap_or(basename),
+                path: parent_path.to_path_buf(),
+                version,
+                dependencies: Vec::new(),
             });
         }
     }
@@ -278,6 +727,41 @@ version = "1.0.0"
         assert!(members.iter().any(|m| m.name() == "node-pkg"));
     }
 
+    #[test]
+    fn test_discover_members_python() {
+        let dir = tempdir().unwrap();
+        let pep621_dir = dir.path().join("pep621");
+        let dynamic_dir = dir.path().join("dynamic");
+        fs::create_dir(&pep621_dir).unwrap();
+        fs::create_dir(&dynamic_dir).unwrap();
+        write_file(
+            &pep621_dir.join("pyproject.toml"),
+            r#"[project]
+name = "py-pkg"
+version = "0.3.0"
+"#,
+        );
+        write_file(
+            &dynamic_dir.join("pyproject.toml"),
+            r#"[project]
+name = "py-dynamic"
+dynamic = ["version"]
+"#,
+        );
+
+        let members = discover_members(dir.path(), &IgnoreOptions::default()).unwrap();
+        assert_eq!(members.len(), 2);
+        assert!(matches!(
+            &members[0],
+            WorkspaceMember::Python { name, version: VersionField::Dynamic, .. } if name == "py-dynamic"
+        ));
+        assert!(matches!(
+            &members[1],
+            WorkspaceMember::Python { name, version: VersionField::Concrete(v), .. }
+                if name == "py-pkg" && v == "0.3.0"
+        ));
+    }
+
     #[test]
     fn test_discover_members_invalid_path() {
         // Test handling of invalid path structure
@@ -407,4 +891,588 @@ version = "2.0.0"
         assert_eq!(members[0].name(), "a-pkg");
         assert_eq!(members[1].name(), "z-pkg");
     }
+
+    #[test]
+    fn test_discover_members_honors_cargo_workspace_members() {
+        let dir = tempdir().unwrap();
+        write_file(
+            &dir.path().join("Cargo.toml"),
+            r#"[workspace]
+members = ["crates/*"]
+exclude = ["crates/scratch"]
+
+[workspace.package]
+version = "1.0.0"
+"#,
+        );
+        for (rel, name) in [
+            ("crates/core", "core"),
+            ("crates/scratch", "scratch"),
+            ("crates/core/examples/demo", "demo"),
+            ("vendor/serde", "serde"),
+        ] {
+            let pkg_dir = dir.path().join(rel);
+            fs::create_dir_all(&pkg_dir).unwrap();
+            write_file(
+                &pkg_dir.join("Cargo.toml"),
+                &format!("[package]\nname = \"{}\"\nversion = \"0.1.0\"\n", name),
+            );
+        }
+        // Undeclared manifests are never parsed, so broken fixtures don't fail discovery
+        fs::create_dir_all(dir.path().join("tests/fixtures/broken")).unwrap();
+        write_file(
+            &dir.path().join("tests/fixtures/broken/Cargo.toml"),
+            "not toml",
+        );
+
+        let members = discover_members(dir.path(), &IgnoreOptions::default()).unwrap();
+        let names: Vec<&str> = members.iter().map(|m| m.name()).collect();
+        let root_name = dir.path().file_name().unwrap().to_str().unwrap();
+        assert_eq!(names.len(), 2);
+        assert!(names.contains(&"core"));
+        assert!(names.contains(&root_name));
+    }
+
+    #[test]
+    fn test_discover_members_honors_node_workspaces() {
+        let dir = tempdir().unwrap();
+        write_file(
+            &dir.path().join("package.json"),
+            r#"{ "name": "root", "version": "1.0.0", "workspaces": ["packages/*"] }"#,
+        );
+        for (rel, name) in [("packages/a", "a"), ("examples/demo", "demo")] {
+            let pkg_dir = dir.path().join(rel);
+            fs::create_dir_all(&pkg_dir).unwrap();
+            write_file(
+                &pkg_dir.join("package.json"),
+                &format!(r#"{{ "name": "{}", "version": "1.0.0" }}"#, name),
+            );
+        }
+        // Cargo has no workspace manifest here, so it falls back to the walk
+        let tool_dir = dir.path().join("tools/helper");
+        fs::create_dir_all(&tool_dir).unwrap();
+        write_file(
+            &tool_dir.join("Cargo.toml"),
+            "[package]\nname = \"helper\"\nversion = \"0.1.0\"\n",
+        );
+
+        let members = discover_members(dir.path(), &IgnoreOptions::default()).unwrap();
+        let names: Vec<&str> = members.iter().map(|m| m.name()).collect();
+        assert_eq!(names, vec!["a", "helper", "root"]);
+    }
+
+    #[test]
+    fn test_find_workspace_root_pnpm() {
+        let dir = tempdir().unwrap();
+        write_file(
+            &dir.path().join("package.json"),
+            r#"{ "name": "root", "private": true }"#,
+        );
+        write_file(
+            &dir.path().join("pnpm-workspace.yaml"),
+            "packages:\n  - 'packages/*'\n",
+        );
+
+        let root = find_workspace_root(dir.path()).unwrap();
+        assert_eq!(root, Some(dir.path().join("package.json")));
+    }
+
+    #[test]
+    fn test_find_workspace_root_from_member_directory() {
+        let dir = tempdir().unwrap();
+        let member_dir = dir.path().join("crates").join("member");
+        fs::create_dir_all(&member_dir).unwrap();
+        write_file(
+            &dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        );
+        write_file(
+            &member_dir.join("Cargo.toml"),
+            "[package]\nname = \"member\"\nversion = \"1.0.0\"\n",
+        );
+
+        let root = find_workspace_root(&member_dir).unwrap();
+        assert_eq!(root, Some(dir.path().join("Cargo.toml")));
+    }
+
+    #[test]
+    fn test_find_workspace_root_node_workspaces() {
+        let dir = tempdir().unwrap();
+        let pkg_dir = dir.path().join("packages").join("a");
+        fs::create_dir_all(&pkg_dir).unwrap();
+        write_file(
+            &dir.path().join("package.json"),
+            r#"{ "name": "root", "version": "1.0.0", "workspaces": ["packages/*"] }"#,
+        );
+        write_file(
+            &pkg_dir.join("package.json"),
+            r#"{ "name": "a", "version": "1.0.0" }"#,
+        );
+
+        let root = find_workspace_root(&pkg_dir).unwrap();
+        assert_eq!(root, Some(dir.path().join("package.json")));
+    }
+
+    #[test]
+    fn test_find_workspace_root_single_package() {
+        let dir = tempdir().unwrap();
+        write_file(
+            &dir.path().join("pyproject.toml"),
+            "[project]\nname = \"solo\"\nversion = \"1.0.0\"\n",
+        );
+
+        let root = find_workspace_root(dir.path()).unwrap();
+        assert_eq!(root, Some(dir.path().join("pyproject.toml")));
+    }
+
+    #[test]
+    fn test_find_workspace_root_none() {
+        let dir = tempdir().unwrap();
+        let nested = dir.path().join("nested");
+        fs::create_dir(&nested).unwrap();
+        write_file(
+            &nested.join("Cargo.toml"),
+            "[package]\nname = \"nested\"\nversion = \"1.0.0\"\n",
+        );
+
+        assert_eq!(find_workspace_root(dir.path()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_render_workspace_updates_dependency_requirements() {
+        let dir = tempdir().unwrap();
+        let core_dir = dir.path().join("core");
+        let app_dir = dir.path().join("app");
+        let web_dir = dir.path().join("web");
+        let ui_dir = dir.path().join("ui");
+        for d in [&core_dir, &app_dir, &web_dir, &ui_dir] {
+            fs::create_dir(d).unwrap();
+        }
+        write_file(
+            &core_dir.join("Cargo.toml"),
+            "[package]\nname = \"core\"\nversion = \"1.2.0\"\n",
+        );
+        write_file(
+            &app_dir.join("Cargo.toml"),
+            r#"[package]
+name = "app"
+version = "0.1.0"
+
+[dependencies]
+core = { path = "../core", version = "1.2" }
+"#,
+        );
+        write_file(
+            &ui_dir.join("package.json"),
+            r#"{ "name": "ui", "version": "1.2.0" }"#,
+        );
+        write_file(
+            &web_dir.join("package.json"),
+            r#"{ "name": "web", "version": "0.1.0", "dependencies": { "ui": "^1.2.0" } }"#,
+        );
+
+        let mut workspace = Workspace {
+            members: discover_members(dir.path(), &IgnoreOptions::default()).unwrap(),
+            ..Default::default()
+        };
+        let selection =
+            crate::domain::PackageSelection::Specific(vec!["core".to_string(), "ui".to_string()]);
+        let result = workspace
+            .roll_version(crate::domain::VersionBump::Major(1), &selection)
+            .unwrap();
+        assert_eq!(result.dependency_changes.len(), 2);
+        write_updates(&render_workspace(&workspace).unwrap()).unwrap();
+
+        let app = fs::read_to_string(app_dir.join("Cargo.toml")).unwrap();
+        assert!(app.contains(r#"core = { path = "../core", version = "2.0" }"#));
+        let web = package_json::parse_dependencies(&web_dir.join("package.json")).unwrap();
+        assert_eq!(web[0].requirement, Some("^2.0.0".to_string()));
+    }
+
+    #[test]
+    fn test_render_workspace_does_not_write() {
+        let dir = tempdir().unwrap();
+        let core_dir = dir.path().join("core");
+        let app_dir = dir.path().join("app");
+        fs::create_dir(&core_dir).unwrap();
+        fs::create_dir(&app_dir).unwrap();
+        let core_manifest = "[package]\nname = \"core\"\nversion = \"1.2.0\"\n";
+        let app_manifest = "[package]\nname = \"app\"\nversion = \"0.1.0\"\n";
+        write_file(&core_dir.join("Cargo.toml"), core_manifest);
+        write_file(&app_dir.join("Cargo.toml"), app_manifest);
+
+        let mut workspace = Workspace {
+            members: discover_members(dir.path(), &IgnoreOptions::default()).unwrap(),
+            ..Default::default()
+        };
+        let selection = crate::domain::PackageSelection::Specific(vec!["core".to_string()]);
+        workspace
+            .roll_version(crate::domain::VersionBump::Minor(1), &selection)
+            .unwrap();
+
+        let updates = render_workspace(&workspace).unwrap();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].path, core_dir.join("Cargo.toml"));
+        assert_eq!(updates[0].original, core_manifest);
+        assert_eq!(updates[0].updated, core_manifest.replace("1.2.0", "1.3.0"));
+
+        // Nothing on disk changed
+        let core = fs::read_to_string(core_dir.join("Cargo.toml")).unwrap();
+        assert_eq!(core, core_manifest);
+
+        assert_eq!(
+            updates[0].unified_diff("core/Cargo.toml"),
+            r#"--- a/core/Cargo.toml
++++ b/core/Cargo.toml
+@@ -1,3 +1,3 @@
+ [package]
+ name = "core"
+-version = "1.2.0"
++version = "1.3.0"
+"#
+        );
+    }
+
+    #[test]
+    fn test_render_workspace_updates_lockfiles() {
+        let dir = tempdir().unwrap();
+        let core_dir = dir.path().join("core");
+        let ui_dir = dir.path().join("ui");
+        fs::create_dir(&core_dir).unwrap();
+        fs::create_dir(&ui_dir).unwrap();
+        write_file(
+            &dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"core\"]\n",
+        );
+        write_file(
+            &core_dir.join("Cargo.toml"),
+            "[package]\nname = \"core\"\nversion = \"1.2.0\"\n",
+        );
+        let cargo_lock = "version = 4\n\n[[package]]\nname = \"core\"\nversion = \"1.2.0\"\n";
+        write_file(&dir.path().join("Cargo.lock"), cargo_lock);
+        write_file(
+            &ui_dir.join("package.json"),
+            r#"{ "name": "ui", "version": "1.2.0" }"#,
+        );
+        let package_lock = r#"{ "packages": { "ui": { "version": "1.2.0" } } }"#;
+        write_file(&dir.path().join("package-lock.json"), package_lock);
+
+        let mut workspace = Workspace {
+            members: discover_members(dir.path(), &IgnoreOptions::default()).unwrap(),
+            root: Some(dir.path().join("Cargo.toml")),
+            ..Default::default()
+        };
+        let selection =
+            crate::domain::PackageSelection::Specific(vec!["core".to_string(), "ui".to_string()]);
+        workspace
+            .roll_version(crate::domain::VersionBump::Minor(1), &selection)
+            .unwrap();
+        let written = write_updates(&render_workspace(&workspace).unwrap()).unwrap();
+
+        assert!(written.contains(&dir.path().join("Cargo.lock")));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("Cargo.lock")).unwrap(),
+            cargo_lock.replace("1.2.0", "1.3.0")
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("package-lock.json")).unwrap(),
+            package_lock.replace("1.2.0", "1.3.0")
+        );
+    }
+
+    #[test]
+    fn test_write_updates_commits_all() {
+        let dir = tempdir().unwrap();
+        let a = dir.path().join("a.toml");
+        let b = dir.path().join("b.toml");
+        write_file(&a, "a = 1\n");
+        write_file(&b, "b = 1\n");
+
+        let updates = vec![
+            FileUpdate {
+                path: a.clone(),
+                original: "a = 1\n".to_string(),
+                updated: "a = 2\n".to_string(),
+                created: false,
+                deleted: false,
+            },
+            FileUpdate {
+                path: b.clone(),
+                original: "b = 1\n".to_string(),
+                updated: "b = 2\n".to_string(),
+                created: false,
+                deleted: false,
+            },
+        ];
+        let committed = write_updates(&updates).unwrap();
+
+        assert_eq!(committed, vec![a.clone(), b.clone()]);
+        assert_eq!(fs::read_to_string(&a).unwrap(), "a = 2\n");
+        assert_eq!(fs::read_to_string(&b).unwrap(), "b = 2\n");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_write_updates_staging_failure_writes_nothing() {
+        let dir = tempdir().unwrap();
+        let a = dir.path().join("a.toml");
+        write_file(&a, "a = 1\n");
+
+        let updates = vec![
+            FileUpdate {
+                path: a.clone(),
+                original: "a = 1\n".to_string(),
+                updated: "a = 2\n".to_string(),
+                created: false,
+                deleted: false,
+            },
+            FileUpdate {
+                path: dir.path().join("missing/b.toml"),
+                original: "b = 1\n".to_string(),
+                updated: "b = 2\n".to_string(),
+                created: false,
+                deleted: false,
+            },
+        ];
+        assert!(write_updates(&updates).is_err());
+
+        assert_eq!(fs::read_to_string(&a).unwrap(), "a = 1\n");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_write_updates_rename_failure_restores_originals() {
+        let dir = tempdir().unwrap();
+        let a = dir.path().join("a.toml");
+        let b = dir.path().join("b.toml");
+        write_file(&a, "a = 1\n");
+        // A directory can't be replaced by a file, so the second rename fails
+        fs::create_dir(&b).unwrap();
+
+        let updates = vec![
+            FileUpdate {
+                path: a.clone(),
+                original: "a = 1\n".to_string(),
+                updated: "a = 2\n".to_string(),
+                created: false,
+                deleted: false,
+            },
+            FileUpdate {
+                path: b.clone(),
+                original: String::new(),
+                updated: "b = 2\n".to_string(),
+                created: false,
+                deleted: false,
+            },
+        ];
+        let err = write_updates(&updates).unwrap_err();
+
+        assert!(err.to_string().contains("Failed to write"));
+        assert_eq!(fs::read_to_string(&a).unwrap(), "a = 1\n");
+        assert!(b.is_dir());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_write_updates_rename_failure_removes_created_files() {
+        let dir = tempdir().unwrap();
+        let created = dir.path().join("CHANGELOG.md");
+        let b = dir.path().join("b.toml");
+        fs::create_dir(&b).unwrap();
+
+        let updates = vec![
+            FileUpdate {
+                path: created.clone(),
+                original: String::new(),
+                updated: "# Changelog\n".to_string(),
+                created: true,
+                deleted: false,
+            },
+            FileUpdate {
+                path: b.clone(),
+                original: String::new(),
+                updated: "b = 2\n".to_string(),
+                created: false,
+                deleted: false,
+            },
+        ];
+        assert!(write_updates(&updates).is_err());
+        assert!(!created.exists());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_write_updates_deletes_files() {
+        let dir = tempdir().unwrap();
+        let a = dir.path().join("a.toml");
+        let gone = dir.path().join("gone.md");
+        write_file(&a, "a = 1\n");
+        write_file(&gone, "bye\n");
+
+        let deletion = FileUpdate {
+            path: gone.clone(),
+            original: "bye\n".to_string(),
+            updated: String::new(),
+            created: false,
+            deleted: true,
+        };
+        assert_eq!(
+            deletion.unified_diff("gone.md"),
+            "--- a/gone.md\n+++ /dev/null\n@@ -1 +0,0 @@\n-bye\n"
+        );
+
+        let updates = vec![
+            deletion,
+            FileUpdate {
+                path: a.clone(),
+                original: "a = 1\n".to_string(),
+                updated: "a = 2\n".to_string(),
+                created: false,
+                deleted: false,
+            },
+        ];
+        let committed = write_updates(&updates).unwrap();
+
+        assert_eq!(committed, vec![a.clone(), gone.clone()]);
+        assert_eq!(fs::read_to_string(&a).unwrap(), "a = 2\n");
+        assert!(!gone.exists());
+    }
+
+    #[test]
+    fn test_write_updates_deletion_failure_restores_originals() {
+        let dir = tempdir().unwrap();
+        let a = dir.path().join("a.toml");
+        write_file(&a, "a = 1\n");
+
+        let updates = vec![
+            FileUpdate {
+                path: a.clone(),
+                original: "a = 1\n".to_string(),
+                updated: "a = 2\n".to_string(),
+                created: false,
+                deleted: false,
+            },
+            FileUpdate {
+                path: dir.path().join("missing.md"),
+                original: "bye\n".to_string(),
+                updated: String::new(),
+                created: false,
+                deleted: true,
+            },
+        ];
+        assert!(write_updates(&updates).is_err());
+        assert_eq!(fs::read_to_string(&a).unwrap(), "a = 1\n");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_read_schemes() {
+        let dir = tempdir().unwrap();
+        for name in ["api", "web", "tool", "lib"] {
+            fs::create_dir(dir.path().join(name)).unwrap();
+        }
+        write_file(
+            &dir.path().join("api/Cargo.toml"),
+            "[package]\nname = \"api\"\nversion = \"2024.3.0\"\n\n[package.metadata.odometer]\nscheme = \"calver:YYYY.MM.MICRO\"\n",
+        );
+        write_file(
+            &dir.path().join("web/package.json"),
+            r#"{ "name": "web", "version": "24.11.0", "odometer": { "scheme": "calver:YY.0W.N" } }"#,
+        );
+        write_file(
+            &dir.path().join("tool/pyproject.toml"),
+            "[project]\nname = \"tool\"\nversion = \"2024.3\"\n\n[tool.odometer]\nscheme = \"calver:YYYY.MM\"\n",
+        );
+        write_file(
+            &dir.path().join("lib/Cargo.toml"),
+            "[package]\nname = \"lib\"\nversion = \"1.0.0\"\n",
+        );
+
+        let members = discover_members(dir.path(), &IgnoreOptions::default()).unwrap();
+        let schemes = read_schemes(&members, &Config::default()).unwrap();
+        assert_eq!(schemes.len(), 3);
+        assert_eq!(
+            schemes[&dir.path().join("web")].to_string(),
+            "calver:YY.0W.N"
+        );
+        assert!(!schemes.contains_key(&dir.path().join("lib")));
+
+        write_file(
+            &dir.path().join("lib/Cargo.toml"),
+            "[package]\nname = \"lib\"\nversion = \"1.0.0\"\n\n[package.metadata.odometer]\nscheme = \"romver\"\n",
+        );
+        let err = read_schemes(&members, &Config::default()).unwrap_err();
+        assert!(err.to_string().contains("Invalid version scheme"));
+    }
+
+    #[test]
+    fn test_read_schemes_from_config() {
+        let dir = tempdir().unwrap();
+        for name in ["api", "web", "lib"] {
+            fs::create_dir(dir.path().join(name)).unwrap();
+        }
+        write_file(
+            &dir.path().join("api/Cargo.toml"),
+            "[package]\nname = \"api\"\nversion = \"2024.3.0\"\n",
+        );
+        write_file(
+            &dir.path().join("web/package.json"),
+            r#"{ "name": "web", "version": "1.0.0", "odometer": { "scheme": "semver" } }"#,
+        );
+        write_file(
+            &dir.path().join("lib/Cargo.toml"),
+            "[package]\nname = \"lib\"\nversion = \"1.0.0\"\n",
+        );
+        write_file(
+            &dir.path().join(config::CONFIG_FILE),
+            "scheme = \"calver:YY.MM.N\"\n\n[packages.lib]\nscheme = \"semver\"\n\n[packages.api]\nscheme = \"calver\"\n",
+        );
+
+        let config = config::load(dir.path()).unwrap();
+        let members = discover_members(dir.path(), &IgnoreOptions::default()).unwrap();
+        let schemes = read_schemes(&members, &config).unwrap();
+
+        // The manifest declaration and [packages.*] beat the configured default
+        assert_eq!(schemes.len(), 1);
+        assert_eq!(
+            schemes[&dir.path().join("api")].to_string(),
+            "calver:YYYY.MM.MICRO"
+        );
+    }
+
+    #[test]
+    fn test_read_groups_rejects_overlap() {
+        let dir = tempdir().unwrap();
+        write_file(
+            &dir.path().join(config::CONFIG_FILE),
+            "[groups.a]\nkind = \"fixed\"\nmembers = [\"x\", \"y\"]\n\n[groups.b]\nkind = \"linked\"\nmembers = [\"y\"]\n",
+        );
+
+        let config = config::load(dir.path()).unwrap();
+        let err = read_groups(&config).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Package 'y' is listed in both group 'a' and group 'b'"
+        );
+    }
+
+    #[test]
+    fn test_discover_members_exclude() {
+        let dir = tempdir().unwrap();
+        for name in ["app", "examples/demo"] {
+            fs::create_dir_all(dir.path().join(name)).unwrap();
+            write_file(
+                &dir.path().join(name).join("package.json"),
+                &format!(r#"{{ "name": "{}", "version": "1.0.0" }}"#, name),
+            );
+        }
+
+        let ignore_options = IgnoreOptions {
+            exclude: vec!["examples/*".to_string()],
+            ..Default::default()
+        };
+        let members = discover_members(dir.path(), &ignore_options).unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].name(), "app");
+    }
 }
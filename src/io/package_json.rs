@@ -2,15 +2,23 @@ use anyhow::{Context, Result};
 use serde_json::Value;
 use std::{fs, path::Path};
 
-use crate::domain::VersionField;
+use super::json_edit;
+use crate::domain::{Dependency, VersionField};
+
+/// Dependency maps that may reference other workspace packages
+const DEPENDENCY_TABLES: [&str; 4] = [
+    "dependencies",
+    "devDependencies",
+    "peerDependencies",
+    "optionalDependencies",
+];
 
 /// Parse a package.json file and return (name, version, has_workspace_inheritance)
 pub fn parse(path: &Path) -> Result<(Option<String>, VersionField)> {
     let content = fs::read_to_string(path). //-
         with_context(|| format!("Failed to read {}", path.display()))?;
 
-    let value: Value = serde_json::from_str(&content)
-        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    let value = parse_value(path, &content)?;
 
     let name = value
         .get("name")
@@ -36,35 +44,146 @@ pub fn parse(path: &Path) -> Result<(Option<String>, VersionField)> {
     Ok((name, version))
 }
 
-/// Update the version in a package.json file
-pub fn update_version(path: &Path, new_version: &VersionField) -> Result<()> {
+/// Parse the versioning scheme from the `odometer` key, if any
+pub fn parse_scheme(path: &Path) -> Result<Option<String>> {
+    let content = fs::read_to_string(path). //-
+        with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let value = parse_value(path, &content)?;
+
+    Ok(value
+        .get("odometer")
+        .and_then(|o| o.get("scheme"))
+        .and_then(|s| s.as_str())
+        .map(|s| s.to_string()))
+}
+
+/// Check whether a package.json file is a workspace root (has a `workspaces` field)
+pub fn is_workspace_root(path: &Path) -> Result<bool> {
+    let content = fs::read_to_string(path). //-
+        with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let value = parse_value(path, &content)?;
+
+    Ok(value.get("workspaces").is_some())
+}
+
+/// Parse the `workspaces` patterns of a package.json file
+///
+/// Accepts both the npm array form and the Yarn `{ "packages": [...] }` form.
+/// Returns `None` when the manifest does not declare workspaces.
+pub fn parse_workspaces(path: &Path) -> Result<Option<Vec<String>>> {
+    let content = fs::read_to_string(path). //-
+        with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let value = parse_value(path, &content)?;
+
+    let patterns = value.get("workspaces").and_then(|w| {
+        w.as_array()
+            .or_else(|| w.get("packages").and_then(|p| p.as_array()))
+    });
+
+    Ok(patterns.map(|patterns| {
+        patterns
+            .iter()
+            .filter_map(|p| p.as_str().map(|s| s.to_string()))
+            .collect()
+    }))
+}
+
+/// Render the contents of a package.json file with its version updated
+///
+/// Only the `version` string is rewritten in the original text, so indentation,
+/// key order, line endings, the trailing newline and any BOM are preserved. A
+/// missing `version` is added after `name`.
+pub fn render_version(path: &Path, content: &str, new_version: &VersionField) -> Result<String> {
     let new_version = match new_version {
         VersionField::Concrete(version) => version,
-        _ => return Ok(()),
+        _ => return Ok(content.to_string()),
+    };
+
+    let value = parse_value(path, content)?;
+    if value.get("version").and_then(|v| v.as_str()) == Some(new_version) {
+        return Ok(content.to_string());
+    }
+
+    let updated = match json_edit::set_string(content, &["version"], new_version)? {
+        Some(updated) => Some(updated),
+        None => json_edit::insert_string(content, &[], "version", new_version, Some("name"))?,
     };
 
+    updated.ok_or_else(|| anyhow::anyhow!("{} is not a JSON object", path.display()))
+}
+
+/// Parse the dependency maps of a package.json file
+pub fn parse_dependencies(path: &Path) -> Result<Vec<Dependency>> {
     let content = fs::read_to_string(path). //-
         with_context(|| format!("Failed to read {}", path.display()))?;
 
-    let mut value: Value = serde_json::from_str(&content)
-        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    let value = parse_value(path, &content)?;
+
+    let mut dependencies = Vec::new();
+    for table in DEPENDENCY_TABLES {
+        if let Some(entries) = value.get(table).and_then(|t| t.as_object()) {
+            for (name, requirement) in entries {
+                dependencies.push(Dependency {
+                    name: name.clone(),
+                    requirement: requirement.as_str().map(|r| r.to_string()),
+                    table: table.to_string(),
+                });
+            }
+        }
+    }
 
-    // Update the version field directly
-    value["version"] = Value::String(new_version.to_string());
+    Ok(dependencies)
+}
 
-    let updated_content = serde_json::to_string_pretty(&value)
-        .with_context(|| format!("Failed to serialize {}", path.display()))?;
+/// Render the contents of a package.json file with its dependency requirements updated
+///
+/// Like `render_version`, only the changed requirement strings are rewritten.
+/// Returns `content` unchanged when no requirement differs.
+pub fn render_dependencies(
+    path: &Path,
+    content: &str,
+    dependencies: &[Dependency],
+) -> Result<String> {
+    let value = parse_value(path, content)?;
+
+    let mut updated = content.to_string();
+    for dependency in dependencies {
+        let requirement = match &dependency.requirement {
+            Some(requirement) => requirement,
+            None => continue,
+        };
+
+        let current = value
+            .get(&dependency.table)
+            .and_then(|t| t.get(&dependency.name));
+        if current.is_none() || current.and_then(|c| c.as_str()) == Some(requirement) {
+            continue;
+        }
 
-    fs::write(path, updated_content)
-        .with_context(|| format!("Failed to write {}", path.display()))?;
+        if let Some(edited) = json_edit::set_string(
+            &updated,
+            &[&dependency.table, &dependency.name],
+            requirement,
+        )? {
+            updated = edited;
+        }
+    }
 
-    Ok(())
+    Ok(updated)
+}
+
+/// Parse package.json contents, tolerating a leading BOM
+fn parse_value(path: &Path, content: &str) -> Result<Value> {
+    serde_json::from_str(content.strip_prefix('\u{feff}').unwrap_or(content))
+        .with_context(|| format!("Failed to parse {}", path.display()))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -157,45 +276,42 @@ mod tests {
     }
 
     #[test]
-    fn test_update_version_basic() {
+    fn test_render_version_basic() {
         let json = r#"{
             "name": "my-package",
             "version": "1.2.3"
         }"#;
         let file = write_temp_json(json);
         let new_version = VersionField::Concrete("2.0.0".to_string());
-        update_version(file.path(), &new_version).unwrap();
-        let content = fs::read_to_string(file.path()).unwrap();
+        let content = render_version(file.path(), json, &new_version).unwrap();
         assert!(content.contains("\"version\": \"2.0.0\""));
     }
 
     #[test]
-    fn test_update_version_adds_if_missing() {
+    fn test_render_version_adds_if_missing() {
         let json = r#"{
             "name": "my-package"
         }"#;
         let file = write_temp_json(json);
         let new_version = VersionField::Concrete("2.0.0".to_string());
-        update_version(file.path(), &new_version).unwrap();
-        let content = fs::read_to_string(file.path()).unwrap();
+        let content = render_version(file.path(), json, &new_version).unwrap();
         assert!(content.contains("\"version\": \"2.0.0\""));
     }
 
     #[test]
-    fn test_update_version_workspace_inheritance() {
+    fn test_render_version_workspace_inheritance() {
         let json = r#"{
             "name": "workspace-package",
             "version": "workspace:*"
         }"#;
         let file = write_temp_json(json);
         let new_version = VersionField::Concrete("2.0.0".to_string());
-        update_version(file.path(), &new_version).unwrap();
-        let content = fs::read_to_string(file.path()).unwrap();
+        let content = render_version(file.path(), json, &new_version).unwrap();
         assert!(content.contains("\"version\": \"2.0.0\""));
     }
 
     #[test]
-    fn test_update_version_preserves_other_fields() {
+    fn test_render_version_preserves_other_fields() {
         let json = r#"{
             "name": "my-package",
             "version": "1.2.3",
@@ -206,8 +322,7 @@ mod tests {
         }"#;
         let file = write_temp_json(json);
         let new_version = VersionField::Concrete("2.0.0".to_string());
-        update_version(file.path(), &new_version).unwrap();
-        let content = fs::read_to_string(file.path()).unwrap();
+        let content = render_version(file.path(), json, &new_version).unwrap();
 
         // Check that version was updated
         assert!(content.contains("\"version\": \"2.0.0\""));
@@ -306,4 +421,164 @@ mod tests {
         assert_eq!(name, Some("my-package".to_string()));
         assert_eq!(version, VersionField::Concrete("".to_string()));
     }
+
+    #[test]
+    fn test_is_workspace_root() {
+        let file = write_temp_json(r#"{ "name": "root", "workspaces": ["packages/*"] }"#);
+        assert!(is_workspace_root(file.path()).unwrap());
+
+        let file = write_temp_json(r#"{ "name": "member", "version": "1.0.0" }"#);
+        assert!(!is_workspace_root(file.path()).unwrap());
+    }
+
+    #[test]
+    fn test_parse_workspaces() {
+        let file = write_temp_json(r#"{ "workspaces": ["packages/*", "!packages/private"] }"#);
+        assert_eq!(
+            parse_workspaces(file.path()).unwrap(),
+            Some(vec![
+                "packages/*".to_string(),
+                "!packages/private".to_string()
+            ])
+        );
+
+        let file = write_temp_json(r#"{ "workspaces": { "packages": ["apps/*"] } }"#);
+        assert_eq!(
+            parse_workspaces(file.path()).unwrap(),
+            Some(vec!["apps/*".to_string()])
+        );
+
+        let file = write_temp_json(r#"{ "name": "solo" }"#);
+        assert_eq!(parse_workspaces(file.path()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_dependencies() {
+        let json = r#"{
+            "name": "app",
+            "version": "1.0.0",
+            "dependencies": {
+                "core": "^1.2.0",
+                "lodash": "^4.17.0"
+            },
+            "devDependencies": {
+                "testkit": "workspace:*"
+            },
+            "peerDependencies": {
+                "react": ">=18"
+            }
+        }"#;
+        let file = write_temp_json(json);
+        let dependencies = parse_dependencies(file.path()).unwrap();
+        assert_eq!(dependencies.len(), 4);
+        assert!(dependencies.contains(&Dependency {
+            name: "core".to_string(),
+            requirement: Some("^1.2.0".to_string()),
+            table: "dependencies".to_string(),
+        }));
+        assert!(dependencies.contains(&Dependency {
+            name: "testkit".to_string(),
+            requirement: Some("workspace:*".to_string()),
+            table: "devDependencies".to_string(),
+        }));
+        assert!(dependencies
+            .iter()
+            .any(|d| d.name == "react" && d.table == "peerDependencies"));
+    }
+
+    #[test]
+    fn test_render_dependencies() {
+        let json = r#"{
+            "name": "app",
+            "dependencies": {
+                "core": "^1.2.0",
+                "lodash": "^4.17.0"
+            },
+            "peerDependencies": {
+                "core": "~1.2.0"
+            }
+        }"#;
+        let dependencies = vec![
+            Dependency {
+                name: "core".to_string(),
+                requirement: Some("^2.0.0".to_string()),
+                table: "dependencies".to_string(),
+            },
+            Dependency {
+                name: "core".to_string(),
+                requirement: Some("~2.0.0".to_string()),
+                table: "peerDependencies".to_string(),
+            },
+        ];
+        let rendered = render_dependencies(Path::new("package.json"), json, &dependencies).unwrap();
+
+        let file = write_temp_json(&rendered);
+        let updated = parse_dependencies(file.path()).unwrap();
+        let find = |table: &str, name: &str| {
+            updated
+                .iter()
+                .find(|d| d.table == table && d.name == name)
+                .and_then(|d| d.requirement.clone())
+        };
+        assert_eq!(find("dependencies", "core"), Some("^2.0.0".to_string()));
+        assert_eq!(find("peerDependencies", "core"), Some("~2.0.0".to_string()));
+        assert_eq!(find("dependencies", "lodash"), Some("^4.17.0".to_string()));
+    }
+
+    #[test]
+    fn test_render_dependencies_unchanged() {
+        let json = r#"{ "name": "app", "dependencies": { "core": "^1.2.0" } }"#;
+        let dependencies = vec![Dependency {
+            name: "core".to_string(),
+            requirement: Some("^1.2.0".to_string()),
+            table: "dependencies".to_string(),
+        }];
+        let rendered = render_dependencies(Path::new("package.json"), json, &dependencies).unwrap();
+        assert_eq!(rendered, json);
+    }
+
+    #[test]
+    fn test_render_version_unchanged_keeps_formatting() {
+        let json = "{\n    \"name\": \"app\",\n    \"version\": \"1.2.0\"\n}\n";
+        let new_version = VersionField::Concrete("1.2.0".to_string());
+        let rendered = render_version(Path::new("package.json"), json, &new_version).unwrap();
+        assert_eq!(rendered, json);
+    }
+
+    #[test]
+    fn test_render_version_preserves_formatting() {
+        let json = "\u{feff}{\r\n\t\"name\": \"app\",\r\n\t\"version\":  \"1.2.0\",\r\n\t\"description\": \"caf\\u00e9\"\r\n}";
+        let new_version = VersionField::Concrete("1.3.0".to_string());
+        let content = render_version(Path::new("package.json"), json, &new_version).unwrap();
+        assert_eq!(content, json.replace("1.2.0", "1.3.0"));
+
+        let file = write_temp_json(&content);
+        let (_, version) = parse(file.path()).unwrap();
+        assert_eq!(version, new_version);
+    }
+
+    #[test]
+    fn test_render_version_adds_after_name() {
+        let json = "{\n    \"name\": \"app\",\n    \"private\": true\n}\n";
+        let file = write_temp_json(json);
+        let new_version = VersionField::Concrete("1.0.0".to_string());
+        let content = render_version(file.path(), json, &new_version).unwrap();
+        assert_eq!(
+            content,
+            "{\n    \"name\": \"app\",\n    \"version\": \"1.0.0\",\n    \"private\": true\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_render_dependencies_preserves_formatting() {
+        let json = "{\n    \"name\": \"app\",\n    \"dependencies\": {\n        \"core\": \"^1.2.0\",\n        \"lodash\": \"^4.17.0\"\n    }\n}\n";
+        let file = write_temp_json(json);
+        let dependencies = vec![Dependency {
+            name: "core".to_string(),
+            requirement: Some("^2.0.0".to_string()),
+            table: "dependencies".to_string(),
+        }];
+        let content = render_dependencies(file.path(), json, &dependencies).unwrap();
+        assert_eq!(content, json.replace("^1.2.0", "^2.0.0"));
+    }
 }
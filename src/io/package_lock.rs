@@ -0,0 +1,182 @@
+use super::json_edit;
+use crate::domain::{VersionField, WorkspaceMember};
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::path::Path;
+
+/// Name of the npm lockfile beside a workspace root
+pub const LOCKFILE: &str = "package-lock.json";
+
+/// Render the contents of a package-lock.json file with workspace package entries updated
+///
+/// Workspace packages are recorded under `packages`, keyed by their directory
+/// relative to `lock_dir` (`""` for the root package, whose version is also
+/// repeated at the top level). Their `version` and the requirements in their
+/// dependency maps on other workspace packages are set to match `members`;
+/// requirements on registry packages are left to npm. Only the changed strings are
+/// rewritten. Returns `content` unchanged when nothing differs; `path` is only
+/// used for error messages.
+pub fn render(
+    path: &Path,
+    content: &str,
+    lock_dir: &Path,
+    members: &[&WorkspaceMember],
+) -> Result<String> {
+    let lock: Value = serde_json::from_str(content.strip_prefix('\u{feff}').unwrap_or(content))
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    let mut updated = content.to_string();
+    let mut set = |keys: &[&str], current: Option<&Value>, new: &str| -> Result<()> {
+        if current.and_then(|c| c.as_str()).is_some_and(|c| c != new) {
+            if let Some(edited) = json_edit::set_string(&updated, keys, new)? {
+                updated = edited;
+            }
+        }
+        Ok(())
+    };
+
+    for member in members {
+        let key = match package_key(lock_dir, member.path()) {
+            Some(key) => key,
+            None => continue,
+        };
+        let entry = match lock.get("packages").and_then(|p| p.get(&key)) {
+            Some(entry) => entry,
+            None => continue,
+        };
+
+        if let VersionField::Concrete(version) = member.version() {
+            set(
+                &["packages", &key, "version"],
+                entry.get("version"),
+                version,
+            )?;
+            if key.is_empty() {
+                set(&["version"], lock.get("version"), version)?;
+            }
+        }
+
+        for dependency in member.dependencies() {
+            if !members.iter().any(|m| m.name() == dependency.name) {
+                continue;
+            }
+            let requirement = match &dependency.requirement {
+                Some(requirement) => requirement,
+                None => continue,
+            };
+            let current = entry
+                .get(&dependency.table)
+                .and_then(|t| t.get(&dependency.name));
+            set(
+                &["packages", &key, &dependency.table, &dependency.name],
+                current,
+                requirement,
+            )?;
+        }
+    }
+
+    Ok(updated)
+}
+
+/// The `packages` key of the package in `dir`: its path relative to `lock_dir`, with `/` separators
+fn package_key(lock_dir: &Path, dir: &Path) -> Option<String> {
+    let relative = dir.strip_prefix(lock_dir).ok()?;
+    Some(
+        relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Dependency;
+    use std::path::PathBuf;
+
+    const LOCK: &str = r#"{
+  "name": "root",
+  "version": "1.0.0",
+  "lockfileVersion": 3,
+  "requires": true,
+  "packages": {
+    "": {
+      "name": "root",
+      "version": "1.0.0",
+      "workspaces": ["packages/*"]
+    },
+    "node_modules/ui": {
+      "resolved": "packages/ui",
+      "link": true
+    },
+    "packages/app": {
+      "version": "0.1.0",
+      "dependencies": {
+        "left-pad": "^1.3.0",
+        "ui": "^1.0.0"
+      }
+    },
+    "packages/ui": {
+      "version": "1.0.0"
+    }
+  }
+}
+"#;
+
+    fn node(path: &str, version: &str, dependencies: Vec<Dependency>) -> WorkspaceMember {
+        WorkspaceMember::Node {
+            name: path.rsplit('/').next().unwrap().to_string(),
+            path: PathBuf::from(path),
+            version: VersionField::Concrete(version.to_string()),
+            dependencies,
+        }
+    }
+
+    #[test]
+    fn test_render_updates_workspace_entries() {
+        let root = node("/ws", "2.0.0", vec![]);
+        let app = node(
+            "/ws/packages/app",
+            "0.1.0",
+            vec![
+                Dependency {
+                    name: "left-pad".to_string(),
+                    requirement: Some("^1.4.0".to_string()),
+                    table: "dependencies".to_string(),
+                },
+                Dependency {
+                    name: "ui".to_string(),
+                    requirement: Some("^2.0.0".to_string()),
+                    table: "dependencies".to_string(),
+                },
+            ],
+        );
+        let ui = node("/ws/packages/ui", "2.0.0", vec![]);
+        let outside = node("/elsewhere/ui", "3.0.0", vec![]);
+
+        let updated = render(
+            Path::new("package-lock.json"),
+            LOCK,
+            Path::new("/ws"),
+            &[&root, &app, &ui, &outside],
+        )
+        .unwrap();
+        // The registry dependency's drifted requirement is left for npm to report
+        assert_eq!(
+            updated,
+            LOCK.replace("\"version\": \"1.0.0\"", "\"version\": \"2.0.0\"")
+                .replace("\"ui\": \"^1.0.0\"", "\"ui\": \"^2.0.0\"")
+        );
+
+        let unchanged = render(
+            Path::new("package-lock.json"),
+            &updated,
+            Path::new("/ws"),
+            &[&root, &app, &ui],
+        )
+        .unwrap();
+        assert_eq!(unchanged, updated);
+    }
+}
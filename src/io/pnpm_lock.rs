@@ -0,0 +1,290 @@
+use crate::domain::WorkspaceMember;
+use std::path::Path;
+
+/// Name of the pnpm lockfile beside a workspace root
+pub const LOCKFILE: &str = "pnpm-lock.yaml";
+
+/// Dependency sections of an importer that record specifiers
+const DEPENDENCY_SECTIONS: [&str; 3] = ["dependencies", "devDependencies", "optionalDependencies"];
+
+/// Render the contents of a pnpm-lock.yaml file with workspace specifiers updated
+///
+/// pnpm doesn't record the versions of workspace packages, but each importer
+/// (a workspace package, keyed by its directory relative to `lock_dir`, `.`
+/// for the root) records the requirement its package.json declares for every
+/// dependency. The specifiers of dependencies on other workspace packages are
+/// set to match `members` (those of registry packages are left to pnpm), in both the
+/// lockfile v6+ layout (`specifier:` under each dependency) and the older
+/// `specifiers:` map. Only the edited lines change.
+pub fn render(content: &str, lock_dir: &Path, members: &[&WorkspaceMember]) -> String {
+    let mut importer: Option<&WorkspaceMember> = None;
+    let mut in_importers = false;
+    let mut section = "";
+    let mut dependency = String::new();
+
+    let mut updated = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        let text = line.trim_end_matches(['\r', '\n']);
+        let ending = &line[text.len()..];
+        let indent = text.len() - text.trim_start().len();
+        let trimmed = text.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            updated.push_str(line);
+            continue;
+        }
+
+        let mut replacement = None;
+        match indent {
+            0 => {
+                in_importers = trimmed == "importers:";
+                importer = None;
+                section = "";
+            }
+            2 if in_importers => {
+                let key = unquote(trimmed.trim_end_matches(':'));
+                importer = members
+                    .iter()
+                    .copied()
+                    .find(|m| importer_key(lock_dir, m.path()).as_deref() == Some(key.as_str()));
+                section = "";
+            }
+            4 if importer.is_some() => {
+                section = DEPENDENCY_SECTIONS
+                    .into_iter()
+                    .chain(["specifiers"])
+                    .find(|s| trimmed == format!("{}:", s))
+                    .unwrap_or("");
+            }
+            6 if !section.is_empty() => {
+                if let Some((key, value)) = split_entry(trimmed) {
+                    dependency = key;
+                    if section == "specifiers" {
+                        replacement = importer
+                            .and_then(|m| requirement(members, m, None, &dependency))
+                            .map(|new| (value, new));
+                    }
+                }
+            }
+            8 if section != "specifiers" && !section.is_empty() => {
+                if let Some((key, value)) = split_entry(trimmed) {
+                    if key == "specifier" {
+                        replacement = importer
+                            .and_then(|m| requirement(members, m, Some(section), &dependency))
+                            .map(|new| (value, new));
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        match replacement {
+            Some((value, new)) if unquote(value) != new => {
+                // `value` borrows from `text`, so its offset locates it in the line
+                let start = value.as_ptr() as usize - text.as_ptr() as usize;
+                updated.push_str(&text[..start]);
+                updated.push_str(&quote_like(value, new));
+                updated.push_str(&text[start + value.len()..]);
+                updated.push_str(ending);
+            }
+            _ => updated.push_str(line),
+        }
+    }
+    updated
+}
+
+/// The importer key of the package in `dir`: its path relative to `lock_dir`, or `.`
+fn importer_key(lock_dir: &Path, dir: &Path) -> Option<String> {
+    let relative = dir.strip_prefix(lock_dir).ok()?;
+    let key = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    Some(if key.is_empty() { ".".to_string() } else { key })
+}
+
+/// The requirement `member` declares for the workspace package `name`, in `table` when given
+fn requirement<'a>(
+    members: &[&WorkspaceMember],
+    member: &'a WorkspaceMember,
+    table: Option<&str>,
+    name: &str,
+) -> Option<&'a str> {
+    if !members.iter().any(|m| m.name() == name) {
+        return None;
+    }
+    member
+        .dependencies()
+        .iter()
+        .find(|d| d.name == name && table.is_none_or(|table| d.table == table))
+        .and_then(|d| d.requirement.as_deref())
+}
+
+/// Split `key: value` into the unquoted key and the raw value (without comment)
+fn split_entry(line: &str) -> Option<(String, &str)> {
+    let line = &line[..line.len() - trailing_comment(line).len()];
+    let (key, value) = if let Some(rest) = line.strip_prefix('\'') {
+        let end = rest.find('\'')?;
+        (&rest[..end], rest[end + 1..].strip_prefix(':')?)
+    } else if let Some(rest) = line.strip_prefix('"') {
+        let end = rest.find('"')?;
+        (&rest[..end], rest[end + 1..].strip_prefix(':')?)
+    } else {
+        let (key, value) = line
+            .split_once(": ")
+            .or_else(|| line.strip_suffix(':').map(|key| (key, &line[line.len()..])))?;
+        (key, value)
+    };
+    Some((key.to_string(), value.trim()))
+}
+
+/// The ` # comment` at the end of `line`, outside quotes, including leading whitespace
+fn trailing_comment(line: &str) -> &str {
+    let mut quote = None;
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '\'' | '"') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '#') if i > 0 && line[..i].ends_with(char::is_whitespace) => {
+                let start = line[..i].trim_end().len();
+                return &line[start..];
+            }
+            _ => {}
+        }
+    }
+    ""
+}
+
+/// Write `new` with the quoting of `old`, quoting plain values YAML would misread
+fn quote_like(old: &str, new: &str) -> String {
+    if old.starts_with('"') {
+        return format!("\"{}\"", new.replace('\\', "\\\\").replace('"', "\\\""));
+    }
+    let needs_quotes = new.is_empty()
+        || new.starts_with([
+            '>', '|', '*', '&', '!', '%', '@', '`', '\'', '"', '{', '[', ',', '#', '?', ':', '-',
+        ])
+        || new.contains(": ")
+        || new.contains(" #");
+    if old.starts_with('\'') || needs_quotes {
+        format!("'{}'", new.replace('\'', "''"))
+    } else {
+        new.to_string()
+    }
+}
+
+fn unquote(value: &str) -> String {
+    if let Some(inner) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+        return inner.replace("''", "'");
+    }
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Dependency, VersionField};
+    use std::path::PathBuf;
+
+    fn node(path: &str, dependencies: &[(&str, &str, &str)]) -> WorkspaceMember {
+        WorkspaceMember::Node {
+            name: path.rsplit('/').next().unwrap().to_string(),
+            path: PathBuf::from(path),
+            version: VersionField::Concrete("1.0.0".to_string()),
+            dependencies: dependencies
+                .iter()
+                .map(|(table, name, requirement)| Dependency {
+                    name: name.to_string(),
+                    requirement: Some(requirement.to_string()),
+                    table: table.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_render_v9_specifiers() {
+        let lock = "lockfileVersion: '9.0'
+
+importers:
+
+  .:
+    devDependencies:
+      typescript:
+        specifier: ^5.0.0
+        version: 5.4.5
+
+  packages/app:
+    dependencies:
+      '@acme/ui':
+        specifier: ^1.0.0 # pinned
+        version: link:../ui
+      left-pad:
+        specifier: ^1.3.0
+        version: 1.3.0
+
+  packages/ui: {}
+
+packages:
+
+  left-pad@1.3.0:
+    resolution: {integrity: sha512-abc}
+";
+        let root = node("/ws", &[("devDependencies", "typescript", "^5.0.0")]);
+        let app = node(
+            "/ws/packages/app",
+            &[
+                ("dependencies", "@acme/ui", ">=2.0.0"),
+                ("dependencies", "left-pad", "^1.4.0"),
+            ],
+        );
+        let ui = WorkspaceMember::Node {
+            name: "@acme/ui".to_string(),
+            path: PathBuf::from("/ws/packages/ui"),
+            version: VersionField::Concrete("1.0.0".to_string()),
+            dependencies: Vec::new(),
+        };
+
+        // Only the workspace dependency's specifier follows package.json
+        let updated = render(lock, Path::new("/ws"), &[&root, &app, &ui]);
+        assert_eq!(
+            updated,
+            lock.replace(
+                "specifier: ^1.0.0 # pinned",
+                "specifier: '>=2.0.0' # pinned"
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_v5_specifiers() {
+        let lock = "lockfileVersion: 5.4\r
+\r
+importers:\r
+\r
+  packages/app:\r
+    specifiers:\r
+      ui: workspace:^1.0.0\r
+    dependencies:\r
+      ui: link:../ui\r
+";
+        let app = node(
+            "/ws/packages/app",
+            &[("dependencies", "ui", "workspace:^2.0.0")],
+        );
+
+        assert_eq!(
+            render(
+                lock,
+                Path::new("/ws"),
+                &[&app, &node("/ws/packages/ui", &[])]
+            ),
+            lock.replace("ui: workspace:^1.0.0", "ui: workspace:^2.0.0")
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use anyhow::{Context, Result};
+use std::{fs, path::Path};
+
+/// Parse the `packages` list of a pnpm-workspace.yaml file
+///
+/// Only the subset of YAML pnpm documents for this key is supported: a block
+/// sequence (`- 'packages/*'`) or a flow sequence (`['packages/*']`) of plain,
+/// single- or double-quoted strings.
+pub fn parse_packages(path: &Path) -> Result<Vec<String>> {
+    let content = fs::read_to_string(path). //-
+        with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let mut lines = content.lines();
+    let mut packages = Vec::new();
+
+    while let Some(line) = lines.next() {
+        let rest = match line.strip_prefix("packages:") {
+            Some(rest) => strip_comment(rest).trim(),
+            None => continue,
+        };
+
+        if let Some(flow) = rest.strip_prefix('[') {
+            let flow = flow.strip_suffix(']').ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Failed to parse {}: unterminated packages list",
+                    path.display()
+                )
+            })?;
+            packages.extend(
+                flow.split(',')
+                    .map(|item| unquote(item.trim()))
+                    .filter(|item| !item.is_empty()),
+            );
+            return Ok(packages);
+        }
+
+        for item_line in lines.by_ref() {
+            let trimmed = item_line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            if !item_line.starts_with([' ', '\t', '-']) {
+                break;
+            }
+            match trimmed.strip_prefix('-') {
+                Some(item) => packages.push(unquote(strip_comment(item).trim())),
+                None => anyhow::bail!(
+                    "Failed to parse {}: unexpected line in packages list: {}",
+                    path.display(),
+                    trimmed
+                ),
+            }
+        }
+        return Ok(packages);
+    }
+
+    Ok(packages)
+}
+
+/// Remove a trailing ` # comment` that is not inside quotes
+fn strip_comment(value: &str) -> &str {
+    let mut quote = None;
+    for (i, c) in value.char_indices() {
+        match (quote, c) {
+            (None, '\'' | '"') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '#') if i == 0 || value[..i].ends_with(char::is_whitespace) => {
+                return &value[..i];
+            }
+            _ => {}
+        }
+    }
+    value
+}
+
+fn unquote(value: &str) -> String {
+    value
+        .strip_prefix('\'')
+        .and_then(|v| v.strip_suffix('\''))
+        .or_else(|| value.strip_prefix('"').and_then(|v| v.strip_suffix('"')))
+        .unwrap_or(value)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn write_temp_yaml(contents: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "{}", contents).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_parse_block_sequence() {
+        let yaml = r#"packages:
+  # all packages in direct subdirs of packages/
+  - 'packages/*'
+  - "apps/**"
+  - tools # inline comment
+  - '!**/test/**'
+
+catalog:
+  react: ^18.2.0
+"#;
+        let file = write_temp_yaml(yaml);
+        assert_eq!(
+            parse_packages(file.path()).unwrap(),
+            vec!["packages/*", "apps/**", "tools", "!**/test/**"]
+        );
+    }
+
+    #[test]
+    fn test_parse_flow_sequence() {
+        let file = write_temp_yaml("packages: ['packages/*', \"apps/*\"]\n");
+        assert_eq!(
+            parse_packages(file.path()).unwrap(),
+            vec!["packages/*", "apps/*"]
+        );
+    }
+
+    #[test]
+    fn test_parse_unindented_block_sequence() {
+        let file = write_temp_yaml("packages:\n- packages/*\n- apps/*\n");
+        assert_eq!(
+            parse_packages(file.path()).unwrap(),
+            vec!["packages/*", "apps/*"]
+        );
+    }
+
+    #[test]
+    fn test_parse_missing_packages() {
+        let file = write_temp_yaml("catalog:\n  react: ^18.2.0\n");
+        assert!(parse_packages(file.path()).unwrap().is_empty());
+    }
+}
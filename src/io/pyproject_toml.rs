@@ -0,0 +1,308 @@
+use anyhow::{Context, Result};
+use std::{fs, path::Path};
+use toml_edit::{DocumentMut, Formatted, Item, Value};
+
+use crate::domain::VersionField;
+
+/// Parse a pyproject.toml file and return (name, version)
+///
+/// Both PEP 621 (`[project]`) and Poetry (`[tool.poetry]`) metadata are supported.
+/// A PEP 621 project that lists `version` in `dynamic` is reported as
+/// `VersionField::Dynamic`, unless Poetry supplies the concrete version.
+pub fn parse(path: &Path) -> Result<(Option<String>, VersionField)> {
+    let content = fs::read_to_string(path). //-
+        with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    let project = doc.get("project");
+    let poetry = doc.get("tool").and_then(|t| t.get("poetry"));
+
+    let name = project
+        .and_then(|p| p.get("name"))
+        .or_else(|| poetry.and_then(|p| p.get("name")))
+        .and_then(|n| n.as_str())
+        .map(|s| s.to_string());
+
+    let version = match version_section(&doc) {
+        Some(section) => match section.get("version") {
+            None => VersionField::Absent,
+            Some(v) => v
+                .as_str()
+                .map(|s| VersionField::Concrete(s.to_string()))
+                .ok_or_else(|| anyhow::anyhow!("Version field must be a string"))?,
+        },
+        None if has_dynamic_version(&doc) => VersionField::Dynamic,
+        None => VersionField::Absent,
+    };
+
+    Ok((name, version))
+}
+
+/// Parse the versioning scheme from `[tool.odometer]`, if any
+pub fn parse_scheme(path: &Path) -> Result<Option<String>> {
+    let content = fs::read_to_string(path). //-
+        with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    Ok(doc
+        .get("tool")
+        .and_then(|t| t.get("odometer"))
+        .and_then(|o| o.get("scheme"))
+        .and_then(|s| s.as_str())
+        .map(|s| s.to_string()))
+}
+
+/// Render the contents of a pyproject.toml file with its version updated
+///
+/// The version is written to `[project].version`, or to `[tool.poetry].version`
+/// for Poetry projects. Projects whose version is dynamic are left untouched.
+/// `path` is only used for error messages.
+pub fn render_version(path: &Path, content: &str, new_version: &VersionField) -> Result<String> {
+    let new_version = match new_version {
+        VersionField::Concrete(version) => version,
+        _ => return Ok(content.to_string()),
+    };
+
+    let mut doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    let section = match version_section_key(&doc) {
+        Some(SectionKey::Project) => doc.get_mut("project"),
+        Some(SectionKey::Poetry) => doc.get_mut("tool").and_then(|t| t.get_mut("poetry")),
+        None if has_dynamic_version(&doc) => return Ok(content.to_string()),
+        None => None,
+    }
+    .ok_or_else(|| {
+        anyhow::anyhow!(
+            "No [project] or [tool.poetry] section found in {}",
+            path.display()
+        )
+    })?;
+
+    // Get the existing decor (comments) from the version field
+    let decor = section
+        .get("version")
+        .and_then(|v| v.as_value())
+        .map(|v| v.decor().clone());
+
+    // Create new value with the same decor
+    let mut new_value = Value::String(Formatted::new(new_version.to_string()));
+    if let Some(d) = decor {
+        if let Some(prefix_str) = d.prefix().and_then(|p| p.as_str()) {
+            new_value.decor_mut().set_prefix(prefix_str.to_string());
+        }
+        if let Some(suffix_str) = d.suffix().and_then(|s| s.as_str()) {
+            new_value.decor_mut().set_suffix(suffix_str.to_string());
+        }
+    }
+
+    section["version"] = Item::Value(new_value);
+
+    Ok(doc.to_string())
+}
+
+enum SectionKey {
+    Project,
+    Poetry,
+}
+
+/// Get the section that owns the version field, if any
+fn version_section(doc: &DocumentMut) -> Option<&Item> {
+    match version_section_key(doc)? {
+        SectionKey::Project => doc.get("project"),
+        SectionKey::Poetry => doc.get("tool").and_then(|t| t.get("poetry")),
+    }
+}
+
+/// Decide which section owns the version field
+///
+/// PEP 621 metadata wins unless it declares the version as dynamic, in which case
+/// Poetry (if present) is the source of truth, as it is for Poetry 2 projects.
+fn version_section_key(doc: &DocumentMut) -> Option<SectionKey> {
+    let has_project = doc.get("project").is_some();
+    let has_poetry = doc.get("tool").and_then(|t| t.get("poetry")).is_some();
+
+    if has_project && !has_dynamic_version(doc) {
+        Some(SectionKey::Project)
+    } else if has_poetry {
+        Some(SectionKey::Poetry)
+    } else {
+        None
+    }
+}
+
+/// Check if `[project].dynamic` lists `version`
+fn has_dynamic_version(doc: &DocumentMut) -> bool {
+    doc.get("project")
+        .and_then(|p| p.get("dynamic"))
+        .and_then(|d| d.as_array())
+        .map(|fields| fields.iter().any(|f| f.as_str() == Some("version")))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn write_temp_toml(contents: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "{}", contents).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_parse_pep621_project() {
+        let toml = r#"
+            [project]
+            name = "my-package"
+            version = "1.2.3"
+        "#;
+        let file = write_temp_toml(toml);
+        let (name, version) = parse(file.path()).unwrap();
+        assert_eq!(name, Some("my-package".to_string()));
+        assert_eq!(version, VersionField::Concrete("1.2.3".to_string()));
+    }
+
+    #[test]
+    fn test_parse_poetry_project() {
+        let toml = r#"
+            [tool.poetry]
+            name = "poetry-package"
+            version = "0.4.0"
+        "#;
+        let file = write_temp_toml(toml);
+        let (name, version) = parse(file.path()).unwrap();
+        assert_eq!(name, Some("poetry-package".to_string()));
+        assert_eq!(version, VersionField::Concrete("0.4.0".to_string()));
+    }
+
+    #[test]
+    fn test_parse_dynamic_version() {
+        let toml = r#"
+            [project]
+            name = "my-package"
+            dynamic = ["version", "readme"]
+
+            [tool.setuptools.dynamic]
+            version = { attr = "my_package.__version__" }
+        "#;
+        let file = write_temp_toml(toml);
+        let (name, version) = parse(file.path()).unwrap();
+        assert_eq!(name, Some("my-package".to_string()));
+        assert_eq!(version, VersionField::Dynamic);
+    }
+
+    #[test]
+    fn test_parse_dynamic_version_with_poetry() {
+        // Poetry 2 projects declare the version as dynamic and keep it in [tool.poetry]
+        let toml = r#"
+            [project]
+            name = "my-package"
+            dynamic = ["version"]
+
+            [tool.poetry]
+            version = "2.1.0"
+        "#;
+        let file = write_temp_toml(toml);
+        let (name, version) = parse(file.path()).unwrap();
+        assert_eq!(name, Some("my-package".to_string()));
+        assert_eq!(version, VersionField::Concrete("2.1.0".to_string()));
+    }
+
+    #[test]
+    fn test_parse_missing_version() {
+        let toml = r#"
+            [project]
+            name = "my-package"
+        "#;
+        let file = write_temp_toml(toml);
+        let (name, version) = parse(file.path()).unwrap();
+        assert_eq!(name, Some("my-package".to_string()));
+        assert_eq!(version, VersionField::Absent);
+    }
+
+    #[test]
+    fn test_parse_no_project_or_poetry() {
+        let toml = r#"
+            [tool.black]
+            line-length = 100
+        "#;
+        let file = write_temp_toml(toml);
+        let (name, version) = parse(file.path()).unwrap();
+        assert_eq!(name, None);
+        assert_eq!(version, VersionField::Absent);
+    }
+
+    #[test]
+    fn test_parse_invalid_toml() {
+        let file = write_temp_toml("[project\nname = \"invalid\"");
+        let result = parse(file.path());
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Failed to parse"));
+    }
+
+    #[test]
+    fn test_render_version_pep621_preserves_formatting() {
+        let toml = r#"# Project metadata
+[project]
+name = "my-package"
+version = "1.2.3"  # managed by odometer
+description = "A test package"
+"#;
+        let file = write_temp_toml(toml);
+        let new_version = VersionField::Concrete("1.3.0".to_string());
+        let content = render_version(file.path(), toml, &new_version).unwrap();
+        assert_eq!(content, toml.replace("1.2.3", "1.3.0"));
+    }
+
+    #[test]
+    fn test_render_version_poetry() {
+        let toml = r#"
+            [tool.poetry]
+            name = "poetry-package"
+            version = "0.4.0"
+        "#;
+        let file = write_temp_toml(toml);
+        let new_version = VersionField::Concrete("0.5.0".to_string());
+        let content = render_version(file.path(), toml, &new_version).unwrap();
+        assert!(content.contains("version = \"0.5.0\""));
+    }
+
+    #[test]
+    fn test_render_version_dynamic_is_noop() {
+        let toml = r#"
+            [project]
+            name = "my-package"
+            dynamic = ["version"]
+        "#;
+        let file = write_temp_toml(toml);
+        let new_version = VersionField::Concrete("2.0.0".to_string());
+        let content = render_version(file.path(), toml, &new_version).unwrap();
+        assert_eq!(content, toml);
+    }
+
+    #[test]
+    fn test_render_version_no_project_or_poetry() {
+        let toml = r#"
+            [tool.black]
+            line-length = 100
+        "#;
+        let file = write_temp_toml(toml);
+        let new_version = VersionField::Concrete("2.0.0".to_string());
+        let result = render_version(file.path(), toml, &new_version);
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("No [project] or [tool.poetry] section found"));
+    }
+}
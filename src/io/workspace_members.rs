@@ -0,0 +1,234 @@
+use anyhow::{Context, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use std::path::Path;
+
+use super::{cargo_toml, package_json, pnpm_workspace};
+use crate::domain::Ecosystem;
+
+/// Member patterns declared by the workspace manifests of a root directory
+///
+/// Ecosystems without a declaration (no `[workspace] members`, no `workspaces`
+/// field and no pnpm-workspace.yaml) are not restricted, so discovery falls
+/// back to including every manifest it finds for them.
+#[derive(Debug, Default)]
+pub struct DeclaredMembers {
+    cargo: Option<MemberPatterns>,
+    node: Option<MemberPatterns>,
+    exclude: Option<GlobSet>,
+}
+
+impl DeclaredMembers {
+    /// Read member declarations from the manifests in `root`
+    pub fn read(root: &Path) -> Result<Self> {
+        let mut declared = DeclaredMembers::default();
+
+        let cargo_manifest = root.join("Cargo.toml");
+        if cargo_manifest.is_file() {
+            if let Some((members, exclude)) = cargo_toml::parse_workspace_members(&cargo_manifest)?
+            {
+                let exclude = exclude
+                    .iter()
+                    .flat_map(|path| {
+                        let path = normalize(path);
+                        [path.to_string(), format!("{}/**", path)]
+                    })
+                    .collect::<Vec<_>>();
+                declared.cargo =
+                    Some(MemberPatterns::new(&members, &exclude).with_context(|| {
+                        format!("Invalid workspace members in {}", cargo_manifest.display())
+                    })?);
+            }
+        }
+
+        let pnpm_manifest = root.join("pnpm-workspace.yaml");
+        let node_manifest = root.join("package.json");
+        let node_patterns = if pnpm_manifest.is_file() {
+            Some((
+                pnpm_workspace::parse_packages(&pnpm_manifest)?,
+                pnpm_manifest,
+            ))
+        } else if node_manifest.is_file() {
+            package_json::parse_workspaces(&node_manifest)?.map(|p| (p, node_manifest))
+        } else {
+            None
+        };
+
+        if let Some((patterns, manifest)) = node_patterns {
+            let (exclude, include): (Vec<String>, Vec<String>) =
+                patterns.into_iter().partition(|p| p.starts_with('!'));
+            let exclude = exclude
+                .iter()
+                .map(|p| p.trim_start_matches('!').to_string())
+                .collect::<Vec<_>>();
+            declared.node = Some(
+                MemberPatterns::new(&include, &exclude)
+                    .with_context(|| format!("Invalid workspaces in {}", manifest.display()))?,
+            );
+        }
+
+        Ok(declared)
+    }
+
+    /// Additionally skip package directories matching any of `patterns`, in every ecosystem
+    pub fn with_exclude(mut self, patterns: &[String]) -> Result<Self> {
+        if !patterns.is_empty() {
+            self.exclude = Some(build_glob_set(patterns)?);
+        }
+        Ok(self)
+    }
+
+    /// Check whether a package directory (relative to the root) is a member
+    ///
+    /// The root directory itself is always a member.
+    pub fn includes(&self, ecosystem: Ecosystem, relative_dir: &Path) -> bool {
+        if relative_dir.as_os_str().is_empty() {
+            return true;
+        }
+
+        if let Some(exclude) = &self.exclude {
+            if exclude.is_match(relative_dir) {
+                return false;
+            }
+        }
+
+        let patterns = match ecosystem {
+            Ecosystem::Cargo => &self.cargo,
+            Ecosystem::Node => &self.node,
+            Ecosystem::Python => &None,
+        };
+
+        match patterns {
+            Some(patterns) => patterns.matches(relative_dir),
+            None => true,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct MemberPatterns {
+    include: GlobSet,
+    exclude: GlobSet,
+}
+
+impl MemberPatterns {
+    fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        Ok(Self {
+            include: build_glob_set(include)?,
+            exclude: build_glob_set(exclude)?,
+        })
+    }
+
+    fn matches(&self, relative_dir: &Path) -> bool {
+        self.include.is_match(relative_dir) && !self.exclude.is_match(relative_dir)
+    }
+}
+
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = GlobBuilder::new(normalize(pattern))
+            .literal_separator(true)
+            .build()
+            .with_context(|| format!("Invalid glob pattern '{}'", pattern))?;
+        builder.add(glob);
+    }
+    Ok(builder.build()?)
+}
+
+/// Strip a leading `./` and trailing `/` so patterns line up with relative paths
+fn normalize(pattern: &str) -> &str {
+    let pattern = pattern.strip_prefix("./").unwrap_or(pattern);
+    pattern.strip_suffix('/').unwrap_or(pattern)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_cargo_members_and_exclude() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\", \"./tools/cli/\"]\nexclude = [\"crates/experimental\"]\n",
+        )
+        .unwrap();
+
+        let declared = DeclaredMembers::read(dir.path()).unwrap();
+        assert!(declared.includes(Ecosystem::Cargo, Path::new("")));
+        assert!(declared.includes(Ecosystem::Cargo, Path::new("crates/core")));
+        assert!(declared.includes(Ecosystem::Cargo, Path::new("tools/cli")));
+        assert!(!declared.includes(Ecosystem::Cargo, Path::new("crates/core/examples/demo")));
+        assert!(!declared.includes(Ecosystem::Cargo, Path::new("crates/experimental")));
+        assert!(!declared.includes(Ecosystem::Cargo, Path::new("vendor/serde")));
+        // Node has no declaration, so it is not restricted
+        assert!(declared.includes(Ecosystem::Node, Path::new("vendor/left-pad")));
+    }
+
+    #[test]
+    fn test_node_workspaces_with_negation() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{ "workspaces": ["packages/**", "!packages/**/fixtures/**"] }"#,
+        )
+        .unwrap();
+
+        let declared = DeclaredMembers::read(dir.path()).unwrap();
+        assert!(declared.includes(Ecosystem::Node, Path::new("packages/a")));
+        assert!(declared.includes(Ecosystem::Node, Path::new("packages/scoped/b")));
+        assert!(!declared.includes(Ecosystem::Node, Path::new("packages/a/fixtures/app")));
+        assert!(!declared.includes(Ecosystem::Node, Path::new("examples/demo")));
+        assert!(declared.includes(Ecosystem::Cargo, Path::new("examples/demo")));
+    }
+
+    #[test]
+    fn test_pnpm_workspace_takes_precedence() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{ "workspaces": ["packages/*"] }"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("pnpm-workspace.yaml"),
+            "packages:\n  - 'apps/*'\n",
+        )
+        .unwrap();
+
+        let declared = DeclaredMembers::read(dir.path()).unwrap();
+        assert!(declared.includes(Ecosystem::Node, Path::new("apps/web")));
+        assert!(!declared.includes(Ecosystem::Node, Path::new("packages/a")));
+    }
+
+    #[test]
+    fn test_configured_exclude() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .unwrap();
+
+        let declared = DeclaredMembers::read(dir.path())
+            .unwrap()
+            .with_exclude(&["crates/legacy-*".to_string(), "examples/**".to_string()])
+            .unwrap();
+        assert!(declared.includes(Ecosystem::Cargo, Path::new("")));
+        assert!(declared.includes(Ecosystem::Cargo, Path::new("crates/core")));
+        assert!(!declared.includes(Ecosystem::Cargo, Path::new("crates/legacy-api")));
+        assert!(!declared.includes(Ecosystem::Node, Path::new("examples/web")));
+        assert!(declared.includes(Ecosystem::Node, Path::new("packages/web")));
+    }
+
+    #[test]
+    fn test_no_declarations() {
+        let dir = tempdir().unwrap();
+        let declared = DeclaredMembers::read(dir.path()).unwrap();
+        assert!(declared.includes(Ecosystem::Cargo, Path::new("anything/at/all")));
+        assert!(declared.includes(Ecosystem::Node, Path::new("anything/at/all")));
+        assert!(declared.includes(Ecosystem::Python, Path::new("anything/at/all")));
+    }
+}
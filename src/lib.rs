@@ -2,53 +2,238 @@ pub mod cli;
 pub mod domain;
 pub mod io;
 
-use clap::Parser;
-use cli::{Cli, Commands, OutputFormat};
+use clap::{Parser, ValueEnum};
+use cli::{Cli, Commands, OutputFormat, WriteOptions};
+use domain::scheme::{Clock, SystemClock};
+use io::config::{Config, DefaultSelection};
+use serde::Serialize;
 
 pub fn run() {
     let cli = Cli::parse();
 
-    let result = match cli.command {
+    let result = io::load_config().and_then(|config| run_command(cli.command, &config));
+
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run_command(command: Commands, config: &Config) -> anyhow::Result<()> {
+    match command {
+        Commands::Roll {
+            bump_type:
+                cli::BumpType::Auto {
+                    package_selection,
+                    ignore_options,
+                    format,
+                    write_options,
+                },
+        } => handle_roll_auto(
+            resolve_selection(package_selection.into(), config, DefaultSelection::Root),
+            resolve_format(format, config)?,
+            &write_options,
+            &ignore_options.with_config(config),
+            config,
+        ),
         Commands::Roll { bump_type } => {
-            let (bump, selection, ignore_options, format) = bump_type.into();
-            handle_roll(bump, selection, format, &ignore_options)
+            let (bump, selection, ignore_options, format, write_options) = bump_type.into();
+            handle_roll(
+                bump,
+                resolve_selection(selection, config, DefaultSelection::Root),
+                resolve_format(format, config)?,
+                &write_options,
+                &ignore_options.with_config(config),
+                config,
+            )
         }
         Commands::Set {
             version,
             package_selection,
             ignore_options,
             format,
-        } => handle_set(version, package_selection.into(), format, &ignore_options),
+            write_options,
+        } => handle_set(
+            version,
+            resolve_selection(package_selection.into(), config, DefaultSelection::Root),
+            resolve_format(format, config)?,
+            &write_options,
+            &ignore_options.with_config(config),
+            config,
+        ),
         Commands::Sync {
             version,
             ignore_options,
             format,
-        } => handle_sync(version, format, &ignore_options),
+            write_options,
+        } => handle_sync(
+            version,
+            resolve_format(format, config)?,
+            &write_options,
+            &ignore_options.with_config(config),
+            config,
+        ),
+        // If no specific selection is made, show and lint all members
         Commands::Show {
+            format,
             package_selection,
             ignore_options,
-        } => handle_show(package_selection.into(), &ignore_options),
+        } => handle_show(
+            resolve_selection(
+                package_selection.into(),
+                config,
+                DefaultSelection::Workspace,
+            ),
+            format,
+            &ignore_options.with_config(config),
+            config,
+        ),
         Commands::Lint {
+            lockstep,
+            fix,
+            format,
             package_selection,
             ignore_options,
-        } => handle_lint(package_selection.into(), &ignore_options),
-    };
+        } => handle_lint(
+            resolve_selection(
+                package_selection.into(),
+                config,
+                DefaultSelection::Workspace,
+            ),
+            lockstep || config.lockstep,
+            fix,
+            format,
+            &ignore_options.with_config(config),
+            config,
+        ),
+        Commands::Graph {
+            format,
+            ignore_options,
+        } => handle_graph(format, &ignore_options.with_config(config), config),
+        Commands::Changeset { command } => match command {
+            cli::ChangesetCommand::Add {
+                level,
+                message,
+                package_selection,
+                ignore_options,
+            } => handle_changeset_add(
+                level.into(),
+                &message,
+                resolve_selection(package_selection.into(), config, DefaultSelection::Root),
+                &ignore_options.with_config(config),
+                config,
+            ),
+            cli::ChangesetCommand::Version {
+                format,
+                write_options,
+                ignore_options,
+            } => handle_changeset_version(
+                resolve_format(format, config)?,
+                &write_options,
+                &ignore_options.with_config(config),
+                config,
+            ),
+            cli::ChangesetCommand::Status {
+                since,
+                ignore_options,
+            } => handle_changeset_status(&since, &ignore_options.with_config(config), config),
+        },
+    }
+}
 
-    if let Err(e) = result {
-        eprintln!("Error: {}", e);
-        std::process::exit(1);
+/// Apply the configured default selection when no selection flag was given
+fn resolve_selection(
+    selection: domain::PackageSelection,
+    config: &Config,
+    fallback: DefaultSelection,
+) -> domain::PackageSelection {
+    match selection {
+        domain::PackageSelection::Default => match config.default_selection.unwrap_or(fallback) {
+            DefaultSelection::Root => domain::PackageSelection::Default,
+            DefaultSelection::Workspace => domain::PackageSelection::Workspace,
+        },
+        _ => selection,
+    }
+}
+
+/// Resolve `--changed-since` into the files changed since the git ref
+fn resolve_changes(
+    selection: domain::PackageSelection,
+    workspace: &domain::Workspace,
+) -> anyhow::Result<domain::PackageSelection> {
+    match selection {
+        domain::PackageSelection::ChangedSince {
+            reference,
+            include_dependents,
+        } => {
+            let current_dir = std::env::current_dir()?;
+            let files = io::git::changed_files(repo_dir(workspace, &current_dir), &reference)?;
+            Ok(domain::PackageSelection::Changed {
+                files,
+                include_dependents,
+            })
+        }
+        _ => Ok(selection),
+    }
+}
+
+/// Use `--format` if given, else the configured format, else the default
+fn resolve_format(format: Option<OutputFormat>, config: &Config) -> anyhow::Result<OutputFormat> {
+    match (format, &config.format) {
+        (Some(format), _) => Ok(format),
+        (None, Some(name)) => OutputFormat::from_str(name, true)
+            .map_err(|e| anyhow::anyhow!("Invalid format '{}' in configuration: {}", name, e)),
+        (None, None) => Ok(OutputFormat::default()),
     }
 }
 
 fn display_operation_result(result: &domain::OperationResult, format: &OutputFormat) {
     match format {
         OutputFormat::Simple => {
+            for decision in &result.decisions {
+                if let Some(root) = &decision.inherits {
+                    println!("{}: inherits the version of {}", decision.package, root);
+                    continue;
+                }
+                let since = match &decision.since {
+                    Some(tag) => format!("since {}", tag),
+                    None => "full history".to_string(),
+                };
+                match &decision.bump {
+                    Some(bump) => println!("{}: {} ({})", decision.package, bump, since),
+                    None => println!("{}: no release needed ({})", decision.package, since),
+                }
+                for commit in &decision.commits {
+                    println!(
+                        "  {} {}",
+                        &commit.hash[..commit.hash.len().min(7)],
+                        commit.summary
+                    );
+                }
+            }
             for change in &result.changes {
                 println!(
                     "{}: {} → {}",
                     change.package, change.old_version, change.new_version
                 );
             }
+            for propagated in &result.propagated {
+                let change = &propagated.change;
+                println!(
+                    "{}: {} → {} (depends on {})",
+                    change.package, change.old_version, change.new_version, propagated.dependency
+                );
+            }
+            for change in &result.dependency_changes {
+                println!(
+                    "{}: {}.{} {} → {}",
+                    change.package,
+                    change.table,
+                    change.dependency,
+                    change.old_requirement,
+                    change.new_requirement
+                );
+            }
         }
         OutputFormat::Json => match serde_json::to_string_pretty(result) {
             Ok(json) => println!("{}", json),
@@ -57,17 +242,577 @@ fn display_operation_result(result: &domain::OperationResult, format: &OutputFor
     }
 }
 
+#[derive(Serialize)]
+struct SavedOutput<'a> {
+    #[serde(flatten)]
+    result: &'a domain::OperationResult,
+    files: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commit: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<Vec<&'a str>>,
+}
+
+fn display_saved(
+    result: &domain::OperationResult,
+    files: &[String],
+    commit: Option<&str>,
+    tags: Option<&[io::git::Tag]>,
+    format: &OutputFormat,
+) {
+    match format {
+        OutputFormat::Simple => {
+            display_operation_result(result, format);
+            for file in files {
+                println!("Wrote {}", file);
+            }
+            if let Some(commit) = commit {
+                println!("Committed {}", &commit[..commit.len().min(7)]);
+            }
+            for tag in tags.unwrap_or_default() {
+                println!("Tagged {}", tag.name);
+            }
+        }
+        OutputFormat::Json => {
+            let output = SavedOutput {
+                result,
+                files,
+                commit,
+                tags: tag_names(tags),
+            };
+            match serde_json::to_string_pretty(&output) {
+                Ok(json) => println!("{}", json),
+                Err(e) => eprintln!("Error serializing to JSON: {}", e),
+            }
+        }
+    }
+}
+
+fn tag_names(tags: Option<&[io::git::Tag]>) -> Option<Vec<&str>> {
+    tags.map(|tags| tags.iter().map(|tag| tag.name.as_str()).collect())
+}
+
+/// A manifest diff, as reported by `--dry-run` and `--check`
+#[derive(Serialize)]
+struct FileDiff {
+    path: String,
+    diff: String,
+}
+
+#[derive(Serialize)]
+struct DryRunOutput<'a> {
+    #[serde(flatten)]
+    result: &'a domain::OperationResult,
+    diffs: &'a [FileDiff],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commit_message: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<Vec<&'a str>>,
+}
+
+fn display_dry_run(
+    result: &domain::OperationResult,
+    diffs: &[FileDiff],
+    commit_message: Option<&str>,
+    tags: Option<&[io::git::Tag]>,
+    format: &OutputFormat,
+) {
+    match format {
+        OutputFormat::Simple => {
+            display_operation_result(result, format);
+            if let Some(message) = commit_message {
+                println!(
+                    "Would commit: {}",
+                    message.lines().next().unwrap_or_default()
+                );
+            }
+            for tag in tags.unwrap_or_default() {
+                println!("Would tag {}", tag.name);
+            }
+            for diff in diffs {
+                print!("{}", diff.diff);
+            }
+        }
+        OutputFormat::Json => {
+            let output = DryRunOutput {
+                result,
+                diffs,
+                commit_message,
+                tags: tag_names(tags),
+            };
+            match serde_json::to_string_pretty(&output) {
+                Ok(json) => println!("{}", json),
+                Err(e) => eprintln!("Error serializing to JSON: {}", e),
+            }
+        }
+    }
+}
+
+/// The directory git commands run in: the workspace root, else the current directory
+fn repo_dir<'a>(
+    workspace: &'a domain::Workspace,
+    current_dir: &'a std::path::Path,
+) -> &'a std::path::Path {
+    workspace
+        .root
+        .as_deref()
+        .and_then(|root| root.parent())
+        .unwrap_or(current_dir)
+}
+
+/// Use `--tag-template` if given, else the configured template, else the default
+fn tag_template<'a>(write_options: &'a WriteOptions, config: &'a Config) -> &'a str {
+    write_options
+        .tag_template
+        .as_deref()
+        .or(config.tag_template.as_deref())
+        .unwrap_or(io::git::DEFAULT_TAG_TEMPLATE)
+}
+
+/// Use `--cascade` if given, else the configured cascade level, if any
+fn cascade(write_options: &WriteOptions, config: &Config) -> Option<domain::Cascade> {
+    let level = write_options.cascade.map(Into::into).or(config.cascade)?;
+    Some(domain::Cascade {
+        level,
+        zero_major: config.zero_major,
+    })
+}
+
+/// Save the workspace, or with `--dry-run`/`--check` only report what would change
+fn apply_changes(
+    workspace: &domain::Workspace,
+    result: &domain::OperationResult,
+    format: &OutputFormat,
+    write_options: &WriteOptions,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let repo_dir = repo_dir(workspace, &current_dir);
+
+    let mut updates = io::render_workspace(workspace)?;
+    if write_options.changelog || config.changelog {
+        let template = tag_template(write_options, config);
+        updates.extend(render_changelogs(workspace, result, |member, change| {
+            let (_, commits) =
+                package_history(workspace, member, &change.old_version, repo_dir, template)?;
+            Ok(io::changelog::entries_from_commits(&commits))
+        })?);
+    }
+
+    apply_updates(workspace, result, updates, format, write_options, config)
+}
+
+/// Write `updates`, committing and tagging as requested, or with
+/// `--dry-run`/`--check` only report what would change
+fn apply_updates(
+    workspace: &domain::Workspace,
+    result: &domain::OperationResult,
+    updates: Vec<io::FileUpdate>,
+    format: &OutputFormat,
+    write_options: &WriteOptions,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let repo_dir = repo_dir(workspace, &current_dir);
+
+    // Refuse a missing repository, a dirty working directory or existing
+    // tags before anything is written
+    if (write_options.commit || write_options.tag) && !updates.is_empty() {
+        io::git::ensure_repo(repo_dir)?;
+    }
+    let commit_message = if write_options.commit && !updates.is_empty() {
+        if !write_options.allow_dirty {
+            io::git::ensure_clean(repo_dir)?;
+        }
+        let template = write_options
+            .commit_message
+            .as_deref()
+            .or(config.commit_message.as_deref())
+            .unwrap_or(io::git::DEFAULT_COMMIT_TEMPLATE);
+        Some(io::git::commit_message(template, result))
+    } else {
+        None
+    };
+
+    let tags = if write_options.tag {
+        let tags = io::git::plan_tags(
+            tag_template(write_options, config),
+            &result.all_changes().cloned().collect::<Vec<_>>(),
+        )?;
+        io::git::ensure_tags_available(repo_dir, &tags)?;
+        Some(tags)
+    } else {
+        None
+    };
+
+    let display_path = |path: &std::path::Path| {
+        path.strip_prefix(&current_dir)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    };
+
+    if !write_options.dry_run && !write_options.check {
+        let written = io::write_updates(&updates)?;
+        let commit = match &commit_message {
+            Some(message) => Some(io::git::commit_files(repo_dir, &written, message)?),
+            None => None,
+        };
+        for tag in tags.iter().flatten() {
+            io::git::create_tag(repo_dir, tag)?;
+        }
+
+        let files = written
+            .iter()
+            .map(|path| display_path(path))
+            .collect::<Vec<_>>();
+        display_saved(result, &files, commit.as_deref(), tags.as_deref(), format);
+        return Ok(());
+    }
+
+    let diffs = updates
+        .iter()
+        .map(|update| {
+            let path = display_path(&update.path);
+            FileDiff {
+                diff: update.unified_diff(&path),
+                path,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    display_dry_run(
+        result,
+        &diffs,
+        commit_message.as_deref(),
+        tags.as_deref(),
+        format,
+    );
+
+    if write_options.check && !updates.is_empty() {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
 fn handle_roll(
     bump: domain::VersionBump,
     selection: domain::PackageSelection,
     format: OutputFormat,
+    write_options: &WriteOptions,
     ignore_options: &cli::IgnoreOptions,
+    config: &Config,
 ) -> anyhow::Result<()> {
-    let mut workspace = io::load_workspace(ignore_options)?;
+    let mut workspace = io::load_workspace(ignore_options, config)?;
+    workspace.cascade = cascade(write_options, config);
+    let selection = resolve_changes(selection, &workspace)?;
     let result = workspace.roll_version(bump, &selection)?;
-    io::save_workspace(&workspace)?;
+    apply_changes(&workspace, &result, &format, write_options, config)
+}
+
+fn handle_roll_auto(
+    selection: domain::PackageSelection,
+    format: OutputFormat,
+    write_options: &WriteOptions,
+    ignore_options: &cli::IgnoreOptions,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let mut workspace = io::load_workspace(ignore_options, config)?;
+    workspace.cascade = cascade(write_options, config);
+    let selection = resolve_changes(selection, &workspace)?;
+    let current_dir = std::env::current_dir()?;
+    let repo_dir = repo_dir(&workspace, &current_dir);
+    let template = tag_template(write_options, config);
+
+    let selected = workspace.select(&selection)?;
+    let root = workspace
+        .inheritance_root()
+        .filter(|root| workspace.resolved_version(root).is_some());
+    let is_root = |member: &domain::WorkspaceMember| {
+        root.is_some_and(|root| root.manifest_path() == member.manifest_path())
+    };
+    let root_selected = selected.iter().any(|member| is_root(member));
+
+    let mut decisions = Vec::new();
+    for member in &selected {
+        let version = match member.version() {
+            domain::VersionField::Concrete(version) if !is_root(member) => version,
+            _ => continue,
+        };
+
+        let (since, commits) = package_history(&workspace, member, version, repo_dir, template)?;
+        decisions.push(domain::conventional::BumpDecision::from_commits(
+            member.name(),
+            since,
+            &commits,
+        ));
+    }
+
+    // Members inheriting the root's version are released with it, so their
+    // commits count toward the root: all of them when the root is selected
+    if let Some(root) = root {
+        let inheriting = workspace
+            .members
+            .iter()
+            .filter(|member| *member.version() == domain::VersionField::Inherited)
+            .filter(|member| {
+                root_selected
+                    || selected
+                        .iter()
+                        .any(|s| s.manifest_path() == member.manifest_path())
+            })
+            .collect::<Vec<_>>();
+
+        if root_selected || !inheriting.is_empty() {
+            let version = workspace.resolved_version(root).unwrap_or_default();
+            let (since, mut commits) =
+                package_history(&workspace, root, version, repo_dir, template)?;
+            for member in inheriting {
+                let nested = nested_members(&workspace, member);
+                let member_commits =
+                    io::git::commits_since(repo_dir, since.as_deref(), member.path(), &nested)?;
+                for commit in member_commits {
+                    if !commits.iter().any(|c| c.hash == commit.hash) {
+                        commits.push(commit);
+                    }
+                }
+                decisions.push(domain::conventional::BumpDecision::inherited(
+                    member.name(),
+                    root.name(),
+                ));
+            }
+            decisions.push(domain::conventional::BumpDecision::from_commits(
+                root.name(),
+                since,
+                &commits,
+            ));
+        }
+    }
+
+    let result = workspace.roll_auto(decisions, config.zero_major)?;
+    apply_changes(&workspace, &result, &format, write_options, config)
+}
+
+/// The last release tag of `member` at `version`, and the commits since then
+/// that touch files under its directory
+fn package_history(
+    workspace: &domain::Workspace,
+    member: &domain::WorkspaceMember,
+    version: &str,
+    repo_dir: &std::path::Path,
+    template: &str,
+) -> anyhow::Result<(Option<String>, Vec<domain::conventional::Commit>)> {
+    let nested = nested_members(workspace, member);
+    let since = io::git::last_release_tag(repo_dir, template, member.name(), version)?;
+    let commits = io::git::commits_since(repo_dir, since.as_deref(), member.path(), &nested)?;
+    Ok((since, commits))
+}
+
+/// The directories of the members nested inside `member`, whose commits belong to them
+fn nested_members(
+    workspace: &domain::Workspace,
+    member: &domain::WorkspaceMember,
+) -> Vec<std::path::PathBuf> {
+    workspace
+        .members
+        .iter()
+        .filter(|other| other.path() != member.path() && other.path().starts_with(member.path()))
+        .map(|other| other.path().clone())
+        .collect()
+}
+
+/// Compute a changelog section for every version change, with entries from
+/// `entries_for`
+fn render_changelogs(
+    workspace: &domain::Workspace,
+    result: &domain::OperationResult,
+    entries_for: impl Fn(
+        &domain::WorkspaceMember,
+        &domain::VersionChange,
+    ) -> anyhow::Result<Vec<io::changelog::Entry>>,
+) -> anyhow::Result<Vec<io::FileUpdate>> {
+    let date = SystemClock.today().to_string();
+
+    let mut updates = Vec::new();
+    for change in result.all_changes() {
+        let member = match workspace
+            .members
+            .iter()
+            .find(|m| m.name() == change.package && *m.path() == change.path)
+        {
+            Some(member) => member,
+            None => continue,
+        };
+
+        let entries = entries_for(member, change)?;
+        updates.push(io::changelog::render_changelog(
+            &change.path,
+            &change.new_version,
+            &date,
+            &entries,
+            workspace.scheme(member),
+        )?);
+    }
+    Ok(updates)
+}
+
+/// Fail if a changeset names a package that isn't in the workspace
+fn ensure_changeset_packages(
+    workspace: &domain::Workspace,
+    files: &[io::changeset::ChangesetFile],
+) -> anyhow::Result<()> {
+    for file in files {
+        for (package, _) in &file.changeset.releases {
+            if !workspace.members.iter().any(|m| m.name() == package) {
+                anyhow::bail!(
+                    "Changeset {} names package '{}', which is not in the workspace",
+                    file.path.display(),
+                    package
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn handle_changeset_add(
+    level: domain::conventional::Level,
+    message: &str,
+    selection: domain::PackageSelection,
+    ignore_options: &cli::IgnoreOptions,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let workspace = io::load_workspace(ignore_options, config)?;
+    let selection = resolve_changes(selection, &workspace)?;
+    let current_dir = std::env::current_dir()?;
+
+    let releases = workspace
+        .select(&selection)?
+        .iter()
+        .map(|member| (member.name().to_string(), level))
+        .collect::<Vec<_>>();
+    if releases.is_empty() {
+        anyhow::bail!("No packages selected for the changeset");
+    }
+    if message.trim().is_empty() {
+        anyhow::bail!("The changeset summary must not be empty");
+    }
+
+    let path = io::changeset::write_new(repo_dir(&workspace, &current_dir), releases, message)?;
+    println!(
+        "Created {}",
+        path.strip_prefix(&current_dir).unwrap_or(&path).display()
+    );
+    Ok(())
+}
+
+fn handle_changeset_version(
+    format: OutputFormat,
+    write_options: &WriteOptions,
+    ignore_options: &cli::IgnoreOptions,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let mut workspace = io::load_workspace(ignore_options, config)?;
+    workspace.cascade = cascade(write_options, config);
+    let current_dir = std::env::current_dir()?;
+    let root = repo_dir(&workspace, &current_dir).to_path_buf();
+
+    let files = io::changeset::read_all(&root)?;
+    if files.is_empty() {
+        println!("No pending changesets in {}", io::changeset::CHANGESET_DIR);
+        return Ok(());
+    }
+    ensure_changeset_packages(&workspace, &files)?;
+
+    let changesets = files
+        .iter()
+        .map(|file| file.changeset.clone())
+        .collect::<Vec<_>>();
+    let levels = domain::changeset::highest_levels(&changesets);
+    // Changesets name the exact level to release, so 0.x versions aren't shifted
+    let result = workspace.roll_levels(
+        "changeset version",
+        &levels,
+        domain::conventional::ZeroMajor::Normal,
+    )?;
+
+    let mut updates = io::render_workspace(&workspace)?;
+    // The root's release also carries the changesets of members inheriting its version
+    let inheriting = workspace
+        .members
+        .iter()
+        .filter(|member| *member.version() == domain::VersionField::Inherited)
+        .collect::<Vec<_>>();
+    updates.extend(render_changelogs(&workspace, &result, |member, _| {
+        let mut entries = io::changelog::entries_from_changesets(member.name(), &changesets);
+        if workspace
+            .inheritance_root()
+            .is_some_and(|root| root.manifest_path() == member.manifest_path())
+        {
+            for inherits in &inheriting {
+                entries.extend(io::changelog::entries_from_changesets(
+                    inherits.name(),
+                    &changesets,
+                ));
+            }
+        }
+        Ok(entries)
+    })?);
+    updates.extend(files.iter().map(|file| file.deletion()));
+
+    apply_updates(&workspace, &result, updates, &format, write_options, config)
+}
+
+fn handle_changeset_status(
+    since: &str,
+    ignore_options: &cli::IgnoreOptions,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let workspace = io::load_workspace(ignore_options, config)?;
+    let current_dir = std::env::current_dir()?;
+    let root = repo_dir(&workspace, &current_dir);
+
+    let files = io::changeset::read_all(root)?;
+    ensure_changeset_packages(&workspace, &files)?;
+    let changesets = files
+        .iter()
+        .map(|file| file.changeset.clone())
+        .collect::<Vec<_>>();
+    let levels = domain::changeset::highest_levels(&changesets);
+
+    // Adding a changeset is not itself a change that needs one
+    let changeset_dir = root.join(io::changeset::CHANGESET_DIR);
+    let files_changed = io::git::changed_files(root, since)?
+        .into_iter()
+        .filter(|file| !file.starts_with(&changeset_dir))
+        .collect();
+    let changed = workspace.select(&domain::PackageSelection::Changed {
+        files: files_changed,
+        include_dependents: false,
+    })?;
+    let missing = changed
+        .iter()
+        .filter(|member| !levels.iter().any(|(name, _)| name == member.name()))
+        .collect::<Vec<_>>();
+
+    if levels.is_empty() {
+        println!("No pending changesets");
+    } else {
+        println!("Pending releases:");
+        for (package, level) in &levels {
+            println!("  {}: {}", package, level);
+        }
+    }
+
+    if !missing.is_empty() {
+        eprintln!("❌ Changed since {} without a changeset:", since);
+        for member in missing {
+            eprintln!("  {}", member.name());
+        }
+        std::process::exit(1);
+    }
 
-    display_operation_result(&result, &format);
     Ok(())
 }
 
@@ -75,66 +820,175 @@ fn handle_set(
     version: String,
     selection: domain::PackageSelection,
     format: OutputFormat,
+    write_options: &WriteOptions,
     ignore_options: &cli::IgnoreOptions,
+    config: &Config,
 ) -> anyhow::Result<()> {
-    let mut workspace = io::load_workspace(ignore_options)?;
+    let mut workspace = io::load_workspace(ignore_options, config)?;
+    workspace.cascade = cascade(write_options, config);
+    let selection = resolve_changes(selection, &workspace)?;
     let result = workspace.set_version(&version, &selection)?;
-    io::save_workspace(&workspace)?;
-
-    display_operation_result(&result, &format);
-    Ok(())
+    apply_changes(&workspace, &result, &format, write_options, config)
 }
 
 fn handle_sync(
     version: String,
     format: OutputFormat,
+    write_options: &WriteOptions,
     ignore_options: &cli::IgnoreOptions,
+    config: &Config,
 ) -> anyhow::Result<()> {
-    let mut workspace = io::load_workspace(ignore_options)?;
+    let mut workspace = io::load_workspace(ignore_options, config)?;
     let result = workspace.sync_version(&version)?;
-    io::save_workspace(&workspace)?;
-
-    display_operation_result(&result, &format);
-    Ok(())
+    apply_changes(&workspace, &result, &format, write_options, config)
 }
 
 fn handle_show(
     selection: domain::PackageSelection,
+    format: cli::ShowFormat,
     ignore_options: &cli::IgnoreOptions,
+    config: &Config,
 ) -> anyhow::Result<()> {
-    let workspace = io::load_workspace(ignore_options)?;
+    let workspace = io::load_workspace(ignore_options, config)?;
+    let selection = resolve_changes(selection, &workspace)?;
 
-    // If no specific selection is made, show all members
-    let effective_selection = match selection {
-        domain::PackageSelection::Default => domain::PackageSelection::Workspace,
-        _ => selection,
-    };
+    let summaries = workspace.summarize(&selection)?;
+    let current_dir = std::env::current_dir()?;
+    match format {
+        cli::ShowFormat::Text => print!("{}", domain::show::to_text(&summaries)),
+        cli::ShowFormat::Json => println!("{}", domain::show::to_json(&summaries, &current_dir)?),
+        cli::ShowFormat::Table => print!("{}", domain::show::to_table(&summaries, &current_dir)),
+        cli::ShowFormat::Tree => print!("{}", domain::show::to_tree(&summaries, &current_dir)),
+        cli::ShowFormat::Tsv => print!("{}", domain::show::to_tsv(&summaries, &current_dir)),
+    }
+    Ok(())
+}
 
-    let output = workspace.show(&effective_selection)?;
-    print!("{}", output);
+fn handle_graph(
+    format: cli::GraphFormat,
+    ignore_options: &cli::IgnoreOptions,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let workspace = io::load_workspace(ignore_options, config)?;
+    let graph = domain::graph::DependencyGraph::from_workspace(&workspace);
+
+    match format {
+        cli::GraphFormat::Text => print!("{}", graph.to_text()),
+        cli::GraphFormat::Dot => print!("{}", graph.to_dot()),
+        cli::GraphFormat::Mermaid => print!("{}", graph.to_mermaid()),
+        cli::GraphFormat::Json => println!("{}", serde_json::to_string_pretty(&graph.report())?),
+    }
     Ok(())
 }
 
 fn handle_lint(
     selection: domain::PackageSelection,
+    lockstep: bool,
+    fix: bool,
+    format: cli::LintFormat,
     ignore_options: &cli::IgnoreOptions,
+    config: &Config,
 ) -> anyhow::Result<()> {
-    let workspace = io::load_workspace(ignore_options)?;
+    let options = domain::lint::LintOptions::new(lockstep, &config.lint)?;
+    let mut workspace = io::load_workspace(ignore_options, config)?;
+    let selection = resolve_changes(selection, &workspace)?;
 
-    // If no specific selection is made, lint all members
-    let effective_selection = match selection {
-        domain::PackageSelection::Default => domain::PackageSelection::Workspace,
-        _ => selection,
+    let mut diagnostics = workspace.lint(&selection, &options)?;
+
+    let current_dir = std::env::current_dir()?;
+    let describe = |diagnostic: &domain::lint::Diagnostic| {
+        let path = diagnostic
+            .path
+            .strip_prefix(&current_dir)
+            .unwrap_or(&diagnostic.path)
+            .display();
+        match &diagnostic.location {
+            Some(location) if diagnostic.path.as_os_str().is_empty() => {
+                format!(
+                    "{}: {} ({})",
+                    diagnostic.member, diagnostic.message, location
+                )
+            }
+            Some(location) => format!(
+                "{}: {} ({}: {})",
+                diagnostic.member, diagnostic.message, path, location
+            ),
+            None => format!("{}: {}", diagnostic.member, diagnostic.message),
+        }
     };
 
-    let errors = workspace.lint(&effective_selection)?;
+    if fix {
+        // A fix can reveal another problem (a member given a version may now be
+        // rejected by its dependents' requirements), so repeat until none apply
+        let mut fixed = Vec::new();
+        for _ in 0..domain::lint::RULES.len() {
+            let applied: Vec<_> = diagnostics
+                .into_iter()
+                .filter(|diagnostic| workspace.apply_fix(diagnostic))
+                .collect();
+            diagnostics = workspace.lint(&selection, &options)?;
+            if applied.is_empty() {
+                break;
+            }
+            fixed.extend(applied);
+        }
+        if !fixed.is_empty() {
+            io::write_updates(&io::render_workspace(&workspace)?)?;
+            for diagnostic in &fixed {
+                match format {
+                    cli::LintFormat::Text => println!("🔧 Fixed {}", describe(diagnostic)),
+                    _ => eprintln!("🔧 Fixed {}", describe(diagnostic)),
+                }
+            }
+        }
+    }
+
+    for diagnostic in &mut diagnostics {
+        diagnostic.line = diagnostic
+            .location
+            .as_deref()
+            .and_then(|location| io::key_line::locate(&diagnostic.path, location));
+    }
 
-    if errors.is_empty() {
-        println!("✅ All workspace versions are valid");
-    } else {
-        for error in errors {
-            eprintln!("❌ {}: {}", error.member, error.message);
+    // Machine-readable paths are relative to the repository root
+    let root = {
+        let mut root = repo_dir(&workspace, &current_dir).to_path_buf();
+        let prefix = io::git::repo_prefix(&root).unwrap_or_default();
+        for _ in std::path::Path::new(&prefix).components() {
+            root.pop();
         }
+        root
+    };
+
+    match format {
+        cli::LintFormat::Text if diagnostics.is_empty() => {
+            println!("✅ All workspace versions are valid");
+        }
+        cli::LintFormat::Text => {
+            for diagnostic in &diagnostics {
+                let icon = match diagnostic.severity {
+                    domain::lint::Severity::Error => "❌",
+                    domain::lint::Severity::Warning => "⚠️",
+                    domain::lint::Severity::Info => "ℹ️",
+                };
+                eprintln!("{} {} [{}]", icon, describe(diagnostic), diagnostic.rule);
+            }
+        }
+        cli::LintFormat::Json => {
+            let findings: Vec<_> = diagnostics.iter().map(|d| d.finding(&root)).collect();
+            println!("{}", serde_json::to_string_pretty(&findings)?);
+        }
+        cli::LintFormat::Sarif => println!(
+            "{}",
+            serde_json::to_string_pretty(&domain::lint::to_sarif(&diagnostics, &root))?
+        ),
+        cli::LintFormat::Github => print!("{}", domain::lint::to_github(&diagnostics, &root)),
+    }
+
+    if diagnostics
+        .iter()
+        .any(|d| d.severity == domain::lint::Severity::Error)
+    {
         std::process::exit(1);
     }
 
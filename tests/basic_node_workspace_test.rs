@@ -32,6 +32,11 @@ fn basic_node_workspace_test() {
             "bin2: 1.0.0 → 1.0.1",
             "lib1: 1.0.0 → 1.0.1",
             "lib2: 1.0.0 → 1.0.1",
+            "Wrote package.json",
+            "Wrote bin1/package.json",
+            "Wrote bin2/package.json",
+            "Wrote lib1/package.json",
+            "Wrote lib2/package.json",
         ],
         "stdout:\n{}",
         stdout.join("\n")
@@ -43,6 +48,7 @@ fn basic_node_workspace_test() {
         stdout,
         vec![
             "bin1: 1.0.1 → 1.1.0", //
+            "Wrote bin1/package.json",
         ],
         "stdout:\n{}",
         stdout.join("\n")
@@ -73,6 +79,11 @@ fn basic_node_workspace_test() {
             "bin2: 1.0.1 → 1.10.0",
             "lib1: 1.0.1 → 1.10.0",
             "lib2: 1.0.1 → 1.10.0",
+            "Wrote package.json",
+            "Wrote bin1/package.json",
+            "Wrote bin2/package.json",
+            "Wrote lib1/package.json",
+            "Wrote lib2/package.json",
         ],
         "stdout:\n{}",
         stdout.join("\n")
@@ -88,6 +99,24 @@ fn basic_node_workspace_test() {
             "bin2: 1.10.0 → 1.2.0",
             "lib1: 1.10.0 → 1.2.0",
             "lib2: 1.10.0 → 1.2.0",
+            "Wrote package.json",
+            "Wrote bin1/package.json",
+            "Wrote bin2/package.json",
+            "Wrote lib1/package.json",
+            "Wrote lib2/package.json",
+        ],
+        "stdout:\n{}",
+        stdout.join("\n")
+    );
+
+    let (success, stdout, stderr) = run_odo(&["roll", "patch"], &fixture_path.join("bin1"));
+    assert!(success, "odo roll patch failed:\n{}", stderr.join("\n"));
+    assert_eq!(
+        stdout,
+        vec![
+            "basic-node-workspace: 1.2.0 → 1.2.1".to_string(),
+            // Files outside the current directory are shown in full
+            format!("Wrote {}", fixture_path.join("package.json").display()),
         ],
         "stdout:\n{}",
         stdout.join("\n")
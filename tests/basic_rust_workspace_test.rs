@@ -30,6 +30,10 @@ fn basic_rust_workspace_test() {
             "bin2: 0.1.0 → 0.1.1",
             "lib1: 0.1.0 → 0.1.1",
             "lib2: 0.1.0 → 0.1.1",
+            "Wrote bin1/Cargo.toml",
+            "Wrote bin2/Cargo.toml",
+            "Wrote lib1/Cargo.toml",
+            "Wrote lib2/Cargo.toml",
         ],
         "stdout:\n{}",
         stdout.join("\n")
@@ -41,6 +45,7 @@ fn basic_rust_workspace_test() {
         stdout,
         vec![
             "bin1: 0.1.1 → 0.2.0", //
+            "Wrote bin1/Cargo.toml",
         ],
         "stdout:\n{}",
         stdout.join("\n")
@@ -69,6 +74,10 @@ fn basic_rust_workspace_test() {
             "bin2: 0.1.1 → 0.10.0",
             "lib1: 0.1.1 → 0.10.0",
             "lib2: 0.1.1 → 0.10.0",
+            "Wrote bin1/Cargo.toml",
+            "Wrote bin2/Cargo.toml",
+            "Wrote lib1/Cargo.toml",
+            "Wrote lib2/Cargo.toml",
         ],
         "stdout:\n{}",
         stdout.join("\n")
@@ -83,6 +92,61 @@ fn basic_rust_workspace_test() {
             "bin2: 0.10.0 → 0.8.0",
             "lib1: 0.10.0 → 0.8.0",
             "lib2: 0.10.0 → 0.8.0",
+            "Wrote bin1/Cargo.toml",
+            "Wrote bin2/Cargo.toml",
+            "Wrote lib1/Cargo.toml",
+            "Wrote lib2/Cargo.toml",
+        ],
+        "stdout:\n{}",
+        stdout.join("\n")
+    );
+
+    let (success, stdout, stderr) = run_odo(
+        &["roll", "patch", "--package", "bin1", "--dry-run"],
+        &fixture_path,
+    );
+    assert!(
+        success,
+        "odo roll patch --dry-run failed:\n{}",
+        stderr.join("\n")
+    );
+    assert_eq!(
+        stdout,
+        vec![
+            "bin1: 0.8.0 → 0.8.1", //
+            "--- a/bin1/Cargo.toml",
+            "+++ b/bin1/Cargo.toml",
+            "@@ -1,6 +1,6 @@",
+            " [package]",
+            " name = \"bin1\"",
+            "-version = \"0.8.0\"",
+            "+version = \"0.8.1\"",
+            " edition = \"2024\"",
+            " ",
+            " [dependencies]",
+        ],
+        "stdout:\n{}",
+        stdout.join("\n")
+    );
+
+    let (success, _, stderr) = run_odo(&["set", "0.8.0", "--workspace", "--check"], &fixture_path);
+    assert!(success, "odo set --check failed:\n{}", stderr.join("\n"));
+
+    let (success, _, _) = run_odo(&["set", "0.9.0", "--workspace", "--check"], &fixture_path);
+    assert!(
+        !success,
+        "odo set --check should fail when manifests would change"
+    );
+
+    let (success, stdout, stderr) = run_odo(&["show"], &fixture_path);
+    assert!(success, "odo show failed:\n{}", stderr.join("\n"));
+    assert_eq!(
+        stdout,
+        vec![
+            "bin1: 0.8.0", //
+            "bin2: 0.8.0",
+            "lib1: 0.8.0",
+            "lib2: 0.8.0",
         ],
         "stdout:\n{}",
         stdout.join("\n")